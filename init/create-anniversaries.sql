@@ -0,0 +1,5 @@
+create table if not exists anniversaries (
+    guild_id integer not null,
+    template text,
+    unique(guild_id)
+);
\ No newline at end of file
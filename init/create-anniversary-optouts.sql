@@ -0,0 +1,5 @@
+create table if not exists anniversary_optouts (
+    guild_id integer not null,
+    user_id integer not null,
+    unique(guild_id, user_id)
+);
\ No newline at end of file
@@ -0,0 +1,6 @@
+create table if not exists automations (
+    guild_id integer not null,
+    reactions text not null,
+    thread integer not null,
+    unique(guild_id)
+);
\ No newline at end of file
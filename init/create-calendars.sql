@@ -0,0 +1,6 @@
+create table if not exists calendars (
+    user_id integer not null,
+    guild_id integer not null,
+    calendar text not null,
+    unique(user_id, guild_id)
+);
\ No newline at end of file
@@ -0,0 +1,7 @@
+create table if not exists digests (
+    guild_id integer not null,
+    frequency text not null,
+    hour integer not null,
+    last_posted text,
+    unique(guild_id)
+);
\ No newline at end of file
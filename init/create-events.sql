@@ -0,0 +1,8 @@
+create table if not exists events (
+    guild_id integer not null,
+    name text not null,
+    description text,
+    user_id integer,
+    date text not null,
+    unique(guild_id, name)
+);
\ No newline at end of file
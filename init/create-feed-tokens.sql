@@ -0,0 +1,6 @@
+create table if not exists feed_tokens (
+    guild_id integer not null,
+    token text not null,
+    unique(guild_id),
+    unique(token)
+);
\ No newline at end of file
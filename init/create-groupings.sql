@@ -0,0 +1,5 @@
+create table if not exists groupings (
+    guild_id integer not null,
+    grouped integer not null,
+    unique(guild_id)
+);
\ No newline at end of file
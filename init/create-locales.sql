@@ -0,0 +1,5 @@
+create table if not exists locales (
+    guild_id integer not null,
+    locale text not null,
+    unique(guild_id)
+);
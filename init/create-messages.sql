@@ -0,0 +1,8 @@
+create table if not exists messages (
+    guild_id integer not null,
+    channel_id integer not null,
+    message_id integer not null,
+    thread_id integer,
+    sent_at text not null,
+    unique(message_id)
+);
\ No newline at end of file
@@ -0,0 +1,9 @@
+create table if not exists milestones (
+    guild_id integer not null,
+    rule text not null,
+    age integer not null,
+    colour integer,
+    title text,
+    role_id integer,
+    unique(guild_id, rule, age)
+);
\ No newline at end of file
@@ -0,0 +1,9 @@
+create table if not exists outbox (
+    guild_id integer not null,
+    channel_id integer not null,
+    kind text not null,
+    payload text not null,
+    attempts integer not null,
+    next_attempt text not null,
+    last_error text not null
+);
\ No newline at end of file
@@ -0,0 +1,9 @@
+create table if not exists pings (
+    guild_id integer not null,
+    celebrants integer not null,
+    role_id integer,
+    here integer not null,
+    everyone integer not null,
+    template text,
+    unique(guild_id)
+);
\ No newline at end of file
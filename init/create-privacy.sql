@@ -0,0 +1,6 @@
+create table if not exists privacy (
+    guild_id integer not null,
+    user_id integer not null,
+    exported boolean not null,
+    unique(guild_id, user_id)
+);
\ No newline at end of file
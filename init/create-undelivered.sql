@@ -0,0 +1,5 @@
+create table if not exists undelivered (
+    guild_id integer not null,
+    kind text not null,
+    payload text not null
+);
\ No newline at end of file
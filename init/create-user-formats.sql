@@ -0,0 +1,7 @@
+create table if not exists user_formats (
+    user_id integer not null,
+    date text not null,
+    clock text not null,
+    show_offset integer not null,
+    unique(user_id)
+);
\ No newline at end of file
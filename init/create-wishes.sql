@@ -0,0 +1,7 @@
+create table if not exists wishes (
+    guild_id integer not null,
+    user_id integer not null,
+    author_id integer not null,
+    message text not null,
+    unique(guild_id, user_id, author_id)
+);
\ No newline at end of file
@@ -0,0 +1,698 @@
+# German translations.
+#
+# Anything missing here falls back to English.
+
+## Shared
+
+yes = Ja
+no = Nein
+none = Keine
+default = Standard
+the-server = dem Server
+list-and = { $init } und { $last }
+
+field-age = Alter
+field-years = Jahre
+field-year = Jahr
+field-month = Monat
+field-day = Tag
+field-hour = Stunde
+field-minute = Minute
+field-second = Sekunde
+field-member = Mitglied
+field-template = Vorlage
+field-problem = Problem
+field-provided-time = Angegebene Zeit
+field-provided-value = Angegebener Wert
+field-missing-permissions = Fehlende Berechtigungen
+field-provided-birthday = Angegebener Geburtstag
+field-provided-date = Angegebenes Datum
+field-provided-reactions = Angegebene Reaktionen
+field-upcoming-birthdays = Anstehende Geburtstage
+field-reactions = Reaktionen
+field-thread = Thread
+field-frequency = Häufigkeit
+field-time = Uhrzeit
+field-token = Token
+field-feeds = Feeds
+field-celebrants = Geburtstagskinder
+field-role = Rolle
+field-everyone-allowed = `@everyone` erlaubt
+field-version = Version
+field-changelog = Änderungsprotokoll
+field-uptime = Laufzeit
+field-guilds = Server
+field-birthdays = Geburtstage
+field-guilds-with-birthdays = Server mit Geburtstagen
+field-database-size = Datenbankgröße
+field-log-level = Log-Level
+field-note = Hinweis
+field-date = Datum
+field-clock = Uhr
+field-offset = Versatz
+field-example = Beispiel
+
+invalid-date-description = Diese Kombination aus Jahr, Monat und Tag ist ungültig.
+invalid-time-description = Diese Kombination aus Stunde, Minute und Sekunde ist ungültig.
+
+button-confirm = Bestätigen
+button-cancel = Abbrechen
+
+## Months
+
+choice-january = Januar
+choice-february = Februar
+choice-march = März
+choice-april = April
+choice-may = Mai
+choice-june = Juni
+choice-july = Juli
+choice-august = August
+choice-september = September
+choice-october = Oktober
+choice-november = November
+choice-december = Dezember
+
+## Choices
+
+choice-daily = Täglich
+choice-weekly = Wöchentlich
+choice-monthly = Monatlich
+choice-exact-age = Genaues Alter
+choice-every-decade = Jedes Jahrzehnt
+choice-every-multiple-of-an-age = Jedes Vielfache eines Alters
+choice-birthdays = Geburtstage
+choice-announcements = Ankündigungen
+choice-customisation = Anpassung
+choice-events = Ereignisse
+choice-day-month-year = Tag Monat Jahr
+choice-month-day-year = Monat Tag Jahr
+choice-iso-8601 = ISO 8601
+choice-discord-timestamp = Discord-Zeitstempel
+choice-24-hour = 24 Stunden
+choice-12-hour = 12 Stunden
+choice-gregorian = Gregorianisch
+choice-chinese = Chinesisch
+choice-hebrew = Hebräisch
+choice-hijri = Hidschri
+
+## Errors
+
+error-invalid-data = Ungültige Daten
+error-invalid-data-description = Einige der für diesen Befehl gespeicherten Daten sind ungültig. Vielleicht wurde die Datenbank von Hand bearbeitet, oder meine Uhr geht falsch.
+error-poisoned-lock = Datenbank nicht verfügbar
+error-poisoned-lock-description = Die Datenbank ist nach einem früheren Fehler nicht mehr verfügbar. Ich muss neu gestartet werden, bevor ich wieder Befehle bearbeiten kann.
+error-ambiguous-time = Mehrdeutige Zeit
+error-ambiguous-time-description = Dieses Datum und diese Uhrzeit gibt es in dieser Zeitzone nicht oder zweimal.
+error-config = Fehlkonfiguriert
+error-config-description = Ich bin falsch konfiguriert und kann diesen Befehl daher nicht bearbeiten.
+error-command = Befehl fehlgeschlagen
+error-command-description = Beim Bearbeiten des Befehls ist ein Fehler aufgetreten: `{ $error }`
+error-panic = Befehl abgestürzt
+error-panic-description = Etwas ist schiefgelaufen und der Befehl ist abgestürzt. Das deutet auf einen Fehler in meinem Code hin - bitte [melde ihn auf GitHub](<https://github.com/Carnagion/birthbot/issues>).
+error-argument = Ungültige Eingabe
+error-argument-description = Eines der Argumente des Befehls ist ungültig.
+error-structure = Ungültiger Befehl
+error-structure-description = Dieser Befehl hat nicht die erwartete Struktur. Vermutlich wurden meine neuesten Befehle noch nicht bei Discord registriert.
+error-cooldown = Abklingzeit aktiv
+error-cooldown-description = Du bist zu schnell. Bitte warte { $seconds ->
+        [one] 1 Sekunde
+       *[other] { $seconds } Sekunden
+    }, bevor du es erneut versuchst.
+error-unauthorised = Nicht berechtigt
+error-bot-permissions-description = Mir fehlen die nötigen Berechtigungen, um diesen Befehl auszuführen.
+error-user-permissions-description = Dir fehlen die nötigen Berechtigungen, um diesen Befehl zu verwenden.
+error-not-an-owner-description = Nur Eigentümer können diesen Befehl verwenden.
+error-unavailable = Nicht verfügbar
+error-unavailable-description = Ich starte gerade neu. Bitte versuche es in etwa einer Minute erneut.
+error-context = Ungültiger Kontext
+error-guild-only-description = Diesen Befehl kannst du nur auf einem Server verwenden.
+error-dm-only-description = Diesen Befehl kannst du nur in Direktnachrichten verwenden.
+error-nsfw-only-description = Diesen Befehl kannst du nur in NSFW-Kanälen verwenden (:flushed:).
+
+## Birthdays
+
+birthday-retrieved = Geburtstag abgerufen
+birthday-get-own = Du bist am { $birthday } geboren.
+birthday-get-other = { $user } ist am { $birthday } geboren.
+birthday-unavailable = Geburtstag nicht verfügbar
+birthday-unavailable-own = Du hast noch keinen Geburtstag festgelegt.
+birthday-unavailable-own-help = Du hast noch keinen Geburtstag festgelegt. Mit `/geburtstag hilfe` erfährst du mehr.
+birthday-unavailable-other = { $user } hat noch keinen Geburtstag festgelegt.
+birthday-invalid = Ungültiger Geburtstag
+birthday-future-description = Zeitreisen gibt es noch nicht, also kann dein Geburtstag nicht in der Zukunft liegen.
+birthday-updated = Geburtstag aktualisiert
+birthday-updated-description = Dein Geburtstag wurde auf { $birthday } aktualisiert.
+birthday-in-calendar = { $date } (Kalender: { $calendar })
+date-unrecognized-description = Das sieht nicht nach einem Datum aus. Schreib es zum Beispiel als `14. März 1998`, `1998-03-14` oder `14.03.1998 10:30 +05:30`.
+date-year-missing-description = Dein Geburtstag braucht ein Jahr, damit dein Alter angezeigt werden kann. Schreib es zum Beispiel als `14. März 1998`.
+date-confirm = Geburtstag bestätigen
+date-confirm-description = Dieses Datum kann auf mehr als eine Weise gelesen werden. Meintest du { $birthday }? Falls nicht, schreib stattdessen den Namen des Monats aus.
+date-expired = Bestätigung abgelaufen
+date-expired-description = Dein Geburtstag wurde nicht aktualisiert. Verwende den Befehl erneut, um es noch einmal zu versuchen.
+date-cancelled = Geburtstag nicht aktualisiert
+date-cancelled-description = Dein Geburtstag wurde nicht aktualisiert. Schreib stattdessen den Namen des Monats aus, zum Beispiel `14. März 1998`.
+birthday-unset = Geburtstag entfernt
+birthday-unset-description = Dein Geburtstag wurde entfernt.
+birthdays-retrieved = Geburtstage abgerufen
+birthdays-retrieved-description = { $count ->
+        [one] 1 Geburtstag wird angezeigt.
+       *[other] { $count } Geburtstage werden angezeigt.
+    }
+birthdays-unavailable = Geburtstage nicht verfügbar
+birthdays-unavailable-description = Es wurden noch keine Geburtstage festgelegt.
+
+calendar-unavailable = Kalender nicht verfügbar
+calendar-unavailable-description = Es können noch keine Geburtstage exportiert werden.
+calendar-exported = Kalender exportiert
+calendar-exported-description = Importiere die angehängte Datei in deine Kalender-App, um { $count ->
+        [one] 1 Geburtstag
+       *[other] { $count } Geburtstage
+    } im Blick zu behalten. Ein erneuter Import aktualisiert die vorhandenen Termine.
+calendar-name = Geburtstage auf { $guild }
+calendar-summary = Geburtstag von { $name }
+
+wish-invalid = Ungültiger Wunsch
+wish-invalid-description = Du kannst dir nicht selbst Glückwünsche hinterlassen, so verlockend es auch ist.
+wish-early = Zu früh
+wish-early-description = Glückwünsche können höchstens { $days } Tage im Voraus hinterlassen werden. Der nächste Geburtstag von { $user } ist { $next }.
+wish-saved = Glückwunsch gespeichert
+wish-saved-description = Dein Glückwunsch wird { $user } am Geburtstag überbracht. Ein weiterer Glückwunsch ersetzt diesen.
+
+privacy-retrieved = Privatsphäre abgerufen
+privacy-retrieved-exported = Dein Geburtstag wird beim Export der Geburtstage des Servers einbezogen, etwa mit `/geburtstag kalender`.
+privacy-retrieved-hidden = Dein Geburtstag wird beim Export der Geburtstage des Servers ausgelassen.
+privacy-updated = Privatsphäre aktualisiert
+privacy-updated-exported = Dein Geburtstag wird ab jetzt beim Export der Geburtstage des Servers einbezogen.
+privacy-updated-hidden = Dein Geburtstag wird ab jetzt beim Export der Geburtstage des Servers ausgelassen.
+
+## Help
+
+help = Hilfe
+help-description = Hier ist eine Liste der verfügbaren Befehle zum Thema { $topic }. Mit `{ $usage }` siehst du andere Themen.
+help-topic-birthdays = Geburtstage
+help-topic-announcements = Ankündigungen
+help-topic-customisation = Anpassung
+help-topic-events = Ereignisse
+
+help-get = Den Geburtstag eines Mitglieds anzeigen
+    .notes = `[mitglied?]` ist standardmäßig du selbst.
+help-set = Deinen Geburtstag festlegen
+    .notes =
+        `[stunde?]`, `[minute?]` und `[sekunde?]` sind standardmäßig 0.
+        `[zeitzone?]` ist standardmäßig UTC (`+00:00`).
+        `[kalender?]` ist standardmäßig gregorianisch. Dein Geburtstag wird trotzdem als gregorianisches Datum angegeben und jedes Jahr am selben Datum im gewählten Kalender gefeiert.
+help-set-text = Deinen Geburtstag als Text festlegen
+    .notes =
+        `[datum]` akzeptiert Daten wie `14. März 1998`, `March 14, 1998`, `1998-03-14` oder `14.03.1998`, optional gefolgt von einer Uhrzeit und Zeitzone wie `10:30 +05:30`. Das Jahr ist erforderlich, da dein Alter angezeigt wird.
+        Daten wie `01.02.1998` werden in der Reihenfolge deines Datumsformats gelesen (siehe `/geburtstag format festlegen`), oder deiner Discord-Sprache, falls du keins gewählt hast, und müssen vor dem Speichern bestätigt werden.
+        `[kalender?]` ist standardmäßig gregorianisch.
+help-unset = Deinen Geburtstag entfernen
+help-list = Alle Geburtstage auflisten
+help-next = Anstehende Geburtstage auflisten
+    .notes = `[anzahl?]` ist standardmäßig 1.
+help-calendar = Alle Geburtstage als Kalenderdatei exportieren
+    .notes = Die Datei kann in Kalender-Apps wie Google Kalender oder Outlook importiert werden.
+help-privacy-get = Anzeigen, ob dein Geburtstag exportiert werden kann
+help-privacy-set = Festlegen, ob dein Geburtstag exportiert werden kann
+help-wish = Jemandem einen Geburtstagswunsch hinterlassen
+    .notes = Glückwünsche können bis zu 7 Tage vor einem Geburtstag hinterlassen werden und werden bei der Ankündigung überbracht.
+help-help = Diese Hilfe anzeigen
+    .notes = `[thema?]` ist standardmäßig Geburtstage.
+
+help-channel-get = Den Ankündigungskanal anzeigen
+help-channel-set = Den Ankündigungskanal festlegen
+help-channel-unset = Den Ankündigungskanal entfernen
+help-locale-get = Die Sprache der Ankündigungen anzeigen
+help-locale-set = Die Sprache der Ankündigungen festlegen
+    .notes = Antworten auf Befehle sind immer in deiner eigenen Sprache.
+help-locale-unset = Ankündigungen wieder in der bevorzugten Sprache des Servers machen
+    .notes = Die bevorzugte Sprache kann in den Community-Einstellungen des Servers festgelegt werden und ist standardmäßig Englisch.
+help-grouping-get = Anzeigen, ob Geburtstage aus derselben stündlichen Prüfung gemeinsam angekündigt werden
+help-grouping-set = Festlegen, ob Geburtstage aus derselben stündlichen Prüfung gemeinsam angekündigt werden
+help-digest-get = Anzeigen, wie oft Geburtstagsübersichten gepostet werden
+help-digest-set = Festlegen, wie oft Geburtstagsübersichten gepostet werden
+    .notes = `[stunde?]` ist in UTC und standardmäßig 0.
+help-digest-unset = Keine Geburtstagsübersichten mehr posten
+help-anniversary-get = Anzeigen, ob Server-Jubiläen angekündigt werden
+help-anniversary-set = Server-Jubiläen ankündigen
+    .notes = `[vorlage?]` unterstützt `{ "{" }user{ "}" }` und `{ "{" }years{ "}" }` und nutzt standardmäßig eine allgemeine Nachricht.
+help-anniversary-unset = Keine Server-Jubiläen mehr ankündigen
+help-anniversary-opt = Die Ankündigung deines Server-Jubiläums ab- oder wieder anmelden
+
+help-ping-get = Anzeigen, wer in Geburtstagsankündigungen erwähnt wird
+help-ping-set = Festlegen, wer in Geburtstagsankündigungen erwähnt wird
+    .notes =
+        `[geburtstagskinder?]` ist standardmäßig wahr, `[here?]` und `[everyone?]` standardmäßig falsch.
+        `[vorlage?]` unterstützt `{ "{" }users{ "}" }`, `{ "{" }role{ "}" }` und `{ "{" }here{ "}" }` und erwähnt standardmäßig alle.
+help-ping-unset = Zurücksetzen, wer in Geburtstagsankündigungen erwähnt wird
+help-automation-get = Anzeigen, was nach Geburtstagsankündigungen passiert
+help-automation-set = Festlegen, was nach Geburtstagsankündigungen passiert
+    .notes = `[reaktionen?]` ist standardmäßig leer, `[thread?]` standardmäßig falsch.
+help-automation-unset = Geburtstagsankündigungen nicht mehr nachbereiten
+help-milestone-list = Besonders gefeierte Alter auflisten
+help-milestone-add = Ein Alter besonders feiern
+    .notes =
+        `[alter?]` ist für genaue Alter und Vielfache eines Alters erforderlich.
+        `[titel?]` unterstützt `{ "{" }age{ "}" }`.
+help-milestone-remove = Ein Alter nicht mehr besonders feiern
+help-format-get = Anzeigen, wie dir Daten angezeigt werden
+help-format-set = Festlegen, wie dir Daten angezeigt werden
+    .notes =
+        `[uhr?]` ist standardmäßig 24 Stunden, `[versatz?]` standardmäßig wahr.
+        Discord-Zeitstempel werden in der Sprache und Zeitzone jedes Betrachters angezeigt.
+help-format-unset = Dir Daten wieder im Format des Servers anzeigen
+help-format-server = Festlegen oder zurücksetzen, wie Daten in Ankündigungen und Mitgliedern ohne eigenes Format angezeigt werden
+
+help-event-list = Die wiederkehrenden Ereignisse des Servers auflisten
+help-event-add = Ein wiederkehrendes Ereignis hinzufügen oder aktualisieren
+    .notes =
+        `[beschreibung?]` nutzt standardmäßig eine allgemeine Nachricht.
+        `[nutzer?]` wird erwähnt, wenn das Ereignis angekündigt wird.
+        `[zeitzone?]` ist standardmäßig `+00:00` (UTC).
+help-event-remove = Ein wiederkehrendes Ereignis entfernen
+
+## Announcement settings
+
+channel-retrieved = Kanal abgerufen
+channel-retrieved-description = Geburtstage und Updates werden in { $channel } angekündigt.
+channel-updated = Kanal aktualisiert
+channel-updated-description = Der Ankündigungskanal wurde auf { $channel } aktualisiert.
+channel-unset = Kanal entfernt
+channel-unset-description = Geburtstage werden in keinem Kanal mehr angekündigt.
+channel-unavailable = Kanal nicht verfügbar
+channel-unavailable-description = Es wurde noch kein Ankündigungskanal festgelegt.
+
+locale-retrieved = Sprache abgerufen
+locale-retrieved-description = Ankündigungen werden auf **{ $language }** gemacht.
+locale-retrieved-default-description = Es wurde keine Sprache gewählt, daher werden Ankündigungen in der bevorzugten Sprache des Servers gemacht, **{ $language }**.
+locale-updated = Sprache aktualisiert
+locale-updated-description = Ankündigungen werden ab jetzt auf **{ $language }** gemacht.
+locale-unset = Sprache entfernt
+locale-unset-description = Ankündigungen werden ab jetzt in der bevorzugten Sprache des Servers gemacht.
+locale-unavailable = Sprache nicht verfügbar
+locale-unavailable-description = Es wurde noch keine Sprache gewählt.
+
+grouping-retrieved = Gruppierung abgerufen
+grouping-retrieved-grouped = Geburtstage aus derselben stündlichen Prüfung werden gemeinsam in einer Nachricht angekündigt.
+grouping-retrieved-separate = Jeder Geburtstag wird in einer eigenen Nachricht angekündigt.
+grouping-updated = Gruppierung aktualisiert
+grouping-updated-grouped = Geburtstage aus derselben stündlichen Prüfung werden ab jetzt gemeinsam in einer Nachricht angekündigt.
+grouping-updated-separate = Jeder Geburtstag wird ab jetzt in einer eigenen Nachricht angekündigt.
+
+digest-retrieved = Übersicht abgerufen
+digest-retrieved-description = Anstehende Geburtstage werden regelmäßig im Ankündigungskanal gepostet.
+digest-updated = Übersicht aktualisiert
+digest-updated-description = Anstehende Geburtstage werden ab jetzt regelmäßig im Ankündigungskanal gepostet.
+digest-unset = Übersicht entfernt
+digest-unset-description = Geburtstagsübersichten werden nicht mehr gepostet.
+digest-unavailable = Übersicht nicht verfügbar
+digest-unavailable-description = Es wurde noch keine Geburtstagsübersicht festgelegt.
+
+anniversary-template = { $user } ist seit { $years } Jahren auf dem Server! :tada:
+anniversary-retrieved = Jubiläen abgerufen
+anniversary-retrieved-description = Die Jahrestage, an denen Mitglieder beigetreten sind, werden im Ankündigungskanal angekündigt.
+anniversary-updated = Jubiläen aktualisiert
+anniversary-updated-description = Die Jahrestage, an denen Mitglieder beigetreten sind, werden ab jetzt im Ankündigungskanal angekündigt.
+anniversary-unset = Jubiläen entfernt
+anniversary-unset-description = Jubiläen werden nicht mehr angekündigt.
+anniversary-unavailable = Jubiläen nicht verfügbar
+anniversary-unavailable-description = Jubiläen wurden noch nicht aktiviert.
+anniversary-opted-out = Abgemeldet
+anniversary-opted-out-description = Der Jahrestag deines Beitritts zum Server wird nicht mehr angekündigt.
+anniversary-opted-in = Angemeldet
+anniversary-opted-in-description = Der Jahrestag deines Beitritts zum Server wird wieder angekündigt.
+anniversary-already-opted-in = Bereits angemeldet
+anniversary-already-opted-in-description = Du hast dich nicht von Jubiläumsankündigungen abgemeldet.
+
+feed-generated = Feed-Token erstellt
+feed-generated-description = Halte dieses Token geheim, denn jeder damit kann die Geburtstage des Servers lesen. Ein neues Token widerruft dieses.
+feed-bearer = Die JSON-Feeds akzeptieren das Token auch als `Authorization: Bearer`-Header.
+feed-revoked = Feed-Token widerrufen
+feed-revoked-description = Auf die Geburtstags-Feeds des Servers kann nicht mehr zugegriffen werden.
+feed-unavailable = Feed-Token nicht verfügbar
+feed-unavailable-description = Es wurde noch kein Feed-Token erstellt.
+
+## Customisation
+
+ping-retrieved = Erwähnungen abgerufen
+ping-retrieved-description = Geburtstagsankündigungen nutzen die folgenden Erwähnungen.
+ping-updated = Erwähnungen aktualisiert
+ping-updated-description = Geburtstagsankündigungen nutzen ab jetzt die folgenden Erwähnungen.
+ping-unset = Erwähnungen entfernt
+ping-unset-description = Geburtstagsankündigungen erwähnen ab jetzt nur noch die Geburtstagskinder.
+ping-unavailable = Erwähnungen nicht verfügbar
+ping-unavailable-description = Es wurden noch keine eigenen Erwähnungen festgelegt.
+
+automation-retrieved = Automatisierung abgerufen
+automation-retrieved-description = Geburtstagsankündigungen werden automatisch wie folgt nachbereitet.
+automation-updated = Automatisierung aktualisiert
+automation-updated-description = Geburtstagsankündigungen werden ab jetzt automatisch wie folgt nachbereitet.
+automation-unset = Automatisierung entfernt
+automation-unset-description = Geburtstagsankündigungen werden nicht mehr automatisch nachbereitet.
+automation-unavailable = Automatisierung nicht verfügbar
+automation-unavailable-description = Es wurde noch keine Automatisierung festgelegt.
+automation-invalid = Ungültige Reaktionen
+automation-invalid-description = Reaktionen müssen bis zu { $max } durch Leerzeichen getrennte Emojis sein.
+
+milestone-exact = Alter { $age }
+milestone-decade = Jedes Jahrzehnt
+milestone-multiple = Alle { $age } Jahre
+milestone-details =
+    Farbe: { $colour }
+    Titel: { $title }
+    Rolle: { $role }
+milestones-retrieved = Meilensteine abgerufen
+milestones-retrieved-description = { $count ->
+        [one] 1 Meilenstein wird angezeigt.
+       *[other] { $count } Meilensteine werden angezeigt.
+    }
+milestones-unavailable = Meilensteine nicht verfügbar
+milestones-unavailable-description = Es wurden noch keine Meilensteine hinzugefügt.
+milestone-invalid = Ungültiger Meilenstein
+milestone-invalid-age-description = Für genaue Alter und Vielfache eines Alters muss ein Alter angegeben werden.
+milestone-invalid-colour-description = Farben müssen Hex-Codes wie `#FFD700` sein.
+milestone-updated = Meilenstein aktualisiert
+milestone-updated-description = Geburtstage, die diesen Meilenstein erreichen, werden ab jetzt besonders gefeiert.
+milestone-removed = Meilenstein entfernt
+milestone-removed-description = Dieser Meilenstein wird nicht mehr besonders gefeiert.
+milestone-unavailable = Meilenstein nicht verfügbar
+milestone-unavailable-description = Dieser Meilenstein wurde noch nicht hinzugefügt.
+
+format-retrieved = Format abgerufen
+format-retrieved-own-description = Daten werden dir in deinem gewählten Format angezeigt.
+format-retrieved-server-description = Du hast kein Format gewählt, daher werden dir Daten im Format des Servers angezeigt.
+format-retrieved-default-description = Weder du noch der Server haben ein Format gewählt, daher werden dir Daten im Standardformat angezeigt.
+format-updated = Format aktualisiert
+format-updated-description = Daten werden dir ab jetzt auf jedem Server in diesem Format angezeigt.
+format-unset = Format entfernt
+format-unset-description = Daten werden dir ab jetzt im Format des Servers angezeigt.
+format-unavailable = Format nicht verfügbar
+format-unavailable-description = Du hast noch kein Format gewählt.
+format-server-updated = Serverformat aktualisiert
+format-server-updated-description = Daten werden ab jetzt in Ankündigungen und Mitgliedern ohne eigenes Format in diesem Format angezeigt.
+format-server-unset = Serverformat entfernt
+format-server-unset-description = Daten werden ab jetzt in Ankündigungen und Mitgliedern ohne eigenes Format im Standardformat angezeigt.
+format-server-unavailable = Serverformat nicht verfügbar
+format-server-unavailable-description = Für den Server wurde noch kein Format gewählt.
+format-discord-note = Discord-Zeitstempel werden in der Sprache und Zeitzone jedes Betrachters angezeigt, daher gelten Uhr und Versatz für sie nicht.
+
+## Events
+
+event-details =
+    Datum: { $date }
+    Beschreibung: { $description }
+    Mitglied: { $user }
+events-retrieved = Ereignisse abgerufen
+events-retrieved-description = { $count ->
+        [one] 1 Ereignis wird angezeigt.
+       *[other] { $count } Ereignisse werden angezeigt.
+    }
+events-retrieved-truncated-description = 25 von { $count } Ereignissen werden angezeigt.
+events-unavailable = Ereignisse nicht verfügbar
+events-unavailable-description = Es wurden noch keine Ereignisse hinzugefügt.
+event-invalid = Ungültiges Ereignis
+event-future-description = Ereignisse müssen bereits mindestens einmal stattgefunden haben.
+event-updated = Ereignis aktualisiert
+event-updated-description = Das Ereignis wird ab jetzt jedes Jahr angekündigt.
+event-removed = Ereignis entfernt
+event-removed-description = Dieses Ereignis wird nicht mehr angekündigt.
+event-unavailable = Ereignis nicht verfügbar
+event-unavailable-description = Dieses Ereignis wurde noch nicht hinzugefügt.
+
+## Announcements
+
+birthday-announcement = Alles Gute zum Geburtstag!
+birthday-announcement-description = { $user } hat heute Geburtstag! :partying_face:
+birthday-announcement-shared = Ein geteilter Geburtstag! :partying_face:
+birthday-announcement-turns = { $user } wird **{ $age }**
+anniversary-announcement = Alles Gute zum Jubiläum!
+event-announcement-description = Heute ist **{ $name }**! :tada:
+thread-birthday = Alles Gute zum Geburtstag, { $names }!
+thread-birthday-generic = Alles Gute zum Geburtstag!
+thread-anniversary = Alles Gute zum Jubiläum, { $names }!
+thread-anniversary-generic = Alles Gute zum Jubiläum!
+
+digest-daily = Heutige Geburtstage
+digest-weekly = Geburtstage dieser Woche
+digest-monthly = Geburtstage dieses Monats
+digest-description = { $count ->
+        [one] Es steht 1 Geburtstag an.
+       *[other] Es stehen { $count } Geburtstage an.
+    }
+
+card = Geburtstagskarte
+card-description = Mitglieder von **{ $guild }** haben dir Geburtstagswünsche hinterlassen! :gift:
+card-page = Seite { $page } von { $pages }
+
+update = Update
+update-description = Ein neues Update wurde veröffentlicht.
+
+## Administration
+
+stats-retrieved = Statistiken abgerufen
+stats-bytes = { $bytes } Bytes
+scan-queued = Prüfung eingeplant
+scan-queued-description = Geburtstage, Ereignisse und Jubiläen werden jetzt geprüft. Alles seit der letzten Prüfung wird angekündigt.
+simulate-empty = Nichts anzukündigen
+simulate-empty-description = Am { $date } würde nichts angekündigt werden.
+simulate-done = Tag simuliert
+simulate-done-description = { $count ->
+        [one] 1 Ankündigung würde gesendet werden.
+       *[other] { $count } Ankündigungen würden gesendet werden.
+    }
+simulate-done-truncated-description = { $count } Ankündigungen würden gesendet werden. Die ersten 25 werden angezeigt.
+simulate-channel = Server `{ $guild }` in { $channel }
+simulate-note = Jubiläen müssen von Discord abgerufen werden und werden daher nicht simuliert.
+resend-changelog-dm-description = In Direktnachrichten musst du für diesen Befehl eine Server-ID angeben.
+changelog-queued = Änderungsprotokoll eingeplant
+changelog-queued-description = Das Änderungsprotokoll wird in Kürze in { $channel } gepostet.
+changelog-unavailable = Änderungsprotokoll nicht verfügbar
+changelog-unavailable-description = Entweder wurde kein Änderungsprotokoll konfiguriert, oder dieser Server hat keinen Ankündigungskanal festgelegt.
+reload-done = Konfiguration neu geladen
+reload-done-description = Log-Level und Änderungsprotokoll wurden neu geladen. Andere Einstellungen werden erst nach einem Neustart wirksam.
+reload-changelog-set = Festgelegt
+
+## Commands
+
+cmd-birthday = geburtstag
+cmd-birthday-get = anzeigen
+    .description = Den Geburtstag eines Mitglieds (oder deinen) anzeigen.
+    .member-name = mitglied
+    .member-description = Wessen Geburtstag angezeigt werden soll. Standardmäßig du selbst.
+cmd-birthday-set = festlegen
+    .description = Deinen Geburtstag aktualisieren oder festlegen.
+    .day-name = tag
+    .day-description = Der Tag, an dem du geboren bist.
+    .month-name = monat
+    .month-description = Der Monat, in dem du geboren bist.
+    .year-name = jahr
+    .year-description = Das Jahr, in dem du geboren bist.
+    .hour-name = stunde
+    .hour-description = Die Stunde, in der du geboren bist. Standardmäßig 0.
+    .minute-name = minute
+    .minute-description = Die Minute, in der du geboren bist. Standardmäßig 0.
+    .second-name = sekunde
+    .second-description = Die Sekunde, in der du geboren bist. Standardmäßig 0.
+    .timezone-name = zeitzone
+    .timezone-description = Die Zeitzone, in der du geboren bist, als `+00:00` oder `-00:00`. Standardmäßig UTC.
+    .calendar-name = kalender
+    .calendar-description = Der Kalender, in dem sich dein Geburtstag wiederholt. Das Datum bleibt gregorianisch.
+cmd-birthday-set-text = als-text-festlegen
+    .description = Deinen Geburtstag aktualisieren oder festlegen, indem du ihn ausschreibst.
+    .date-name = datum
+    .date-description = Dein Geburtstag, wie `14. März 1998`, `1998-03-14` oder `14.03.1998 10:30 +05:30`.
+    .calendar-name = kalender
+    .calendar-description = Der Kalender, in dem sich dein Geburtstag wiederholt. Das Datum bleibt gregorianisch.
+cmd-birthday-unset = entfernen
+    .description = Deinen Geburtstag entfernen.
+cmd-birthday-list = liste
+    .description = Die Geburtstage aller Mitglieder auflisten.
+cmd-birthday-next = nächste
+    .description = Anstehende Geburtstage der Reihe nach auflisten.
+    .limit-name = anzahl
+    .limit-description = Wie viele anstehende Geburtstage angezeigt werden sollen. Standardmäßig 1.
+cmd-birthday-calendar = kalender
+    .description = Die Geburtstage aller Mitglieder als Kalenderdatei exportieren.
+cmd-birthday-wish = wunsch
+    .description = Jemandem einen Geburtstagswunsch hinterlassen, der am Geburtstag überbracht wird.
+    .member-name = mitglied
+    .member-description = Für wen der Glückwunsch ist.
+    .message-name = nachricht
+    .message-description = Dein Geburtstagswunsch.
+cmd-birthday-privacy = privatsphäre
+cmd-birthday-privacy-get = anzeigen
+    .description = Anzeigen, ob dein Geburtstag außerhalb von Discord exportiert werden kann.
+cmd-birthday-privacy-set = festlegen
+    .description = Festlegen, ob dein Geburtstag außerhalb von Discord exportiert werden kann.
+    .exported-name = exportiert
+    .exported-description = Ob dein Geburtstag beim Export der Geburtstage des Servers einbezogen wird.
+cmd-birthday-channel = kanal
+cmd-birthday-channel-get = anzeigen
+    .description = Den Kanal für Geburtstagsankündigungen anzeigen.
+cmd-birthday-channel-set = festlegen
+    .description = Den Kanal für Geburtstagsankündigungen aktualisieren oder festlegen.
+    .channel-name = kanal
+    .channel-description = Der Kanal für Geburtstagsankündigungen.
+cmd-birthday-channel-unset = entfernen
+    .description = Den Kanal für Geburtstagsankündigungen entfernen.
+cmd-birthday-locale = sprache
+cmd-birthday-locale-get = anzeigen
+    .description = Die Sprache anzeigen, in der Ankündigungen gemacht werden.
+cmd-birthday-locale-set = festlegen
+    .description = Die Sprache aktualisieren oder festlegen, in der Ankündigungen gemacht werden.
+    .language-name = sprache
+    .language-description = Die Sprache, in der Ankündigungen gemacht werden.
+cmd-birthday-locale-unset = entfernen
+    .description = Ankündigungen wieder in der bevorzugten Sprache des Servers machen.
+cmd-birthday-grouping = gruppierung
+cmd-birthday-grouping-get = anzeigen
+    .description = Anzeigen, ob Geburtstage aus derselben stündlichen Prüfung gemeinsam angekündigt werden.
+cmd-birthday-grouping-set = festlegen
+    .description = Festlegen, ob Geburtstage aus derselben stündlichen Prüfung gemeinsam angekündigt werden.
+    .grouped-name = gruppiert
+    .grouped-description = Ob Geburtstage aus derselben stündlichen Prüfung in einer Nachricht angekündigt werden.
+cmd-birthday-digest = übersicht
+cmd-birthday-digest-get = anzeigen
+    .description = Anzeigen, wie oft Geburtstagsübersichten gepostet werden.
+cmd-birthday-digest-set = festlegen
+    .description = Festlegen, wie oft Geburtstagsübersichten gepostet werden.
+    .frequency-name = häufigkeit
+    .frequency-description = Wie oft Übersichten gepostet werden. Wöchentlich montags, monatlich am 1.
+    .hour-name = stunde
+    .hour-description = Die Stunde (in UTC), zu der Übersichten gepostet werden. Standardmäßig 0.
+cmd-birthday-digest-unset = entfernen
+    .description = Keine Geburtstagsübersichten mehr posten.
+cmd-birthday-anniversary = jubiläum
+cmd-birthday-anniversary-get = anzeigen
+    .description = Anzeigen, ob Server-Jubiläen angekündigt werden.
+cmd-birthday-anniversary-set = festlegen
+    .description = Die Jahrestage ankündigen, an denen Mitglieder dem Server beigetreten sind.
+    .template-name = vorlage
+    .template-description = Die Nachricht. Unterstützt `{ "{" }user{ "}" }` und `{ "{" }years{ "}" }`. Standardmäßig allgemein.
+cmd-birthday-anniversary-unset = entfernen
+    .description = Die Jahrestage des Beitritts nicht mehr ankündigen.
+cmd-birthday-anniversary-opt-out = abmelden
+    .description = Die Ankündigung deines Server-Jubiläums verhindern.
+cmd-birthday-anniversary-opt-in = anmelden
+    .description = Die Ankündigung deines Server-Jubiläums wieder erlauben.
+cmd-birthday-ping = erwähnung
+cmd-birthday-ping-get = anzeigen
+    .description = Anzeigen, wer bei Geburtstagsankündigungen erwähnt wird.
+cmd-birthday-ping-set = festlegen
+    .description = Festlegen, wer bei Geburtstagsankündigungen erwähnt wird.
+    .celebrants-name = geburtstagskinder
+    .celebrants-description = Ob die Geburtstagskinder erwähnt werden. Standardmäßig wahr.
+    .role-name = rolle
+    .role-description = Eine Rolle, die in jeder Ankündigung erwähnt wird. Standardmäßig keine.
+    .here-name = here
+    .here-description = Ob in jeder Ankündigung `@here` erwähnt wird. Standardmäßig falsch.
+    .everyone-name = everyone
+    .everyone-description = Ob die Vorlage `@everyone` erwähnen darf. Standardmäßig falsch.
+    .template-name = vorlage
+    .template-description = Die Nachricht. Unterstützt `{ "{" }users{ "}" }`, `{ "{" }role{ "}" }` und `{ "{" }here{ "}" }`.
+cmd-birthday-ping-unset = entfernen
+    .description = Zurücksetzen, wer bei Geburtstagsankündigungen erwähnt wird.
+cmd-birthday-automation = automatisierung
+cmd-birthday-automation-get = anzeigen
+    .description = Anzeigen, was nach einer Geburtstagsankündigung passiert.
+cmd-birthday-automation-set = festlegen
+    .description = Festlegen, was nach einer Geburtstagsankündigung passiert.
+    .reactions-name = reaktionen
+    .reactions-description = Emojis, mit denen auf Ankündigungen reagiert wird, durch Leerzeichen getrennt.
+    .thread-name = thread
+    .thread-description = Ob zu jeder Ankündigung ein Thread eröffnet wird. Standardmäßig falsch.
+cmd-birthday-automation-unset = entfernen
+    .description = Geburtstagsankündigungen nicht mehr nachbereiten.
+cmd-birthday-milestone = meilenstein
+cmd-birthday-milestone-list = liste
+    .description = Die Alter auflisten, die mit besonderen Ankündigungen gefeiert werden.
+cmd-birthday-milestone-add = hinzufügen
+    .description = Ein Alter hinzufügen oder aktualisieren, das besonders gefeiert wird.
+    .rule-name = regel
+    .rule-description = Für welche Alter der Meilenstein gilt.
+    .age-name = alter
+    .age-description = Das genaue Alter oder das Alter, dessen Vielfache gefeiert werden.
+    .colour-name = farbe
+    .colour-description = Die Farbe der Ankündigung, etwa `#FFD700`. Standardmäßig die übliche Farbe.
+    .title-name = titel
+    .title-description = Der Titel der Ankündigung. Unterstützt `{ "{" }age{ "}" }`. Standardmäßig der übliche.
+    .role-name = rolle
+    .role-description = Eine Rolle, die in der Ankündigung erwähnt wird. Standardmäßig keine.
+cmd-birthday-milestone-remove = entfernen
+    .description = Ein Alter entfernen, das besonders gefeiert wird.
+    .rule-name = regel
+    .rule-description = Für welche Alter der Meilenstein gilt.
+    .age-name = alter
+    .age-description = Das genaue Alter oder das Alter, dessen Vielfache gefeiert werden.
+cmd-birthday-event = ereignis
+cmd-birthday-format = format
+cmd-birthday-format-get = anzeigen
+    .description = Anzeigen, wie dir Daten angezeigt werden.
+cmd-birthday-format-set = festlegen
+    .description = Festlegen, wie dir Daten angezeigt werden.
+    .date-name = datum
+    .date-description = Die Reihenfolge von Daten, oder Discord-Zeitstempel für die Einstellungen deines Clients.
+    .clock-name = uhr
+    .clock-description = Ob Uhrzeiten mit 24 oder 12 Stunden geschrieben werden. Standardmäßig 24 Stunden.
+    .offset-name = versatz
+    .offset-description = Ob der UTC-Versatz von Daten angezeigt wird. Standardmäßig wahr.
+cmd-birthday-format-unset = entfernen
+    .description = Dir Daten wieder im Format des Servers anzeigen.
+cmd-birthday-format-set-server = server-festlegen
+    .description = Festlegen, wie Daten in Ankündigungen und Mitgliedern ohne eigenes Format angezeigt werden.
+    .date-name = datum
+    .date-description = Die Reihenfolge von Daten, oder Discord-Zeitstempel für die Einstellungen jedes Mitglieds.
+    .clock-name = uhr
+    .clock-description = Ob Uhrzeiten mit 24 oder 12 Stunden geschrieben werden. Standardmäßig 24 Stunden.
+    .offset-name = versatz
+    .offset-description = Ob der UTC-Versatz von Daten angezeigt wird. Standardmäßig wahr.
+cmd-birthday-format-unset-server = server-entfernen
+    .description = Daten auf dem Server wieder im Standardformat anzeigen.
+cmd-birthday-event-list = liste
+    .description = Die wiederkehrenden Ereignisse des Servers auflisten.
+cmd-birthday-event-add = hinzufügen
+    .description = Ein wiederkehrendes Ereignis hinzufügen oder aktualisieren, etwa die Servergründung.
+    .name-name = name
+    .name-description = Der Name des Ereignisses.
+    .day-name = tag
+    .day-description = Der Tag, an dem das Ereignis zuerst stattfand.
+    .month-name = monat
+    .month-description = Der Monat, in dem das Ereignis zuerst stattfand.
+    .year-name = jahr
+    .year-description = Das Jahr, in dem das Ereignis zuerst stattfand.
+    .description-name = beschreibung
+    .description-description = Die Beschreibung bei der Ankündigung. Standardmäßig eine allgemeine Nachricht.
+    .user-name = nutzer
+    .user-description = Ein Nutzer, um den es geht und der bei der Ankündigung erwähnt wird.
+    .timezone-name = zeitzone
+    .timezone-description = Die Zeitzone des Ereignisses, als `+00:00` oder `-00:00`. Standardmäßig UTC.
+cmd-birthday-event-remove = entfernen
+    .description = Ein wiederkehrendes Ereignis entfernen.
+    .name-name = name
+    .name-description = Der Name des Ereignisses.
+cmd-birthday-help = hilfe
+    .description = Anleitungen für die Geburtstagsbefehle anzeigen.
+    .topic-name = thema
+    .topic-description = Wofür Anleitungen angezeigt werden. Standardmäßig Geburtstage.
+cmd-birthday-feed = feed
+cmd-birthday-feed-generate = erstellen
+    .description = Ein geheimes Token für die Geburtstags-Feeds erstellen und das vorherige ersetzen.
+cmd-birthday-feed-revoke = widerrufen
+    .description = Das geheime Token für die Geburtstags-Feeds des Servers widerrufen.
+cmd-birthbot = birthbot
+cmd-birthbot-admin = admin
+cmd-birthbot-admin-stats = statistiken
+    .description = Statistiken über den Bot und seine Datenbank anzeigen.
+cmd-birthbot-admin-scan = prüfen
+    .description = Sofort nach anzukündigenden Geburtstagen, Ereignissen und Jubiläen suchen.
+cmd-birthbot-admin-simulate = simulieren
+    .description = Anzeigen, was an einem Tag angekündigt würde, ohne es anzukündigen.
+    .date-name = datum
+    .date-description = Der zu simulierende Tag (in UTC), etwa `2024-02-29`.
+cmd-birthbot-admin-resend-changelog = änderungsprotokoll-senden
+    .description = Das aktuelle Änderungsprotokoll erneut auf einem Server posten.
+    .guild-name = server
+    .guild-description = Die ID des Servers. Standardmäßig dieser Server.
+cmd-birthbot-admin-reload = neu-laden
+    .description = Log-Level und Änderungsprotokoll aus der Konfiguration neu laden.
@@ -0,0 +1,475 @@
+# English translations, which every other language falls back to.
+#
+# Command names and descriptions are written in the commands themselves, so unlike the other languages, there are no
+# `cmd-` messages here.
+
+## Shared
+
+yes = Yes
+no = No
+none = None
+default = Default
+the-server = the server
+list-and = { $init } and { $last }
+
+field-age = Age
+field-years = Years
+field-year = Year
+field-month = Month
+field-day = Day
+field-hour = Hour
+field-minute = Minute
+field-second = Second
+field-member = Member
+field-template = Template
+field-problem = Problem
+field-provided-time = Provided time
+field-provided-value = Value provided
+field-missing-permissions = Missing permissions
+field-provided-birthday = Provided birthday
+field-provided-date = Provided date
+field-provided-reactions = Provided reactions
+field-upcoming-birthdays = Upcoming birthdays
+field-reactions = Reactions
+field-thread = Thread
+field-frequency = Frequency
+field-time = Time
+field-token = Token
+field-feeds = Feeds
+field-celebrants = Celebrants
+field-role = Role
+field-everyone-allowed = `@everyone` allowed
+field-version = Version
+field-changelog = Changelog
+field-uptime = Uptime
+field-guilds = Guilds
+field-birthdays = Birthdays
+field-guilds-with-birthdays = Guilds with birthdays
+field-database-size = Database size
+field-log-level = Log level
+field-note = Note
+field-date = Date
+field-clock = Clock
+field-offset = Offset
+field-example = Example
+
+invalid-date-description = That's not a valid year-month-day combination.
+invalid-time-description = That's not a valid hour-minute-second combination.
+
+button-confirm = Confirm
+button-cancel = Cancel
+
+## Months
+
+choice-january = January
+choice-february = February
+choice-march = March
+choice-april = April
+choice-may = May
+choice-june = June
+choice-july = July
+choice-august = August
+choice-september = September
+choice-october = October
+choice-november = November
+choice-december = December
+
+## Choices
+
+choice-daily = Daily
+choice-weekly = Weekly
+choice-monthly = Monthly
+choice-exact-age = Exact age
+choice-every-decade = Every decade
+choice-every-multiple-of-an-age = Every multiple of an age
+choice-birthdays = Birthdays
+choice-announcements = Announcements
+choice-customisation = Customisation
+choice-events = Events
+choice-day-month-year = Day Month Year
+choice-month-day-year = Month Day Year
+choice-iso-8601 = ISO 8601
+choice-discord-timestamp = Discord timestamp
+choice-24-hour = 24-hour
+choice-12-hour = 12-hour
+choice-gregorian = Gregorian
+choice-chinese = Chinese
+choice-hebrew = Hebrew
+choice-hijri = Hijri
+
+## Errors
+
+error-invalid-data = Invalid data
+error-invalid-data-description = Some of the stored data needed for that command is invalid. This may be because the database was edited by hand, or my clock is wrong.
+error-poisoned-lock = Database unavailable
+error-poisoned-lock-description = The database became unavailable after something went wrong earlier. I need to be restarted before I can handle commands again.
+error-ambiguous-time = Ambiguous time
+error-ambiguous-time-description = That date and time doesn't exist, or exists twice, in that timezone.
+error-config = Misconfigured
+error-config-description = I'm misconfigured, so I can't handle that command.
+error-command = Command failed
+error-command-description = An error occurred while handling the command: `{ $error }`
+error-panic = Command panicked
+error-panic-description = Something went wrong and the command handler panicked. This indicates a bug in my code - please [file an issue on GitHub](<https://github.com/Carnagion/birthbot/issues>).
+error-argument = Invalid input
+error-argument-description = One of the command arguments is invalid.
+error-structure = Invalid command
+error-structure-description = That command structure doesn't match what I expected. This indicates that my latest commands may not have been registered with Discord yet.
+error-cooldown = Cooldown active
+error-cooldown-description = You're too fast. Please wait { $seconds ->
+        [one] 1 second
+       *[other] { $seconds } seconds
+    } before retrying.
+error-unauthorised = Unauthorised
+error-bot-permissions-description = I lack the necessary permissions to execute that command.
+error-user-permissions-description = You lack the necessary permissions to issue that command.
+error-not-an-owner-description = Only owners can use that command.
+error-unavailable = Unavailable
+error-unavailable-description = I'm restarting right now. Please try again in a minute or so.
+error-context = Invalid context
+error-guild-only-description = You can only use that command in a guild (i.e. server).
+error-dm-only-description = You can only use that command in DMs.
+error-nsfw-only-description = You can only use that command in NSFW channels (:flushed:).
+
+## Birthdays
+
+birthday-retrieved = Birthday retrieved
+birthday-get-own = You were born on { $birthday }.
+birthday-get-other = { $user } was born on { $birthday }.
+birthday-unavailable = Birthday unavailable
+birthday-unavailable-own = You haven't set a birthday yet.
+birthday-unavailable-own-help = You haven't set a birthday yet. Use `/birthday help` for information.
+birthday-unavailable-other = { $user } hasn't set a birthday yet.
+birthday-invalid = Invalid birthday
+birthday-future-description = Time travel doesn't exist yet, so your birthday can't be in the future.
+birthday-updated = Birthday updated
+birthday-updated-description = Your birthday has been updated to { $birthday }.
+birthday-in-calendar = { $date } ({ $calendar } calendar)
+date-unrecognized-description = That doesn't look like a date. Try writing it like `14 March 1998`, `1998-03-14`, or `14/03/1998 10:30 +05:30`.
+date-year-missing-description = Your birthday needs a year so that your age can be shown. Try writing it like `14 March 1998`.
+date-confirm = Confirm birthday
+date-confirm-description = That date can be read more than one way. Did you mean { $birthday }? If not, try writing the month's name instead.
+date-expired = Confirmation expired
+date-expired-description = Your birthday wasn't updated. Use the command again to try again.
+date-cancelled = Birthday not updated
+date-cancelled-description = Your birthday wasn't updated. Try writing the month's name instead, such as `14 March 1998`.
+birthday-unset = Birthday unset
+birthday-unset-description = Your birthday was removed.
+birthdays-retrieved = Birthdays retrieved
+birthdays-retrieved-description = { $count ->
+        [one] Showing 1 birthday.
+       *[other] Showing { $count } birthdays.
+    }
+birthdays-unavailable = Birthdays unavailable
+birthdays-unavailable-description = No birthdays have been set yet.
+
+calendar-unavailable = Calendar unavailable
+calendar-unavailable-description = No birthdays can be exported yet.
+calendar-exported = Calendar exported
+calendar-exported-description = Import the attached file into your calendar app to keep track of { $count ->
+        [one] 1 birthday
+       *[other] { $count } birthdays
+    }. Importing it again later updates the existing events.
+calendar-name = { $guild } birthdays
+calendar-summary = { $name }'s birthday
+
+wish-invalid = Invalid wish
+wish-invalid-description = You can't leave a birthday wish for yourself, no matter how tempting.
+wish-early = Too early
+wish-early-description = You can only leave birthday wishes up to { $days } days in advance. { $user }'s next birthday is { $next }.
+wish-saved = Wish saved
+wish-saved-description = Your wish will be delivered to { $user } on their birthday. Leaving another wish for them will replace this one.
+
+privacy-retrieved = Privacy retrieved
+privacy-retrieved-exported = Your birthday is included when the server's birthdays are exported, such as with `/birthday calendar`.
+privacy-retrieved-hidden = Your birthday is left out when the server's birthdays are exported.
+privacy-updated = Privacy updated
+privacy-updated-exported = Your birthday will now be included when the server's birthdays are exported.
+privacy-updated-hidden = Your birthday will now be left out when the server's birthdays are exported.
+
+## Help
+
+help = Help
+help-description = Here's a list of available commands for { $topic }. Use `{ $usage }` to see other topics.
+help-topic-birthdays = birthdays
+help-topic-announcements = announcements
+help-topic-customisation = customisation
+help-topic-events = events
+
+help-get = Display a user's birthday
+    .notes = `[member?]` defaults to you if not specified.
+help-set = Set your birthday
+    .notes =
+        `[hour?]`, `[minute?]`, and `[second?]` default to 0 if not specified.
+        `[timezone?]` defaults to UTC (`+00:00`) if not specified.
+        `[calendar?]` defaults to Gregorian if not specified. Your birthday is still entered as a Gregorian date, and is celebrated on the same date in the chosen calendar every year.
+help-set-text = Set your birthday by writing it out
+    .notes =
+        `[date]` accepts dates like `14 March 1998`, `March 14, 1998`, `1998-03-14`, or `14/03/1998`, optionally followed by a time and timezone such as `10:30 +05:30`. The year is required, since your age is shown.
+        Dates like `01/02/1998` are read in the order of your date format (see `/birthday format set`), or of your Discord language if you haven't chosen one, and need to be confirmed before they're saved.
+        `[calendar?]` defaults to Gregorian if not specified.
+help-unset = Remove your birthday
+help-list = List all birthdays
+help-next = List upcoming birthdays
+    .notes = `[limit?]` defaults to 1 if not specified.
+help-calendar = Export all birthdays as a calendar file
+    .notes = The file can be imported into calendar apps such as Google Calendar or Outlook.
+help-privacy-get = Display whether your birthday can be exported
+help-privacy-set = Set whether your birthday can be exported
+help-wish = Leave a birthday wish for someone
+    .notes = Wishes can be left up to 7 days before someone's birthday, and are delivered to them when it's announced.
+help-help = Show this help message
+    .notes = `[topic?]` defaults to birthdays if not specified.
+
+help-channel-get = Display the birthday announcement channel
+help-channel-set = Set the birthday announcement channel
+help-channel-unset = Remove the birthday announcement channel
+help-locale-get = Display the language announcements are made in
+help-locale-set = Set the language announcements are made in
+    .notes = Responses to commands are always in your own language.
+help-locale-unset = Make announcements in the server's preferred language again
+    .notes = The preferred language can be set in the server's community settings, and defaults to English.
+help-grouping-get = Display whether birthdays found in the same hourly check are announced together
+help-grouping-set = Set whether birthdays found in the same hourly check are announced together
+help-digest-get = Display how often birthday digests are posted
+help-digest-set = Set how often birthday digests are posted
+    .notes = `[hour?]` is in UTC and defaults to 0 if not specified.
+help-digest-unset = Stop posting birthday digests
+help-anniversary-get = Display whether server anniversaries are announced
+help-anniversary-set = Announce server anniversaries
+    .notes = `[template?]` supports `{ "{" }user{ "}" }` and `{ "{" }years{ "}" }`, and uses a generic message if not specified.
+help-anniversary-unset = Stop announcing server anniversaries
+help-anniversary-opt = Opt out of or back into having your server anniversary announced
+
+help-ping-get = Display who gets pinged in birthday announcements
+help-ping-set = Set who gets pinged in birthday announcements
+    .notes =
+        `[celebrants?]` defaults to true, and `[here?]` and `[everyone?]` to false if not specified.
+        `[template?]` supports `{ "{" }users{ "}" }`, `{ "{" }role{ "}" }`, and `{ "{" }here{ "}" }`, and mentions all of them if not specified.
+help-ping-unset = Reset who gets pinged in birthday announcements
+help-automation-get = Display what happens after birthdays are announced
+help-automation-set = Set what happens after birthdays are announced
+    .notes = `[reactions?]` defaults to none, and `[thread?]` to false if not specified.
+help-automation-unset = Stop following up on birthday announcements
+help-milestone-list = List ages that are celebrated specially
+help-milestone-add = Celebrate an age specially
+    .notes =
+        `[age?]` is required for exact ages and multiples of an age.
+        `[title?]` supports `{ "{" }age{ "}" }`.
+help-milestone-remove = Stop celebrating an age specially
+help-format-get = Display how dates are shown to you
+help-format-set = Set how dates are shown to you
+    .notes =
+        `[clock?]` defaults to 24-hour, and `[offset?]` to true if not specified.
+        Discord timestamps are shown in each viewer's own language and timezone.
+help-format-unset = Show dates to you in the server's format again
+help-format-server = Set or reset how dates are shown in announcements and to members without a format
+
+help-event-list = List the server's recurring events
+help-event-add = Add or update a recurring event
+    .notes =
+        `[description?]` uses a generic message if not specified.
+        `[user?]` is pinged when the event is announced.
+        `[timezone?]` defaults to `+00:00` (UTC) if not specified.
+help-event-remove = Remove a recurring event
+
+## Announcement settings
+
+channel-retrieved = Channel retrieved
+channel-retrieved-description = Birthdays and updates are announced in { $channel }.
+channel-updated = Channel updated
+channel-updated-description = The birthday announcement channel has been updated to { $channel }.
+channel-unset = Channel unset
+channel-unset-description = Birthdays are no longer announced in any channel.
+channel-unavailable = Channel unavailable
+channel-unavailable-description = A birthday announcement channel hasn't been set yet.
+
+locale-retrieved = Language retrieved
+locale-retrieved-description = Announcements are made in **{ $language }**.
+locale-retrieved-default-description = A language hasn't been chosen, so announcements are made in the server's preferred language, **{ $language }**.
+locale-updated = Language updated
+locale-updated-description = Announcements will now be made in **{ $language }**.
+locale-unset = Language unset
+locale-unset-description = Announcements will now be made in the server's preferred language.
+locale-unavailable = Language unavailable
+locale-unavailable-description = A language hasn't been chosen yet.
+
+grouping-retrieved = Grouping retrieved
+grouping-retrieved-grouped = Birthdays found in the same hourly check are announced together in a single message.
+grouping-retrieved-separate = Each birthday is announced in its own message.
+grouping-updated = Grouping updated
+grouping-updated-grouped = Birthdays found in the same hourly check will now be announced together in a single message.
+grouping-updated-separate = Each birthday will now be announced in its own message.
+
+digest-retrieved = Digest retrieved
+digest-retrieved-description = Upcoming birthdays are regularly posted in the announcement channel.
+digest-updated = Digest updated
+digest-updated-description = Upcoming birthdays will now be regularly posted in the announcement channel.
+digest-unset = Digest unset
+digest-unset-description = Birthday digests are no longer posted.
+digest-unavailable = Digest unavailable
+digest-unavailable-description = A birthday digest hasn't been set yet.
+
+anniversary-template = It's { $user }'s { $years }-year anniversary in the server! :tada:
+anniversary-retrieved = Anniversaries retrieved
+anniversary-retrieved-description = The anniversaries of when members joined are announced in the announcement channel.
+anniversary-updated = Anniversaries updated
+anniversary-updated-description = The anniversaries of when members joined will now be announced in the announcement channel.
+anniversary-unset = Anniversaries unset
+anniversary-unset-description = Anniversaries are no longer announced.
+anniversary-unavailable = Anniversaries unavailable
+anniversary-unavailable-description = Anniversaries haven't been enabled yet.
+anniversary-opted-out = Opted out
+anniversary-opted-out-description = Your anniversary of joining the server will no longer be announced.
+anniversary-opted-in = Opted in
+anniversary-opted-in-description = Your anniversary of joining the server will be announced again.
+anniversary-already-opted-in = Already opted in
+anniversary-already-opted-in-description = You haven't opted out of anniversary announcements.
+
+feed-generated = Feed token generated
+feed-generated-description = Keep this token secret, since anyone with it can read the server's birthdays. Generating a new token revokes this one.
+feed-bearer = The JSON feeds also accept the token as an `Authorization: Bearer` header.
+feed-revoked = Feed token revoked
+feed-revoked-description = The server's birthday feeds can no longer be accessed.
+feed-unavailable = Feed token unavailable
+feed-unavailable-description = No feed token has been generated yet.
+
+## Customisation
+
+ping-retrieved = Pings retrieved
+ping-retrieved-description = Birthday announcements use the following pings.
+ping-updated = Pings updated
+ping-updated-description = Birthday announcements will now use the following pings.
+ping-unset = Pings unset
+ping-unset-description = Birthday announcements will now only ping the members whose birthday it is.
+ping-unavailable = Pings unavailable
+ping-unavailable-description = Custom pings haven't been set yet.
+
+automation-retrieved = Automation retrieved
+automation-retrieved-description = Birthday announcements are automatically followed up as follows.
+automation-updated = Automation updated
+automation-updated-description = Birthday announcements will now be automatically followed up as follows.
+automation-unset = Automation unset
+automation-unset-description = Birthday announcements are no longer automatically followed up.
+automation-unavailable = Automation unavailable
+automation-unavailable-description = Automation hasn't been set yet.
+automation-invalid = Invalid reactions
+automation-invalid-description = Reactions must be up to { $max } emojis separated by spaces.
+
+milestone-exact = Age { $age }
+milestone-decade = Every decade
+milestone-multiple = Every { $age } years
+milestone-details =
+    Colour: { $colour }
+    Title: { $title }
+    Role: { $role }
+milestones-retrieved = Milestones retrieved
+milestones-retrieved-description = { $count ->
+        [one] Showing 1 milestone.
+       *[other] Showing { $count } milestones.
+    }
+milestones-unavailable = Milestones unavailable
+milestones-unavailable-description = No milestones have been added yet.
+milestone-invalid = Invalid milestone
+milestone-invalid-age-description = An age must be provided for exact ages and multiples of an age.
+milestone-invalid-colour-description = Colours must be hex codes such as `#FFD700`.
+milestone-updated = Milestone updated
+milestone-updated-description = Birthdays reaching this milestone will now be celebrated specially.
+milestone-removed = Milestone removed
+milestone-removed-description = That milestone is no longer celebrated specially.
+milestone-unavailable = Milestone unavailable
+milestone-unavailable-description = That milestone hasn't been added yet.
+
+format-retrieved = Format retrieved
+format-retrieved-own-description = Dates are shown to you in the format you chose.
+format-retrieved-server-description = You haven't chosen a format, so dates are shown to you in the server's format.
+format-retrieved-default-description = Neither you nor the server have chosen a format, so dates are shown to you in the default format.
+format-updated = Format updated
+format-updated-description = Dates will now be shown to you in this format in every server.
+format-unset = Format unset
+format-unset-description = Dates will now be shown to you in the server's format.
+format-unavailable = Format unavailable
+format-unavailable-description = You haven't chosen a format yet.
+format-server-updated = Server format updated
+format-server-updated-description = Dates will now be shown in this format in announcements, and to members who haven't chosen their own format.
+format-server-unset = Server format unset
+format-server-unset-description = Dates will now be shown in the default format in announcements, and to members who haven't chosen their own format.
+format-server-unavailable = Server format unavailable
+format-server-unavailable-description = A format hasn't been chosen for the server yet.
+format-discord-note = Discord timestamps are shown in each viewer's own language and timezone, so the clock and offset don't apply to them.
+
+## Events
+
+event-details =
+    Date: { $date }
+    Description: { $description }
+    Member: { $user }
+events-retrieved = Events retrieved
+events-retrieved-description = { $count ->
+        [one] Showing 1 event.
+       *[other] Showing { $count } events.
+    }
+events-retrieved-truncated-description = Showing 25 of { $count } events.
+events-unavailable = Events unavailable
+events-unavailable-description = No events have been added yet.
+event-invalid = Invalid event
+event-future-description = Events must have already happened at least once.
+event-updated = Event updated
+event-updated-description = The event will now be announced every year.
+event-removed = Event removed
+event-removed-description = That event will no longer be announced.
+event-unavailable = Event unavailable
+event-unavailable-description = That event hasn't been added yet.
+
+## Announcements
+
+birthday-announcement = Happy birthday!
+birthday-announcement-description = It's { $user }'s birthday! :partying_face:
+birthday-announcement-shared = It's a shared birthday! :partying_face:
+birthday-announcement-turns = { $user } turns **{ $age }**
+anniversary-announcement = Happy anniversary!
+event-announcement-description = It's **{ $name }**! :tada:
+thread-birthday = Happy birthday, { $names }!
+thread-birthday-generic = Happy birthday!
+thread-anniversary = Happy anniversary, { $names }!
+thread-anniversary-generic = Happy anniversary!
+
+digest-daily = Today's birthdays
+digest-weekly = This week's birthdays
+digest-monthly = This month's birthdays
+digest-description = { $count ->
+        [one] There's 1 birthday coming up.
+       *[other] There are { $count } birthdays coming up.
+    }
+
+card = Birthday card
+card-description = Members of **{ $guild }** left you some birthday wishes! :gift:
+card-page = Page { $page } of { $pages }
+
+update = Update
+update-description = A new update has been released.
+
+## Administration
+
+stats-retrieved = Statistics retrieved
+stats-bytes = { $bytes } bytes
+scan-queued = Scan queued
+scan-queued-description = Birthdays, events, and anniversaries are being checked now. Anything that has happened since the last check will be announced.
+simulate-empty = Nothing to announce
+simulate-empty-description = Nothing would be announced on { $date }.
+simulate-done = Day simulated
+simulate-done-description = { $count ->
+        [one] 1 announcement would be sent.
+       *[other] { $count } announcements would be sent.
+    }
+simulate-done-truncated-description = { $count } announcements would be sent. Showing the first 25.
+simulate-channel = Guild `{ $guild }` in { $channel }
+simulate-note = Anniversaries need to be fetched from Discord, so they aren't simulated.
+resend-changelog-dm-description = You need to provide a server ID when using that command in DMs.
+changelog-queued = Changelog queued
+changelog-queued-description = The changelog will be posted in { $channel } shortly.
+changelog-unavailable = Changelog unavailable
+changelog-unavailable-description = Either no changelog has been configured, or that server hasn't set an announcement channel.
+reload-done = Config reloaded
+reload-done-description = The log level and changelog have been reloaded. Other settings only take effect after restarting.
+reload-changelog-set = Set
@@ -0,0 +1,46 @@
+use poise::serenity_prelude as serenity;
+
+use rusqlite::Connection;
+
+use serenity::{GuildId, UserId};
+
+use crate::locale::{Language, tr};
+
+/// Returns the template used for anniversary announcements when a guild hasn't provided its own.
+pub fn default_template(language: Language) -> String {
+    // NOTE: The placeholders are passed in as they are, so that the translated message is itself a template.
+    tr!(
+        language,
+        "anniversary-template",
+        user = "{user}",
+        years = "{years}",
+    )
+}
+
+/// Retrieves a guild's anniversary template, or `None` if the guild hasn't opted into anniversaries.
+///
+/// Guilds that have opted in without providing a template use the [default template](default_template) in
+/// `language`.
+pub fn load_template(
+    conn: &Connection,
+    guild_id: GuildId,
+    language: Language,
+) -> rusqlite::Result<Option<String>> {
+    let query = "select template from anniversaries where guild_id = ?1";
+    let template = conn
+        .prepare(query)?
+        // NOTE: See the note in `birthday::get`.
+        .query((guild_id.get() as i64,))?
+        .next()?
+        .map(|row| row.get::<_, Option<String>>(0))
+        .transpose()?
+        .map(|template| template.unwrap_or_else(|| default_template(language)));
+    Ok(template)
+}
+
+/// Renders an anniversary template, replacing the `{user}` and `{years}` placeholders.
+pub fn render(template: &str, user_id: UserId, years: u32) -> String {
+    template
+        .replace("{user}", &format!("<@{}>", user_id))
+        .replace("{years}", &years.to_string())
+}
@@ -0,0 +1,47 @@
+use poise::serenity_prelude as serenity;
+
+use rusqlite::Connection;
+
+use serenity::{GuildId, ReactionType};
+
+/// The maximum number of reactions that can be added to an announcement.
+pub const MAX_REACTIONS: usize = 20;
+
+/// A guild's settings for what happens after a birthday announcement is sent.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Automation {
+    pub reactions: Vec<ReactionType>,
+    pub thread: bool,
+}
+
+impl Automation {
+    /// Retrieves a guild's automation settings, falling back to doing nothing if it hasn't set any.
+    pub fn load(conn: &Connection, guild_id: GuildId) -> rusqlite::Result<Self> {
+        let query = "select reactions, thread from automations where guild_id = ?1";
+        let automation = conn
+            .prepare(query)?
+            // NOTE: See the note in `birthday::get`.
+            .query((guild_id.get() as i64,))?
+            .next()?
+            .map(|row| {
+                let reactions = row.get::<_, String>(0)?;
+                Ok::<_, rusqlite::Error>(Self {
+                    // NOTE: Reactions are validated before being stored, so any that fail to parse now were stored
+                    //       by hand and can be ignored.
+                    reactions: parse_reactions(&reactions).unwrap_or_default(),
+                    thread: row.get(1)?,
+                })
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(automation)
+    }
+}
+
+/// Parses a whitespace-separated list of emojis, returning `None` if any of them are invalid.
+pub fn parse_reactions(reactions: &str) -> Option<Vec<ReactionType>> {
+    reactions
+        .split_whitespace()
+        .map(|reaction| ReactionType::try_from(reaction).ok())
+        .collect()
+}
@@ -1,3 +1,9 @@
+pub mod backups;
+
 pub mod birthdays;
 
 pub mod changelog;
+
+pub mod digests;
+
+pub mod retries;
@@ -0,0 +1,52 @@
+use std::{num::NonZeroU64, path::PathBuf};
+
+use tokio::{task, time};
+
+use tracing::{error, info};
+
+use crate::{
+    backup,
+    shutdown::{Phase, Shutdown},
+};
+
+#[derive(Debug, Clone)]
+pub struct Backups {
+    pub db: PathBuf,
+    pub dir: PathBuf,
+    pub interval: NonZeroU64,
+    pub retention: usize,
+    pub compress: bool,
+}
+
+#[tracing::instrument]
+pub async fn watch_backups(backups: Backups, shutdown: Shutdown) {
+    let interval = time::Duration::from_secs(backups.interval.get() * 60 * 60);
+    let mut interval = time::interval(interval);
+    loop {
+        // NOTE: See the note in `watch_birthdays`.
+        tokio::select! {
+            _ = interval.tick() => {},
+            _ = shutdown.reached(Phase::Draining) => break,
+        }
+
+        // NOTE: Backups can take a while for large databases, so we avoid blocking other tasks on this thread.
+        let created =
+            task::block_in_place(|| backup::create(&backups.db, &backups.dir, backups.compress));
+        match created {
+            Ok(path) => info!("backed up database to {}", path.display()),
+            Err(err) => {
+                error!(?err, "failed to back up database");
+                continue;
+            },
+        }
+
+        match task::block_in_place(|| backup::prune(&backups.dir, backups.retention)) {
+            Ok(pruned) => {
+                for path in pruned {
+                    info!("deleted old backup {}", path.display());
+                }
+            },
+            Err(err) => error!(?err, "failed to delete old backups"),
+        }
+    }
+}
@@ -1,8 +1,19 @@
-use chrono::{Months, TimeDelta, Utc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    ops::Range,
+    time::Instant,
+};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeDelta};
 
 use poise::serenity_prelude as serenity;
 
-use serenity::{ChannelId, Context, CreateMessage, GuildId, UserId};
+use rusqlite::Connection;
+
+use serde::{Deserialize, Serialize};
+
+use serenity::{ChannelId, Context, CreateMessage, CreateThread, GuildId, Member, UserId};
 
 use tokio::{
     sync::mpsc::{self, Receiver, Sender},
@@ -10,105 +21,699 @@ use tokio::{
     time,
 };
 
-use tracing::error;
+use tracing::{error, info, warn};
 
-use crate::{announcement, birthday::Birthday, error::Result, state::State};
+use crate::{
+    anniversary,
+    announcement,
+    automation::Automation,
+    birthday::Birthday,
+    clock::{Clock, ManualClock},
+    error::{Error, Result},
+    event::Event,
+    locale::{self, Language, tr},
+    milestone,
+    outbox,
+    ping::Pings,
+    recurrence::{self, Calendar},
+    shutdown::Phase,
+    state::State,
+    undelivered,
+    wish,
+};
+
+use super::retries::watch_retries;
 
 const INTERVAL: TimeDelta = TimeDelta::hours(1);
 
+// NOTE: The kind that announcement batches are stored as when they're undelivered or waiting to be retried.
+const KIND: &str = "announcement";
+
+// NOTE: This is the maximum number of members that Discord returns at once.
+const MEMBERS_PER_PAGE: u64 = 1000;
+
 #[tracing::instrument]
 pub async fn watch_birthdays(ctx: Context, data: State) {
     let (tx, rx) = mpsc::channel(100);
 
     // Spawn a long-running task for announcing birthdays found by the birthday-checking task
-    tokio::spawn(announce_birthdays(ctx, rx));
+    data.shutdown
+        .spawn(announce_birthdays(ctx.clone(), data.clone(), rx));
+
+    // Spawn a long-running task for retrying announcements that failed to be sent
+    data.shutdown
+        .spawn(watch_retries(data.clone(), KIND, tx.clone()));
+
+    // Announce anything that wasn't delivered before the last shutdown
+    let undelivered = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        undelivered::take::<Batch>(&conn, KIND)
+    });
+    match undelivered {
+        Ok(batches) => {
+            if !batches.is_empty() {
+                info!("resending {} undelivered announcement(s)", batches.len());
+            }
+            for batch in batches {
+                // NOTE: See the note in `queue_changelog_posts`.
+                if tx.send(batch).await.is_err() {
+                    break;
+                }
+            }
+        },
+        Err(err) => error!(?err, "failed to retrieve undelivered announcements"),
+    }
 
     // PANICS: The interval used is always positive and thus a valid `std::time::Duration`.
     let mut interval = time::interval(INTERVAL.to_std().unwrap());
+    let mut last_scan = None;
     loop {
         // NOTE: The first call to `tick` yields immediately. Also, `Interval` already accounts for time passed
-        //       since the previous call, so we don't have to handle it manually.
-        interval.tick().await;
+        //       since the previous call, so we don't have to handle it manually. Once shutting down, we stop
+        //       queueing announcements, which lets the announcing task finish once it has sent the queued ones.
+        //       Forced scans push the next regular scan back by a whole interval.
+        tokio::select! {
+            _ = interval.tick() => {},
+            _ = data.scan.notified() => interval.reset(),
+            _ = data.shutdown.reached(Phase::Draining) => break,
+        }
+
+        // NOTE: Each scan picks up where the previous one left off, so that forced scans don't announce anything
+        //       twice. We can't know when the last scan before starting was, so the first one covers a whole
+        //       interval.
+        let now = data.clock.now().fixed_offset();
+        let window = last_scan.unwrap_or(now - INTERVAL)..now;
+        last_scan = Some(now);
+
+        // NOTE: The announcing task needs the connection for every batch, so batches are only queued once it's been
+        //       released. Otherwise, a scan finding more batches than the channel holds would wait for the announcing
+        //       task while the announcing task waits for the connection. See the note in `queue_changelog_posts`.
+        let found = RefCell::new(Vec::new());
 
-        let tx = tx.clone();
-        if let Err(err) = task::block_in_place(|| queue_birthday_announcements(&data, tx)) {
+        let started = Instant::now();
+        let queued = task::block_in_place(|| {
+            let conn = data.conn.lock()?;
+            queue_birthday_announcements(&conn, window.clone(), &found)
+        });
+        if let Err(err) = queued {
             error!("failed to announce all birthdays: {}", err);
         }
+        data.metrics.record_scan(started.elapsed());
+
+        let queued = task::block_in_place(|| {
+            let conn = data.conn.lock()?;
+            queue_event_announcements(&conn, window.clone(), &found)
+        });
+        if let Err(err) = queued {
+            error!("failed to announce all events: {}", err);
+        }
+
+        task::block_in_place(|| {
+            for anns in found.take() {
+                if !tx.queue(anns) {
+                    break;
+                }
+            }
+        });
+
+        if let Err(err) = queue_anniversary_announcements(&ctx, &data, window, &tx).await {
+            error!("failed to announce all anniversaries: {}", err);
+        }
     }
 }
 
-#[derive(Debug)]
+// NOTE: Batches keep track of how many times they've failed to be sent, so that retries can back off further.
+#[derive(Debug, Serialize, Deserialize)]
+struct Batch {
+    announcements: Vec<Announcement>,
+    attempts: u32,
+}
+
+impl From<Vec<Announcement>> for Batch {
+    fn from(announcements: Vec<Announcement>) -> Self {
+        Self {
+            announcements,
+            attempts: 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 struct Announcement {
-    user_id: UserId,
     guild_id: GuildId,
     channel_id: ChannelId,
-    birthday: Birthday,
+    occasion: Occasion,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum Occasion {
+    Birthday(UserId, Birthday),
+    Anniversary(UserId, DateTime<FixedOffset>),
+    Event(Event),
+}
+
+impl Occasion {
+    fn user_id(&self) -> Option<UserId> {
+        match self {
+            Self::Birthday(user_id, _) | Self::Anniversary(user_id, _) => Some(*user_id),
+            Self::Event(event) => event.user_id,
+        }
+    }
+
+    fn date(&self) -> DateTime<FixedOffset> {
+        match self {
+            Self::Birthday(_, birthday) => birthday.0,
+            Self::Anniversary(_, joined_at) => *joined_at,
+            Self::Event(event) => event.date.0,
+        }
+    }
+
+    // NOTE: Birthdays count years in the calendar they recur in, which can differ from the Gregorian years since
+    //       they were born.
+    fn years(&self, now: DateTime<FixedOffset>) -> Option<u32> {
+        match self {
+            Self::Birthday(_, birthday) => birthday.age(now).ok(),
+            _ => now.years_since(self.date()),
+        }
+    }
+}
+
+// NOTE: Announcements are sent in batches so that guilds which group birthdays found in the same scan together can
+//       receive them in a single message. Every batch is non-empty, and all of its announcements belong to the same
+//       guild.
 #[tracing::instrument]
-async fn announce_birthdays(ctx: Context, mut rx: Receiver<Announcement>) {
-    while let Some(ann) = rx.recv().await {
-        let Announcement {
-            user_id,
+async fn announce_birthdays(ctx: Context, data: State, mut rx: Receiver<Batch>) {
+    loop {
+        let Batch {
+            announcements: anns,
+            attempts,
+        } = tokio::select! {
+            batch = rx.recv() => match batch {
+                Some(batch) => batch,
+                None => break,
+            },
+            _ = data.shutdown.reached(Phase::Stopped) => {
+                persist_announcements(&data, &mut rx);
+                break;
+            },
+        };
+
+        let now = data.clock.now().fixed_offset();
+
+        // PANICS: Batches are never empty.
+        let (guild_id, channel_id) = anns
+            .first()
+            .map(|ann| (ann.guild_id, ann.channel_id))
+            .unwrap();
+
+        // NOTE: We still announce birthdays even if the guild's settings can't be retrieved, since an announcement
+        //       with the default settings is better than no announcement at all.
+        let (language, pings, automation, milestones, anniversary_template) =
+            task::block_in_place(|| {
+                let conn = data.conn.lock()?;
+                let language = locale::load(&conn, &ctx, guild_id)?;
+                Ok::<_, Error>((
+                    language,
+                    Pings::load(&conn, guild_id)?,
+                    Automation::load(&conn, guild_id)?,
+                    milestone::load(&conn, guild_id)?,
+                    anniversary::load_template(&conn, guild_id, language)?,
+                ))
+            })
+            .unwrap_or_else(|err| {
+                error!(
+                    ?err,
+                    "failed to retrieve announcement settings for {}", guild_id
+                );
+                Default::default()
+            });
+
+        // NOTE: Future dates are rejected when setting birthdays and adding events, and members can't join in the
+        //       future, but the clock may have moved backwards since the announcements were queued.
+        let (anns, ages) = anns
+            .into_iter()
+            .filter_map(|ann| match ann.occasion.years(now) {
+                Some(years) => Some((ann, years)),
+                None => {
+                    error!(?ann, "skipping announcement for a date in the future");
+                    None
+                },
+            })
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+        if anns.is_empty() {
+            continue;
+        }
+
+        let mut role_ids = Vec::new();
+        let embed = match (anns.as_slice(), ages.as_slice()) {
+            ([ann], &[years]) => match &ann.occasion {
+                Occasion::Anniversary(user_id, _) => {
+                    // NOTE: The guild may have opted out of anniversaries after this one was queued.
+                    let template = anniversary_template
+                        .clone()
+                        .unwrap_or_else(|| anniversary::default_template(language));
+                    announcement(&tr!(language, "anniversary-announcement"))
+                        .description(anniversary::render(&template, *user_id, years))
+                        .field(tr!(language, "field-years"), years.to_string(), true)
+                },
+                Occasion::Event(event) => {
+                    let description = event.description.clone().unwrap_or_else(|| {
+                        tr!(
+                            language,
+                            "event-announcement-description",
+                            name = event.name.as_str(),
+                        )
+                    });
+                    let embed = announcement(&event.name).description(description).field(
+                        tr!(language, "field-years"),
+                        years.to_string(),
+                        true,
+                    );
+                    match event.user_id {
+                        Some(user_id) => embed.field(
+                            tr!(language, "field-member"),
+                            format!("<@{}>", user_id),
+                            true,
+                        ),
+                        None => embed,
+                    }
+                },
+                Occasion::Birthday(user_id, _) => {
+                    let age = years;
+                    let milestone = milestone::find(&milestones, age);
+                    let title = milestone
+                        .and_then(|milestone| milestone.title(age))
+                        .unwrap_or_else(|| tr!(language, "birthday-announcement"));
+
+                    let embed = announcement(&title)
+                        .description(tr!(
+                            language,
+                            "birthday-announcement-description",
+                            user = format!("<@{}>", user_id),
+                        ))
+                        .field(tr!(language, "field-age"), age.to_string(), true);
+
+                    role_ids.extend(milestone.and_then(|milestone| milestone.role_id));
+                    match milestone.and_then(|milestone| milestone.colour) {
+                        Some(colour) => embed.colour(colour),
+                        None => embed,
+                    }
+                },
+            },
+            (anns, ages) => {
+                let mut description = tr!(language, "birthday-announcement-shared");
+                description.push_str("\n\n");
+                for (ann, &age) in anns.iter().zip(ages) {
+                    // NOTE: Only birthdays are ever grouped, so every announcement in a batch has a celebrant.
+                    let Some(user_id) = ann.occasion.user_id() else {
+                        continue;
+                    };
+                    description.push_str(&tr!(
+                        language,
+                        "birthday-announcement-turns",
+                        user = format!("<@{}>", user_id),
+                        age = age,
+                    ));
+
+                    // NOTE: We can't style a shared announcement after a single milestone, so we just highlight
+                    //       everyone who has reached one.
+                    if let Some(milestone) = milestone::find(&milestones, age) {
+                        description.push_str(" :star:");
+                        role_ids.extend(milestone.role_id);
+                    }
+                    description.push('\n');
+                }
+
+                announcement(&tr!(language, "birthday-announcement")).description(description)
+            },
+        };
+        role_ids.sort_unstable();
+        role_ids.dedup();
+
+        // NOTE: Mentions inside embeds don't notify anyone, so we mention everyone involved in the message content.
+        let user_ids = anns
+            .iter()
+            .filter_map(|ann| ann.occasion.user_id())
+            .collect::<Vec<_>>();
+        let (content, mentions) = pings.render(&user_ids, &role_ids);
+
+        let message = CreateMessage::default()
+            .embed(embed)
+            .allowed_mentions(mentions);
+        let message = if content.is_empty() {
+            message
+        } else {
+            message.content(content)
+        };
+
+        // We continue announcing other birthdays even if some of them fail to be announced.
+        let sent = channel_id.send_message(&ctx, message).await;
+        data.metrics.record_announcement(sent.is_ok());
+        let message = match sent {
+            Ok(message) => message,
+            Err(err) => {
+                error!(
+                    ?err,
+                    ?user_ids,
+                    ?guild_id,
+                    "failed to send birthday announcement to {}",
+                    channel_id,
+                );
+                let batch = Batch {
+                    announcements: anns,
+                    attempts: attempts + 1,
+                };
+                retry_later(&data, guild_id, channel_id, batch, &err);
+                continue;
+            },
+        };
+
+        for reaction in &automation.reactions {
+            if let Err(err) = message.react(&ctx, reaction.clone()).await {
+                error!(
+                    ?err,
+                    ?reaction,
+                    ?guild_id,
+                    "failed to react to birthday announcement {}",
+                    message.id,
+                );
+            }
+        }
+
+        let thread_id = if automation.thread {
+            // NOTE: Anniversaries and events are never grouped, so only single announcements can be for them.
+            let name = match anns.as_slice() {
+                [
+                    Announcement {
+                        occasion: Occasion::Event(event),
+                        ..
+                    },
+                ] => event.name.clone(),
+                [
+                    Announcement {
+                        occasion: Occasion::Anniversary(..),
+                        ..
+                    },
+                ] => thread_name(&ctx, language, guild_id, &user_ids, Greeting::Anniversary).await,
+                _ => thread_name(&ctx, language, guild_id, &user_ids, Greeting::Birthday).await,
+            };
+            match channel_id
+                .create_thread_from_message(&ctx, message.id, CreateThread::new(name))
+                .await
+            {
+                Ok(thread) => Some(thread.id),
+                Err(err) => {
+                    error!(
+                        ?err,
+                        ?guild_id,
+                        "failed to create thread for birthday announcement {}",
+                        message.id,
+                    );
+                    None
+                },
+            }
+        } else {
+            None
+        };
+
+        // NOTE: We keep track of sent announcements (and their threads) so that they can be cleaned up later.
+        let stored = task::block_in_place(|| {
+            let conn = data.conn.lock()?;
+            let query = "insert into messages (guild_id, channel_id, message_id, thread_id, \
+                         sent_at) values (?1, ?2, ?3, ?4, ?5)";
+            conn.execute(
+                query,
+                (
+                    // NOTE: See the note in `birthday::get`.
+                    guild_id.get() as i64,
+                    channel_id.get() as i64,
+                    message.id.get() as i64,
+                    thread_id.map(|id| id.get() as i64),
+                    now,
+                ),
+            )?;
+            Ok::<_, Error>(())
+        });
+        if let Err(err) = stored {
+            error!(
+                ?err,
+                ?guild_id,
+                ?thread_id,
+                "failed to store birthday announcement {}",
+                message.id,
+            );
+        }
+
+        // NOTE: Birthday cards are posted in the announcement's thread (if there is one) when they can't be DM'd.
+        let fallback_id = thread_id.unwrap_or(channel_id);
+        for ann in &anns {
+            if let Occasion::Birthday(user_id, _) = ann.occasion {
+                deliver_card(&ctx, &data, language, guild_id, fallback_id, user_id).await;
+            }
+        }
+    }
+}
+
+/// Schedules an announcement that failed to be sent to be retried, unless retrying it is pointless.
+fn retry_later(
+    data: &State,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    batch: Batch,
+    err: &serenity::Error,
+) {
+    if !outbox::is_transient(err) || batch.attempts >= outbox::MAX_ATTEMPTS {
+        warn!(
+            attempts = batch.attempts,
+            ?guild_id,
+            "giving up on birthday announcement to {}",
+            channel_id,
+        );
+        return;
+    }
+
+    let scheduled = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        let error = err.to_string();
+        outbox::schedule(
+            &conn,
             guild_id,
             channel_id,
-            birthday,
-        } = ann;
+            KIND,
+            &batch,
+            batch.attempts,
+            &error,
+            data.clock.now(),
+        )
+    });
+    match scheduled {
+        Ok(next_attempt) => info!(
+            attempts = batch.attempts,
+            ?guild_id,
+            "retrying birthday announcement to {} at {}",
+            channel_id,
+            next_attempt,
+        ),
+        Err(err) => error!(
+            ?err,
+            ?guild_id,
+            "failed to schedule retrying birthday announcement to {}",
+            channel_id,
+        ),
+    }
+}
 
-        let now = Utc::now().fixed_offset();
-        let age = now.years_since(birthday.0).unwrap(); // PANICS: Future dates are rejected when setting birthdays
+/// Persists every queued announcement so that they can be sent on the next start.
+fn persist_announcements(data: &State, rx: &mut Receiver<Batch>) {
+    rx.close();
+    let mut batches = Vec::new();
+    while let Ok(batch) = rx.try_recv() {
+        batches.push(batch);
+    }
+    if batches.is_empty() {
+        return;
+    }
 
-        let embed = announcement("Happy birthday!")
-            .description(format!("It's <@{}>'s birthday! :partying_face:", user_id))
-            .field("Age", age.to_string(), true);
+    let persisted = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        for batch in &batches {
+            // PANICS: Batches are never empty.
+            let guild_id = batch.announcements.first().unwrap().guild_id;
+            undelivered::store(&conn, guild_id, KIND, batch)?;
+        }
+        Ok::<_, Error>(())
+    });
+    match persisted {
+        Ok(()) => info!("persisted {} undelivered announcement(s)", batches.len()),
+        Err(err) => error!(?err, "failed to persist undelivered announcements"),
+    }
+}
 
-        let message = CreateMessage::default().embed(embed);
+async fn deliver_card(
+    ctx: &Context,
+    data: &State,
+    language: Language,
+    guild_id: GuildId,
+    fallback_id: ChannelId,
+    user_id: UserId,
+) {
+    let wishes = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        Ok::<_, Error>(wish::load(&conn, guild_id, user_id)?)
+    });
+    let wishes = match wishes {
+        Ok(wishes) if wishes.is_empty() => return,
+        Ok(wishes) => wishes,
+        Err(err) => {
+            error!(?err, ?guild_id, "failed to retrieve wishes for {}", user_id);
+            return;
+        },
+    };
 
-        // We continue announcing other birthdays even if some of them fail to be announced.
-        if let Err(err) = channel_id.send_message(&ctx, message).await {
+    let guild_name = guild_id
+        .name(ctx)
+        .unwrap_or_else(|| tr!(language, "the-server"));
+
+    // NOTE: Birthday cards are meant for the celebrant, so we try to DM them first. If that fails (usually because
+    //       they don't accept DMs from server members), we post the card publicly instead.
+    let mut dm = true;
+    for page in wish::card(language, &guild_name, &wishes) {
+        if dm {
+            let message = CreateMessage::default().embed(page.clone());
+            match user_id.direct_message(ctx, message).await {
+                Ok(_) => continue,
+                Err(err) => {
+                    warn!(
+                        ?err,
+                        ?guild_id,
+                        "failed to DM birthday card to {}, posting it in {} instead",
+                        user_id,
+                        fallback_id,
+                    );
+                    dm = false;
+                },
+            }
+        }
+
+        let message = CreateMessage::default().embed(page);
+        if let Err(err) = fallback_id.send_message(ctx, message).await {
             error!(
                 ?err,
-                ?user_id,
                 ?guild_id,
-                "failed to send birthday announcement to {}",
-                channel_id,
+                "failed to deliver birthday card for {} to {}",
+                user_id,
+                fallback_id,
             );
+            return;
         }
     }
+
+    // NOTE: Wishes are only cleared once the whole card has been delivered, so that a failed delivery doesn't lose
+    //       them.
+    let cleared = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        Ok::<_, Error>(wish::clear(&conn, guild_id, user_id)?)
+    });
+    if let Err(err) = cleared {
+        error!(?err, ?guild_id, "failed to clear wishes for {}", user_id);
+    }
 }
 
-#[tracing::instrument]
-fn queue_birthday_announcements(data: &State, tx: Sender<Announcement>) -> Result<()> {
-    // NOTE: If we calculate the interval inside the loop, it's entirely possible (although unlikely) for
-    //       enough time to pass during an iteration that the next iteration's interval misses out on a
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Greeting {
+    Birthday,
+    Anniversary,
+}
+
+async fn thread_name(
+    ctx: &Context,
+    language: Language,
+    guild_id: GuildId,
+    user_ids: &[UserId],
+    greeting: Greeting,
+) -> String {
+    let generic = match greeting {
+        Greeting::Birthday => tr!(language, "thread-birthday-generic"),
+        Greeting::Anniversary => tr!(language, "thread-anniversary-generic"),
+    };
+
+    let mut names = Vec::new();
+    for &user_id in user_ids {
+        match guild_id.member(ctx, user_id).await {
+            Ok(member) => names.push(member.display_name().to_owned()),
+            Err(err) => {
+                error!(?err, ?guild_id, "failed to retrieve member {}", user_id);
+                return generic;
+            },
+        }
+    }
+
+    let names = match names.as_slice() {
+        [init @ .., last] if !init.is_empty() => tr!(
+            language,
+            "list-and",
+            init = init.join(", "),
+            last = last.as_str(),
+        ),
+        _ => names.concat(),
+    };
+
+    // NOTE: Thread names can be at most 100 characters long, so we fall back to a generic name for long ones.
+    let name = match greeting {
+        Greeting::Birthday => tr!(language, "thread-birthday", names = names),
+        Greeting::Anniversary => tr!(language, "thread-anniversary", names = names),
+    };
+    if name.chars().count() > 100 {
+        generic
+    } else {
+        name
+    }
+}
+
+#[tracing::instrument(skip(conn, sink))]
+fn queue_birthday_announcements(
+    conn: &Connection,
+    window: Range<DateTime<FixedOffset>>,
+    sink: &impl AnnouncementSink,
+) -> Result<()> {
+    // NOTE: If we calculate the window inside the loop, it's entirely possible (although unlikely) for
+    //       enough time to pass during an iteration that the next iteration's window misses out on a
     //       birthday which just happened to be during that time spent in the first iteration. As a result,
-    //       it's better to fix the interval before we enter the loop and then make up for any time spent
-    //       in the loop by waiting less until the next birthday announcement check.
-    let now = Utc::now().fixed_offset();
-    let interval = now - INTERVAL..now;
+    //       the window is fixed before we enter the loop, and the next scan picks up wherever it ended.
+    let now = window.end;
 
-    let conn = data.conn.lock().unwrap();
-    let mut stmt = conn.prepare("select user_id, guild_id, birthday from birthdays")?;
+    let query = "select birthdays.user_id, birthdays.guild_id, birthday, coalesce(calendar, \
+                 'gregorian') from birthdays left join calendars on birthdays.user_id = \
+                 calendars.user_id and birthdays.guild_id = calendars.guild_id";
+    let mut stmt = conn.prepare(query)?;
     let mut rows = stmt.query(())?;
 
+    // NOTE: Announcements for guilds that group birthdays found in the same scan together are collected here and
+    //       only queued once we've gone through every birthday.
+    let mut grouped = HashMap::<GuildId, Vec<Announcement>>::new();
+
     while let Some(row) = rows.next()? {
         // NOTE: See the note in `birthday::get`.
         let user_id = row.get(0).map(|id: i64| UserId::new(id as u64))?;
         let guild_id = row.get(1).map(|id: i64| GuildId::new(id as u64))?;
-        let birthday = row.get(2).map(Birthday)?;
-
-        // NOTE: We can't just use `birthday.0.with_year(now.year())` due to edge cases that might create
-        //       invalid dates, such as Feb 29 in a non-leap year. Instead, we figure out how many years
-        //       have passed since the date of birth, then add that many years to it. This produces a date
-        //       that is either in the year before the current date, or in the same year as the current date.
-        //       In the former case, the user has not celebrated their birthday this year, while in the latter
-        //       case, the user's birthday has already passed this year.
-        let years = now.years_since(birthday.0).unwrap(); // PANICS: Birthdays are always in the past
-        let last_birthday = birthday
-            .0
-            .checked_add_months(Months::new(years * 12))
-            .unwrap();
-        if !interval.contains(&last_birthday) {
+
+        // NOTE: A single bad row shouldn't stop every other birthday from being announced, so we skip it instead.
+        let occurrence = row
+            .get(2)
+            .and_then(|date| Ok(Birthday(date, row.get(3)?)))
+            .map_err(Error::from)
+            .and_then(|birthday| {
+                let last_birthday = birthday.last_occurrence(now)?;
+                Ok((birthday, last_birthday))
+            });
+        let (birthday, last_birthday) = match occurrence {
+            Ok(occurrence) => occurrence,
+            Err(err) => {
+                error!(?err, ?guild_id, "skipping invalid birthday of {}", user_id);
+                continue;
+            },
+        };
+        if !window.contains(&last_birthday) {
             continue;
         }
 
@@ -127,25 +732,620 @@ fn queue_birthday_announcements(data: &State, tx: Sender<Announcement>) -> Resul
         };
 
         let ann = Announcement {
-            user_id,
             guild_id,
             channel_id,
-            birthday,
+            occasion: Occasion::Birthday(user_id, birthday),
         };
 
+        let is_grouped = conn
+            .prepare("select grouped from groupings where guild_id = ?1")?
+            .query((guild_id.get() as i64,))? // NOTE: See the note in `birthday::get`.
+            .next()?
+            .map(|row| row.get(0))
+            .transpose()?
+            .unwrap_or(false);
+
+        if is_grouped {
+            grouped.entry(guild_id).or_default().push(ann);
+            continue;
+        }
+
+        if !sink.queue(vec![ann]) {
+            return Ok(());
+        }
+    }
+
+    for anns in grouped.into_values() {
+        if !sink.queue(anns) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Where announcements are queued once they're found.
+///
+/// Finding announcements doesn't depend on Discord, so this lets them be collected somewhere other than the
+/// announcing task, such as in tests.
+trait AnnouncementSink {
+    /// Queues a batch of announcements, returning whether more can be queued.
+    fn queue(&self, anns: Vec<Announcement>) -> bool;
+}
+
+impl AnnouncementSink for Sender<Batch> {
+    fn queue(&self, anns: Vec<Announcement>) -> bool {
         // NOTE: `Sender::blocking_send` only fails if the corresponding receiver has been closed, at which point
         //       there's no reason to continue checking for birthdays since we can't announce them anyways.
-        let Ok(()) = tx.blocking_send(ann) else {
-            error!(
-                ?channel_id,
-                ?birthday,
-                "failed to queue birthday announcement for {} in {}",
-                user_id,
-                guild_id,
-            );
+        let Err(err) = self.blocking_send(anns.into()) else {
+            return true;
+        };
+
+        let user_ids = err
+            .0
+            .announcements
+            .iter()
+            .filter_map(|ann| ann.occasion.user_id())
+            .collect::<Vec<_>>();
+        let ann = err.0.announcements.first().unwrap(); // PANICS: Batches are never empty
+        error!(
+            channel_id = ?ann.channel_id,
+            ?user_ids,
+            "failed to queue birthday announcements in {}",
+            ann.guild_id,
+        );
+
+        false
+    }
+}
+
+impl AnnouncementSink for RefCell<Vec<Vec<Announcement>>> {
+    fn queue(&self, anns: Vec<Announcement>) -> bool {
+        self.borrow_mut().push(anns);
+        true
+    }
+}
+
+#[tracing::instrument(skip(conn, sink))]
+fn queue_event_announcements(
+    conn: &Connection,
+    window: Range<DateTime<FixedOffset>>,
+    sink: &impl AnnouncementSink,
+) -> Result<()> {
+    // NOTE: See the note in `queue_birthday_announcements`.
+    let now = window.end;
+
+    let query = "select events.guild_id, channel_id, name, description, user_id, date from events \
+                 join announcements on events.guild_id = announcements.guild_id where channel_id \
+                 is not null";
+    let mut stmt = conn.prepare(query)?;
+    let mut rows = stmt.query(())?;
+
+    while let Some(row) = rows.next()? {
+        // NOTE: See the note in `birthday::get`.
+        let guild_id = row.get(0).map(|id: i64| GuildId::new(id as u64))?;
+        let channel_id = row.get(1).map(|id: i64| ChannelId::new(id as u64))?;
+        let event = Event {
+            name: row.get(2)?,
+            description: row.get(3)?,
+            // NOTE: See the note in `birthday::get`.
+            user_id: row
+                .get::<_, Option<i64>>(4)?
+                .map(|id| UserId::new(id as u64)),
+            date: row.get(5).map(|date| Birthday(date, Calendar::Gregorian))?,
+        };
+
+        // NOTE: See the note in `queue_birthday_announcements`.
+        let last_occurrence = match event.date.last_occurrence(now) {
+            Ok(last_occurrence) => last_occurrence,
+            Err(err) => {
+                error!(?err, ?guild_id, "skipping invalid event {}", event.name);
+                continue;
+            },
+        };
+
+        // NOTE: Like with anniversaries, the original date of an event isn't worth announcing.
+        if last_occurrence == event.date.0 || !window.contains(&last_occurrence) {
+            continue;
+        }
+
+        let ann = Announcement {
+            guild_id,
+            channel_id,
+            occasion: Occasion::Event(event),
+        };
+
+        if !sink.queue(vec![ann]) {
             break;
+        }
+    }
+
+    Ok(())
+}
+
+/// An announcement that would have been sent while simulating a day.
+#[derive(Debug)]
+pub struct Simulated {
+    pub at: DateTime<FixedOffset>,
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
+    pub occasions: Vec<String>,
+}
+
+/// Replays every scan for birthdays and events over a day (in UTC), returning what would have been announced
+/// without announcing anything.
+///
+/// Anniversaries are left out, since finding them requires fetching members from Discord.
+pub fn simulate(conn: &Connection, day: NaiveDate) -> Result<Vec<Simulated>> {
+    // NOTE: Each scan covers the interval before it, so the first scan of the day is one interval after midnight,
+    //       and the last one is at midnight the next day.
+    let clock = ManualClock::new(day.and_time(NaiveTime::MIN).and_utc());
+    let end = clock.now() + TimeDelta::days(1);
+
+    let sink = RefCell::new(Vec::new());
+    let mut simulated = Vec::new();
+    while clock.now() < end {
+        clock.advance(INTERVAL);
+        let now = clock.now().fixed_offset();
+
+        queue_birthday_announcements(conn, now - INTERVAL..now, &sink)?;
+        queue_event_announcements(conn, now - INTERVAL..now, &sink)?;
+
+        for anns in sink.take() {
+            // PANICS: Batches are never empty.
+            let (guild_id, channel_id) = anns
+                .first()
+                .map(|ann| (ann.guild_id, ann.channel_id))
+                .unwrap();
+            let occasions = anns
+                .iter()
+                .map(|ann| {
+                    // NOTE: See the note in `announce_birthdays`.
+                    let years = ann.occasion.years(now).unwrap_or_default();
+                    match &ann.occasion {
+                        Occasion::Birthday(user_id, _) => {
+                            format!("birthday of {} (turning {})", user_id, years)
+                        },
+                        Occasion::Anniversary(user_id, _) => {
+                            format!("anniversary of {} ({} years)", user_id, years)
+                        },
+                        Occasion::Event(event) => {
+                            format!("event `{}` ({} years)", event.name, years)
+                        },
+                    }
+                })
+                .collect();
+            simulated.push(Simulated {
+                at: now,
+                guild_id,
+                channel_id,
+                occasions,
+            });
+        }
+    }
+
+    Ok(simulated)
+}
+
+#[tracing::instrument(skip(ctx))]
+async fn queue_anniversary_announcements(
+    ctx: &Context,
+    data: &State,
+    window: Range<DateTime<FixedOffset>>,
+    tx: &Sender<Batch>,
+) -> Result<()> {
+    // NOTE: See the note in `queue_birthday_announcements`.
+    let now = window.end;
+
+    let guilds = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        let query = "select anniversaries.guild_id, channel_id from anniversaries join \
+                     announcements on anniversaries.guild_id = announcements.guild_id where \
+                     channel_id is not null";
+        let mut stmt = conn.prepare(query)?;
+        let mut rows = stmt.query(())?;
+
+        let mut guilds = Vec::new();
+        while let Some(row) = rows.next()? {
+            // NOTE: See the note in `birthday::get`.
+            let guild_id = row.get(0).map(|id: i64| GuildId::new(id as u64))?;
+            let channel_id = row.get(1).map(|id: i64| ChannelId::new(id as u64))?;
+            guilds.push((guild_id, channel_id));
+        }
+
+        Ok::<_, Error>(guilds)
+    })?;
+
+    for (guild_id, channel_id) in guilds {
+        // We continue checking other guilds even if the members of some of them can't be retrieved.
+        let members = match members(ctx, guild_id, data.members_intent).await {
+            Ok(members) => members,
+            Err(err) => {
+                error!(?err, "failed to retrieve members of {}", guild_id);
+                continue;
+            },
+        };
+
+        let opted_out = task::block_in_place(|| {
+            let conn = data.conn.lock()?;
+            let query = "select user_id from anniversary_optouts where guild_id = ?1";
+            let mut stmt = conn.prepare(query)?;
+            let mut rows = stmt.query((guild_id.get() as i64,))?; // NOTE: See the note in `birthday::get`.
+
+            let mut opted_out = HashSet::new();
+            while let Some(row) = rows.next()? {
+                // NOTE: See the note in `birthday::get`.
+                opted_out.insert(row.get(0).map(|id: i64| UserId::new(id as u64))?);
+            }
+
+            Ok::<_, Error>(opted_out)
+        });
+        let opted_out = match opted_out {
+            Ok(opted_out) => opted_out,
+            Err(err) => {
+                error!(
+                    ?err,
+                    "failed to retrieve anniversary opt-outs of {}", guild_id
+                );
+                continue;
+            },
         };
+
+        for (user_id, joined_at) in members {
+            if opted_out.contains(&user_id) {
+                continue;
+            }
+
+            // NOTE: The day a member joins isn't an anniversary, so we skip members who joined within the window.
+            // NOTE: Members can't join in the future, but clocks can be wrong.
+            let Some(last_anniversary) = recurrence::last(joined_at, now) else {
+                continue;
+            };
+            if last_anniversary == joined_at || !window.contains(&last_anniversary) {
+                continue;
+            }
+
+            let ann = Announcement {
+                guild_id,
+                channel_id,
+                occasion: Occasion::Anniversary(user_id, joined_at),
+            };
+
+            // NOTE: See the note in `AnnouncementSink::queue`.
+            let Ok(()) = tx.send(vec![ann].into()).await else {
+                error!(
+                    ?channel_id,
+                    "failed to queue anniversary announcement for {} in {}", user_id, guild_id,
+                );
+                return Ok(());
+            };
+        }
     }
 
     Ok(())
 }
+
+// NOTE: Members are only cached with the privileged `GUILD_MEMBERS` intent, so without it we fetch them over HTTP
+//       instead. This still requires the intent to be enabled in the developer portal, but not to be requested.
+async fn members(
+    ctx: &Context,
+    guild_id: GuildId,
+    cached: bool,
+) -> Result<Vec<(UserId, DateTime<FixedOffset>)>> {
+    fn joined(member: &Member) -> Option<(UserId, DateTime<FixedOffset>)> {
+        if member.user.bot {
+            return None;
+        }
+        member
+            .joined_at
+            .map(|joined_at| (member.user.id, joined_at.fixed_offset()))
+    }
+
+    // NOTE: Large guilds only have some of their members cached, since we don't request the rest from the gateway.
+    //       Their members are fetched over HTTP instead, so that nobody's anniversary is missed.
+    if cached {
+        let members = guild_id.to_guild_cached(ctx).and_then(|guild| {
+            let complete = guild.members.len() as u64 >= guild.member_count;
+            complete.then(|| guild.members.values().filter_map(joined).collect())
+        });
+        if let Some(members) = members {
+            return Ok(members);
+        }
+    }
+
+    let mut members = Vec::new();
+    let mut after = None;
+    loop {
+        let page = guild_id.members(ctx, Some(MEMBERS_PER_PAGE), after).await?;
+        members.extend(page.iter().filter_map(joined));
+
+        if page.len() < MEMBERS_PER_PAGE as usize {
+            break;
+        }
+        after = page.last().map(|member| member.user.id);
+    }
+
+    Ok(members)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use crate::db;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Collected {
+        batches: RefCell<Vec<Vec<Announcement>>>,
+        capacity: Option<usize>,
+    }
+
+    impl AnnouncementSink for Collected {
+        fn queue(&self, anns: Vec<Announcement>) -> bool {
+            let mut batches = self.batches.borrow_mut();
+            batches.push(anns);
+            self.capacity
+                .is_none_or(|capacity| batches.len() < capacity)
+        }
+    }
+
+    impl Collected {
+        fn occasions(&self) -> Vec<Vec<(u64, Option<u64>)>> {
+            let mut batches = self
+                .batches
+                .borrow()
+                .iter()
+                .map(|anns| {
+                    let mut occasions = anns
+                        .iter()
+                        .map(|ann| (ann.guild_id.get(), ann.occasion.user_id().map(UserId::get)))
+                        .collect::<Vec<_>>();
+                    occasions.sort_unstable();
+                    occasions
+                })
+                .collect::<Vec<_>>();
+            batches.sort_unstable();
+            batches
+        }
+    }
+
+    fn at(rfc3339: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap()
+    }
+
+    fn window() -> Range<DateTime<FixedOffset>> {
+        let now = at("2024-06-01T12:30:00Z");
+        now - INTERVAL..now
+    }
+
+    fn setup() -> Connection {
+        let conn = db::open_in_memory().unwrap();
+        for guild_id in [1, 2] {
+            conn.execute(
+                "insert into announcements (guild_id, channel_id) values (?1, ?2)",
+                (guild_id, guild_id * 10),
+            )
+            .unwrap();
+        }
+        conn
+    }
+
+    fn add_birthday(conn: &Connection, guild_id: i64, user_id: i64, birthday: &str) {
+        conn.execute(
+            "insert into birthdays (user_id, guild_id, birthday) values (?1, ?2, ?3)",
+            (user_id, guild_id, birthday),
+        )
+        .unwrap();
+    }
+
+    fn add_event(conn: &Connection, guild_id: i64, name: &str, date: &str) {
+        conn.execute(
+            "insert into events (guild_id, name, date) values (?1, ?2, ?3)",
+            (guild_id, name, at(date)),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn birthdays_in_the_last_interval_are_announced() {
+        let conn = setup();
+        add_birthday(&conn, 1, 100, &at("2000-06-01T12:00:00Z").to_rfc3339());
+        // NOTE: Announced by the previous scan.
+        add_birthday(&conn, 1, 101, &at("2000-06-01T11:29:59Z").to_rfc3339());
+        // NOTE: Not announced until the next scan.
+        add_birthday(&conn, 1, 102, &at("2000-06-01T12:30:01Z").to_rfc3339());
+        add_birthday(&conn, 1, 103, &at("2000-06-02T12:00:00Z").to_rfc3339());
+        add_birthday(&conn, 2, 200, &at("1990-06-01T14:00:00+02:00").to_rfc3339());
+
+        let sink = Collected::default();
+        queue_birthday_announcements(&conn, window(), &sink).unwrap();
+
+        assert_eq!(
+            sink.occasions(),
+            [vec![(1, Some(100))], vec![(2, Some(200))]],
+        );
+    }
+
+    #[test]
+    fn birthdays_are_announced_in_their_calendar() {
+        let conn = setup();
+        // NOTE: Both were born on Chinese New Year 2000, which fell on Feb 10 in 2024.
+        add_birthday(&conn, 1, 100, &at("2000-02-05T12:00:00Z").to_rfc3339());
+        add_birthday(&conn, 1, 101, &at("2000-02-05T12:00:00Z").to_rfc3339());
+        conn.execute(
+            "insert into calendars (user_id, guild_id, calendar) values (?1, ?2, ?3)",
+            (101, 1, Calendar::Chinese),
+        )
+        .unwrap();
+
+        let now = at("2024-02-10T12:30:00Z");
+        let sink = Collected::default();
+        queue_birthday_announcements(&conn, now - INTERVAL..now, &sink).unwrap();
+        assert_eq!(sink.occasions(), [vec![(1, Some(101))]]);
+
+        let now = at("2024-02-05T12:30:00Z");
+        let sink = Collected::default();
+        queue_birthday_announcements(&conn, now - INTERVAL..now, &sink).unwrap();
+        assert_eq!(sink.occasions(), [vec![(1, Some(100))]]);
+    }
+
+    #[test]
+    fn birthdays_count_years_in_their_calendar() {
+        let date = at("1990-01-01T00:00:00Z");
+        let now = at("2024-01-01T00:00:00Z");
+        let user_id = UserId::new(1);
+
+        // NOTE: Hijri years are about 11 days shorter, so 35 of them have passed but only 34 Gregorian years have.
+        let hijri = Occasion::Birthday(user_id, Birthday(date, Calendar::Hijri));
+        assert_eq!(hijri.years(now), Some(35));
+        let gregorian = Occasion::Birthday(user_id, Birthday(date, Calendar::Gregorian));
+        assert_eq!(gregorian.years(now), Some(34));
+        assert_eq!(Occasion::Anniversary(user_id, date).years(now), Some(34));
+    }
+
+    #[test]
+    fn guilds_without_a_channel_are_skipped() {
+        let conn = setup();
+        conn.execute(
+            "insert into announcements (guild_id, channel_id) values (3, null)",
+            (),
+        )
+        .unwrap();
+        add_birthday(&conn, 3, 300, &at("2000-06-01T12:00:00Z").to_rfc3339());
+        add_birthday(&conn, 4, 400, &at("2000-06-01T12:00:00Z").to_rfc3339());
+
+        let sink = Collected::default();
+        queue_birthday_announcements(&conn, window(), &sink).unwrap();
+
+        assert!(sink.occasions().is_empty());
+    }
+
+    #[test]
+    fn grouped_birthdays_are_batched_by_guild() {
+        let conn = setup();
+        conn.execute(
+            "insert into groupings (guild_id, grouped) values (1, true)",
+            (),
+        )
+        .unwrap();
+        add_birthday(&conn, 1, 100, &at("2000-06-01T12:00:00Z").to_rfc3339());
+        add_birthday(&conn, 1, 101, &at("1995-06-01T12:15:00Z").to_rfc3339());
+        add_birthday(&conn, 2, 200, &at("2000-06-01T12:00:00Z").to_rfc3339());
+        add_birthday(&conn, 2, 201, &at("1995-06-01T12:15:00Z").to_rfc3339());
+
+        let sink = Collected::default();
+        queue_birthday_announcements(&conn, window(), &sink).unwrap();
+
+        assert_eq!(
+            sink.occasions(),
+            [
+                vec![(1, Some(100)), (1, Some(101))],
+                vec![(2, Some(200))],
+                vec![(2, Some(201))],
+            ],
+        );
+    }
+
+    #[test]
+    fn invalid_birthdays_are_skipped() {
+        let conn = setup();
+        add_birthday(&conn, 1, 100, "not a date");
+        add_birthday(&conn, 1, 101, &at("2030-06-01T12:00:00Z").to_rfc3339());
+        add_birthday(&conn, 1, 102, &at("2000-06-01T12:00:00Z").to_rfc3339());
+
+        let sink = Collected::default();
+        queue_birthday_announcements(&conn, window(), &sink).unwrap();
+
+        assert_eq!(sink.occasions(), [vec![(1, Some(102))]]);
+    }
+
+    #[test]
+    fn scanning_stops_once_the_sink_is_closed() {
+        let conn = setup();
+        add_birthday(&conn, 1, 100, &at("2000-06-01T12:00:00Z").to_rfc3339());
+        add_birthday(&conn, 1, 101, &at("2000-06-01T12:00:00Z").to_rfc3339());
+        add_birthday(&conn, 2, 200, &at("2000-06-01T12:00:00Z").to_rfc3339());
+
+        let sink = Collected {
+            capacity: Some(1),
+            ..Default::default()
+        };
+        queue_birthday_announcements(&conn, window(), &sink).unwrap();
+
+        assert_eq!(sink.batches.borrow().len(), 1);
+    }
+
+    #[test]
+    fn events_are_announced_on_anniversaries_only() {
+        let conn = setup();
+        add_event(&conn, 1, "Founding", "2020-06-01T12:00:00Z");
+        // NOTE: The original date isn't an anniversary.
+        add_event(&conn, 1, "Launch", "2024-06-01T12:00:00Z");
+        add_event(&conn, 2, "Rename", "2021-07-01T12:00:00Z");
+
+        let sink = Collected::default();
+        queue_event_announcements(&conn, window(), &sink).unwrap();
+
+        let batches = sink.batches.borrow();
+        let names = batches
+            .iter()
+            .flatten()
+            .map(|ann| match &ann.occasion {
+                Occasion::Event(event) => event.name.as_str(),
+                occasion => panic!("unexpected occasion {:?}", occasion),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Founding"]);
+    }
+
+    #[test]
+    fn simulating_a_day_replays_every_scan() {
+        let conn = setup();
+        add_birthday(&conn, 1, 100, &at("2000-06-01T00:00:00Z").to_rfc3339());
+        add_birthday(&conn, 1, 101, &at("2000-06-01T23:59:59Z").to_rfc3339());
+        add_birthday(&conn, 1, 102, &at("2000-06-02T00:00:00Z").to_rfc3339());
+        add_birthday(&conn, 2, 200, &at("2000-06-01T00:00:00-05:00").to_rfc3339());
+        add_event(&conn, 2, "Founding", "2020-06-01T12:00:00Z");
+
+        let day = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let simulated = simulate(&conn, day).unwrap();
+
+        let simulated = simulated
+            .iter()
+            .map(|simulated| {
+                (
+                    simulated.at.to_rfc3339(),
+                    simulated.guild_id.get(),
+                    simulated.occasions.join(", "),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            simulated,
+            [
+                (
+                    "2024-06-01T01:00:00+00:00".to_owned(),
+                    1,
+                    "birthday of 100 (turning 24)".to_owned(),
+                ),
+                (
+                    "2024-06-01T06:00:00+00:00".to_owned(),
+                    2,
+                    "birthday of 200 (turning 24)".to_owned(),
+                ),
+                (
+                    "2024-06-01T13:00:00+00:00".to_owned(),
+                    2,
+                    "event `Founding` (4 years)".to_owned(),
+                ),
+                (
+                    "2024-06-02T00:00:00+00:00".to_owned(),
+                    1,
+                    "birthday of 101 (turning 24)".to_owned(),
+                ),
+            ],
+        );
+    }
+}
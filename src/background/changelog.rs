@@ -1,74 +1,270 @@
+use std::iter;
+
 use poise::serenity_prelude as serenity;
 
-use serenity::{ChannelId, Context, CreateMessage};
+use serde::{Deserialize, Serialize};
+
+use serenity::{ChannelId, Context, CreateMessage, GuildId};
 
 use tokio::{
-    sync::mpsc::{self, Receiver, Sender},
+    sync::mpsc::{Receiver, Sender, error::SendError},
     task,
 };
 
-use tracing::error;
+use tracing::{error, info, warn};
+
+use crate::{
+    announcement,
+    error::{Error, Result},
+    locale::{self, Language, tr},
+    outbox,
+    shutdown::Phase,
+    state::State,
+    undelivered,
+};
+
+use super::retries::watch_retries;
 
-use crate::{announcement, error::Result, state::State};
+// NOTE: The kind that changelog posts are stored as when they're undelivered or waiting to be retried.
+const KIND: &str = "changelog";
 
-#[tracing::instrument]
-pub async fn announce_updates(ctx: Context, data: State, changelog: String) {
-    let (tx, rx) = mpsc::channel(100);
+const VERSION: &str = concat!(
+    "`",
+    env!("CARGO_PKG_VERSION_MAJOR"),
+    ".",
+    env!("CARGO_PKG_VERSION_MINOR"),
+    ".",
+    env!("CARGO_PKG_VERSION_PATCH"),
+    "`",
+);
+
+// NOTE: Posts keep track of their version and changelog so that they can still be posted correctly after being
+//       persisted and the bot being updated again.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Post {
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    version: String,
+    changelog: String,
+    attempts: u32,
+}
+
+// NOTE: Posts queued after starting (such as when resending a changelog) are sent through `State::updates`, which
+//       `rx` receives from.
+#[tracing::instrument(skip(rx))]
+pub async fn announce_updates(ctx: Context, data: State, rx: Receiver<Post>) {
+    let tx = data.updates.clone();
 
     // Spawn a long-running task for posting changelogs
-    tokio::spawn(post_changelogs(ctx, changelog, rx));
+    data.shutdown.spawn(post_changelogs(ctx, data.clone(), rx));
 
-    if let Err(err) = task::block_in_place(|| queue_changelog_posts(data, tx)) {
+    // Spawn a long-running task for retrying changelog posts that failed to be sent
+    data.shutdown
+        .spawn(watch_retries(data.clone(), KIND, tx.clone()));
+
+    if let Err(err) = task::block_in_place(|| queue_changelog_posts(&data, tx)) {
         error!("failed to announce changelogs in all guilds: {}", err);
     }
 }
 
+/// Queues the current changelog to be posted in a guild again, returning the channel it will be posted in.
+///
+/// Returns `None` if there's no changelog, or the guild has no announcement channel.
+pub async fn resend_changelog(data: &State, guild_id: GuildId) -> Result<Option<ChannelId>> {
+    let Some(changelog) = data.changelog.read()?.clone() else {
+        return Ok(None);
+    };
+
+    let channel_id = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        let query =
+            "select channel_id from announcements where guild_id = ?1 and channel_id is not null";
+        let mut stmt = conn.prepare(query)?;
+        let mut rows = stmt.query((guild_id.get() as i64,))?; // NOTE: See the note in `birthday::get`.
+        rows.next()?
+            .map(|row| row.get(0).map(|id: i64| ChannelId::new(id as u64))) // NOTE: See the note in `birthday::get`.
+            .transpose()
+            .map_err(Error::from)
+    })?;
+    let Some(channel_id) = channel_id else {
+        return Ok(None);
+    };
+
+    let post = Post {
+        guild_id,
+        channel_id,
+        version: VERSION.to_owned(),
+        changelog,
+        attempts: 0,
+    };
+
+    // NOTE: See the note in `queue_changelog_posts`.
+    if let Err(SendError(post)) = data.updates.send(post).await {
+        task::block_in_place(|| {
+            let conn = data.conn.lock()?;
+            undelivered::store(&conn, guild_id, KIND, &post)
+        })?;
+    }
+
+    Ok(Some(channel_id))
+}
+
 #[tracing::instrument]
-async fn post_changelogs(ctx: Context, changelog: String, mut rx: Receiver<ChannelId>) {
-    let version = concat!(
-        "`",
-        env!("CARGO_PKG_VERSION_MAJOR"),
-        ".",
-        env!("CARGO_PKG_VERSION_MINOR"),
-        ".",
-        env!("CARGO_PKG_VERSION_PATCH"),
-        "`",
-    );
-
-    while let Some(channel_id) = rx.recv().await {
-        let embed = announcement("Update")
-            .description("A new update has been released.")
-            .field("Version", version, false)
-            .field("Changelog", format!("```md\n{}\n```", changelog), false);
+async fn post_changelogs(ctx: Context, data: State, mut rx: Receiver<Post>) {
+    loop {
+        let post = tokio::select! {
+            post = rx.recv() => match post {
+                Some(post) => post,
+                None => break,
+            },
+            _ = data.shutdown.reached(Phase::Stopped) => {
+                persist_posts(&data, &mut rx);
+                break;
+            },
+        };
+
+        // NOTE: See the note in `announce_birthdays` about retrieving settings. Changelogs themselves are only
+        //       written in one language, so only the text around them is translated.
+        let language = task::block_in_place(|| {
+            let conn = data.conn.lock()?;
+            Ok::<_, Error>(locale::load(&conn, &ctx, post.guild_id)?)
+        })
+        .unwrap_or_else(|err| {
+            error!(?err, "failed to retrieve language for {}", post.guild_id);
+            Language::default()
+        });
+
+        let embed = announcement(&tr!(language, "update"))
+            .description(tr!(language, "update-description"))
+            .field(tr!(language, "field-version"), &post.version, false)
+            .field(
+                tr!(language, "field-changelog"),
+                format!("```md\n{}\n```", post.changelog),
+                false,
+            );
 
         // We continue announcing updates even if it fails in some channels.
         let msg = CreateMessage::new().embed(embed);
-        if let Err(err) = channel_id.send_message(&ctx.http, msg).await {
-            error!(?err, "failed to announce updates to {}", channel_id);
+        if let Err(err) = post.channel_id.send_message(&ctx.http, msg).await {
+            error!(?err, "failed to announce updates to {}", post.channel_id);
+            let post = Post {
+                attempts: post.attempts + 1,
+                ..post
+            };
+            retry_later(&data, post, &err);
         }
     }
 }
 
+/// Schedules a changelog post that failed to be sent to be retried, unless retrying it is pointless.
+fn retry_later(data: &State, post: Post, err: &serenity::Error) {
+    let Post {
+        guild_id,
+        channel_id,
+        attempts,
+        ..
+    } = post;
+    if !outbox::is_transient(err) || attempts >= outbox::MAX_ATTEMPTS {
+        warn!(
+            attempts,
+            ?guild_id,
+            "giving up on update announcement to {}",
+            channel_id
+        );
+        return;
+    }
+
+    let scheduled = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        let error = err.to_string();
+        let now = data.clock.now();
+        outbox::schedule(
+            &conn, guild_id, channel_id, KIND, &post, attempts, &error, now,
+        )
+    });
+    match scheduled {
+        Ok(next_attempt) => info!(
+            attempts,
+            ?guild_id,
+            "retrying update announcement to {} at {}",
+            channel_id,
+            next_attempt,
+        ),
+        Err(err) => error!(
+            ?err,
+            ?guild_id,
+            "failed to schedule retrying update announcement to {}",
+            channel_id,
+        ),
+    }
+}
+
+/// Persists every queued changelog post so that they can be sent on the next start.
+fn persist_posts(data: &State, rx: &mut Receiver<Post>) {
+    rx.close();
+    let mut posts = Vec::new();
+    while let Ok(post) = rx.try_recv() {
+        posts.push(post);
+    }
+    if posts.is_empty() {
+        return;
+    }
+
+    let persisted = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        for post in &posts {
+            undelivered::store(&conn, post.guild_id, KIND, post)?;
+        }
+        Ok::<_, Error>(())
+    });
+    match persisted {
+        Ok(()) => info!("persisted {} undelivered changelog post(s)", posts.len()),
+        Err(err) => error!(?err, "failed to persist undelivered changelog posts"),
+    }
+}
+
 #[tracing::instrument]
-fn queue_changelog_posts(data: State, tx: Sender<ChannelId>) -> Result<()> {
-    let conn = data.conn.lock().unwrap();
-    let mut stmt =
-        conn.prepare("select channel_id from announcements where channel_id is not null")?;
-    let mut rows = stmt.query(())?;
-
-    while let Some(row) = rows.next()? {
-        // NOTE: See the note in `birthday::get`.
-        let channel_id = row.get(0).map(|id: i64| ChannelId::new(id as u64))?;
-
-        // NOTE: `Sender::blocking_send` only fails if the corresponding receiver has been closed, at which point
-        //       there's no reason to continue since the update announcing task is no longer running.
-        let Ok(()) = tx.blocking_send(channel_id) else {
-            error!(
-                ?channel_id,
-                "failed to queue update announcement for {}", channel_id,
-            );
+fn queue_changelog_posts(data: &State, tx: Sender<Post>) -> Result<()> {
+    let changelog = data.changelog.read()?.clone();
+    let conn = data.conn.lock()?;
+
+    // NOTE: Posts that weren't delivered before the last shutdown are queued first, since they're older.
+    let mut posts = undelivered::take::<Post>(&conn, KIND)?;
+
+    if let Some(changelog) = changelog {
+        let mut stmt = conn.prepare(
+            "select guild_id, channel_id from announcements where channel_id is not null",
+        )?;
+        let mut rows = stmt.query(())?;
+
+        while let Some(row) = rows.next()? {
+            // NOTE: See the note in `birthday::get`.
+            let guild_id = row.get(0).map(|id: i64| GuildId::new(id as u64))?;
+            let channel_id = row.get(1).map(|id: i64| ChannelId::new(id as u64))?;
+            posts.push(Post {
+                guild_id,
+                channel_id,
+                version: VERSION.to_owned(),
+                changelog: changelog.clone(),
+                attempts: 0,
+            });
+        }
+    }
+
+    // NOTE: We release the connection before queueing, since the posting task needs it if it has to persist posts.
+    drop(conn);
+
+    let mut posts = posts.into_iter();
+    for post in posts.by_ref() {
+        // NOTE: `Sender::blocking_send` only fails if the corresponding receiver has been closed, which happens when
+        //       shutting down. The rest of the posts are persisted so they can be queued again on the next start.
+        if let Err(SendError(post)) = tx.blocking_send(post) {
+            let conn = data.conn.lock()?;
+            for post in iter::once(post).chain(posts) {
+                undelivered::store(&conn, post.guild_id, KIND, &post)?;
+            }
             break;
-        };
+        }
     }
 
     Ok(())
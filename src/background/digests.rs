@@ -0,0 +1,221 @@
+use chrono::{DateTime, FixedOffset, NaiveTime, TimeDelta, Timelike};
+
+use poise::serenity_prelude as serenity;
+
+use serenity::{ChannelId, Context, CreateMessage, GuildId, UserId};
+
+use tokio::{
+    sync::mpsc::{self, Receiver, Sender, error::SendError},
+    task,
+    time,
+};
+
+use tracing::error;
+
+use crate::{
+    announcement,
+    birthday::{self, Birthday},
+    digest::Frequency,
+    error::{Error, Result},
+    format,
+    locale::{self, tr},
+    shutdown::Phase,
+    state::State,
+};
+
+const INTERVAL: TimeDelta = TimeDelta::hours(1);
+
+#[tracing::instrument]
+pub async fn watch_digests(ctx: Context, data: State) {
+    let (tx, rx) = mpsc::channel(100);
+
+    // Spawn a long-running task for posting digests found by the digest-checking task
+    data.shutdown.spawn(post_digests(ctx, data.clone(), rx));
+
+    // PANICS: The interval used is always positive and thus a valid `std::time::Duration`.
+    let mut interval = time::interval(INTERVAL.to_std().unwrap());
+    loop {
+        // NOTE: See the note in `watch_birthdays`.
+        tokio::select! {
+            _ = interval.tick() => {},
+            _ = data.shutdown.reached(Phase::Draining) => break,
+        }
+
+        let tx = tx.clone();
+        if let Err(err) = task::block_in_place(|| queue_digests(&data, tx)) {
+            error!("failed to post all digests: {}", err);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Digest {
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    frequency: Frequency,
+    // NOTE: Each birthday comes with its next occurrence, which it's sorted and grouped by.
+    birthdays: Vec<(DateTime<FixedOffset>, UserId, Birthday)>,
+}
+
+// NOTE: Digests are only a reminder of upcoming birthdays, so unlike announcements, any that are still queued when
+//       shutting down are dropped rather than persisted.
+#[tracing::instrument]
+async fn post_digests(ctx: Context, data: State, mut rx: Receiver<Digest>) {
+    loop {
+        let digest = tokio::select! {
+            digest = rx.recv() => match digest {
+                Some(digest) => digest,
+                None => break,
+            },
+            _ = data.shutdown.reached(Phase::Stopped) => break,
+        };
+
+        let Digest {
+            guild_id,
+            channel_id,
+            frequency,
+            birthdays,
+        } = digest;
+
+        // NOTE: See the note in `announce_birthdays` about retrieving settings.
+        let (language, format) = task::block_in_place(|| {
+            let conn = data.conn.lock()?;
+            Ok::<_, Error>((
+                locale::load(&conn, &ctx, guild_id)?,
+                format::load_guild(&conn, guild_id)?.unwrap_or_default(),
+            ))
+        })
+        .unwrap_or_else(|err| {
+            error!(
+                ?err,
+                "failed to retrieve language and format for {}", guild_id
+            );
+            Default::default()
+        });
+
+        let embed = announcement(&frequency.title(language)).description(tr!(
+            language,
+            "digest-description",
+            count = birthdays.len(),
+        ));
+        let embed = embed.fields(birthday::group_by_month(
+            language,
+            format,
+            birthdays
+                .into_iter()
+                .map(|(next_birthday, user_id, birthday)| {
+                    (format!("<@{}>", user_id), birthday, next_birthday)
+                }),
+        ));
+
+        // We continue posting other digests even if some of them fail to be posted.
+        let message = CreateMessage::default().embed(embed);
+        if let Err(err) = channel_id.send_message(&ctx, message).await {
+            error!(
+                ?err,
+                ?guild_id,
+                ?frequency,
+                "failed to post birthday digest to {}",
+                channel_id,
+            );
+        }
+    }
+}
+
+#[tracing::instrument]
+fn queue_digests(data: &State, tx: Sender<Digest>) -> Result<()> {
+    // NOTE: Digests always cover whole days in UTC, regardless of the hour at which they are posted.
+    let now = data.clock.now().fixed_offset();
+    let today = now
+        .date_naive()
+        .and_time(NaiveTime::MIN)
+        .and_utc()
+        .fixed_offset();
+
+    let conn = data.conn.lock()?;
+    let mut digests = Vec::new();
+
+    // NOTE: Digests are posted on the first scan at or after the configured hour, so that a restart or downtime
+    //       during that hour doesn't skip the day's digest. We keep track of when each digest was last posted so
+    //       that later scans on the same day don't post it twice.
+    let query = "select digests.guild_id, channel_id, frequency from digests join announcements \
+                 on digests.guild_id = announcements.guild_id where hour <= ?1 and channel_id is \
+                 not null and (last_posted is null or last_posted < ?2)";
+    let mut stmt = conn.prepare(query)?;
+    let mut rows = stmt.query((now.hour(), today.date_naive()))?;
+
+    while let Some(row) = rows.next()? {
+        // NOTE: See the note in `birthday::get`.
+        let guild_id = row.get(0).map(|id: i64| GuildId::new(id as u64))?;
+        let channel_id = row.get(1).map(|id: i64| ChannelId::new(id as u64))?;
+        let frequency: Frequency = row.get(2)?;
+
+        let Some(window) = frequency.window(today) else {
+            continue;
+        };
+
+        let mut birthdays = Vec::new();
+        let query = "select birthdays.user_id, birthday, coalesce(calendar, 'gregorian') from \
+                     birthdays left join calendars on birthdays.user_id = calendars.user_id and \
+                     birthdays.guild_id = calendars.guild_id where birthdays.guild_id = ?1";
+        let mut stmt = conn.prepare(query)?;
+        let mut birthday_rows = stmt.query((guild_id.get() as i64,))?; // NOTE: See the note in `birthday::get`.
+        while let Some(row) = birthday_rows.next()? {
+            // NOTE: See the note in `birthday::get`.
+            let user_id = row.get(0).map(|id: i64| UserId::new(id as u64))?;
+            let birthday = Birthday(row.get(1)?, row.get(2)?);
+
+            // NOTE: A single bad row shouldn't stop the rest of the digest from being posted, so we skip it instead.
+            let next_birthday = match birthday.next_occurrence(window.start) {
+                Ok(next_birthday) => next_birthday,
+                Err(err) => {
+                    error!(?err, ?guild_id, "skipping invalid birthday of {}", user_id);
+                    continue;
+                },
+            };
+            if window.contains(&next_birthday) {
+                birthdays.push((next_birthday, user_id, birthday));
+            }
+        }
+        birthdays.sort_unstable_by_key(|&(next_birthday, ..)| next_birthday);
+
+        conn.execute(
+            "update digests set last_posted = ?2 where guild_id = ?1",
+            (guild_id.get() as i64, today.date_naive()), // NOTE: See the note in `birthday::get`.
+        )?;
+
+        // NOTE: There's no point in posting a digest with nothing in it.
+        if birthdays.is_empty() {
+            continue;
+        }
+
+        digests.push(Digest {
+            guild_id,
+            channel_id,
+            frequency,
+            birthdays,
+        });
+    }
+
+    // NOTE: We release the connection before queueing, since the posting task needs it for every digest. See the
+    //       note in `watch_birthdays`.
+    drop(rows);
+    drop(stmt);
+    drop(conn);
+
+    for digest in digests {
+        // NOTE: `Sender::blocking_send` only fails if the corresponding receiver has been closed, at which point
+        //       there's no reason to continue queueing digests since we can't post them anyways.
+        if let Err(SendError(digest)) = tx.blocking_send(digest) {
+            error!(
+                channel_id = ?digest.channel_id,
+                frequency = ?digest.frequency,
+                "failed to queue birthday digest for {}",
+                digest.guild_id,
+            );
+            break;
+        }
+    }
+
+    Ok(())
+}
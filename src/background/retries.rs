@@ -0,0 +1,50 @@
+use std::fmt::Debug;
+
+use chrono::TimeDelta;
+
+use serde::de::DeserializeOwned;
+
+use tokio::{sync::mpsc::Sender, task, time};
+
+use tracing::error;
+
+use crate::{outbox, shutdown::Phase, state::State};
+
+const INTERVAL: TimeDelta = TimeDelta::minutes(1);
+
+/// Queues anything of a kind in the outbox again once it is due to be retried.
+#[tracing::instrument(skip(tx))]
+pub async fn watch_retries<T>(data: State, kind: &'static str, tx: Sender<T>)
+where
+    T: DeserializeOwned + Debug + Send + 'static,
+{
+    // PANICS: The interval used is always positive and thus a valid `std::time::Duration`.
+    let mut interval = time::interval(INTERVAL.to_std().unwrap());
+    loop {
+        // NOTE: See the note in `watch_birthdays`.
+        tokio::select! {
+            _ = interval.tick() => {},
+            _ = data.shutdown.reached(Phase::Draining) => break,
+        }
+
+        let due = task::block_in_place(|| {
+            let conn = data.conn.lock()?;
+            outbox::take_due::<T>(&conn, kind, data.clock.now())
+        });
+        let items = match due {
+            Ok(items) => items,
+            Err(err) => {
+                error!(?err, "failed to retrieve {}s to retry", kind);
+                continue;
+            },
+        };
+
+        for item in items {
+            // NOTE: Sending only fails once the sending task has stopped, in which case there's nothing to retry with.
+            if let Err(err) = tx.send(item).await {
+                error!(item = ?err.0, "failed to queue {} for retrying", kind);
+                break;
+            }
+        }
+    }
+}
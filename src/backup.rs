@@ -0,0 +1,109 @@
+use std::{
+    ffi::OsStr,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    num::NonZeroU64,
+    path::{Path, PathBuf},
+};
+
+use chrono::Utc;
+
+use flate2::{Compression, bufread::GzDecoder, write::GzEncoder};
+
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+
+use crate::error::Result;
+
+const PREFIX: &str = "birthbot-";
+
+/// Returns how many hours to wait between backups when none is configured.
+pub fn default_interval() -> NonZeroU64 {
+    NonZeroU64::new(24).unwrap() // PANICS: 24 is not 0
+}
+
+/// Returns how many backups to keep when none is configured.
+pub fn default_retention() -> usize {
+    7
+}
+
+/// Snapshots the database into a new file in the backup directory, returning its path.
+pub fn create(db: &Path, dir: &Path, compress: bool) -> Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    // NOTE: Backups are named after when they were made, so that sorting them by name also sorts them by age.
+    let name = format!("{}{}.db", PREFIX, Utc::now().format("%Y%m%dT%H%M%SZ"));
+    let path = dir.join(&name);
+
+    // NOTE: We back up using a separate connection so that the bot's own connection isn't locked for the whole
+    //       backup. SQLite's online backup API takes care of any writes made by the bot in the meantime.
+    let conn = Connection::open_with_flags(db, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    conn.backup(DatabaseName::Main, &path, None)?;
+    drop(conn);
+
+    if !compress {
+        return Ok(path);
+    }
+
+    let compressed_path = dir.join(format!("{}.gz", name));
+    let mut encoder = GzEncoder::new(
+        BufWriter::new(File::create(&compressed_path)?),
+        Compression::default(),
+    );
+    io::copy(&mut File::open(&path)?, &mut encoder)?;
+    encoder.finish()?.flush()?;
+    fs::remove_file(&path)?;
+
+    Ok(compressed_path)
+}
+
+/// Deletes the oldest backups in the backup directory so that at most `retention` of them remain, returning the
+/// deleted backups.
+pub fn prune(dir: &Path, retention: usize) -> Result<Vec<PathBuf>> {
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if is_backup(&path) {
+            backups.push(path);
+        }
+    }
+    backups.sort_unstable();
+
+    let excess = backups.len().saturating_sub(retention);
+    let pruned = backups.drain(..excess).collect::<Vec<_>>();
+    for path in &pruned {
+        fs::remove_file(path)?;
+    }
+
+    Ok(pruned)
+}
+
+/// Copies a backup (decompressing it if needed) into a temporary file next to the database, returning its path.
+///
+/// The backup is only copied, so that it can be checked before being restored without touching the original.
+pub fn unpack(backup: &Path, db: &Path) -> Result<PathBuf> {
+    let mut name = db
+        .file_name()
+        .unwrap_or(OsStr::new("birthbot.db"))
+        .to_owned();
+    name.push(".restoring");
+    let path = db.with_file_name(name);
+
+    if backup.extension() == Some(OsStr::new("gz")) {
+        let mut decoder = GzDecoder::new(BufReader::new(File::open(backup)?));
+        let mut file = BufWriter::new(File::create(&path)?);
+        io::copy(&mut decoder, &mut file)?;
+        file.flush()?;
+    } else {
+        fs::copy(backup, &path)?;
+    }
+
+    Ok(path)
+}
+
+fn is_backup(path: &Path) -> bool {
+    path.file_name()
+        .and_then(OsStr::to_str)
+        .is_some_and(|name| {
+            name.starts_with(PREFIX) && (name.ends_with(".db") || name.ends_with(".db.gz"))
+        })
+}
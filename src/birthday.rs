@@ -1,24 +1,153 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+    fmt::{self, Display, Formatter, Write},
+    mem,
+};
 
-use chrono::{DateTime, FixedOffset, Timelike};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
 
 use poise::ChoiceParameter;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct Birthday(pub DateTime<FixedOffset>);
+use serde::{Deserialize, Serialize};
 
-impl Display for Birthday {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let fmt = if self.0.hour() == 0 && self.0.minute() == 0 && self.0.second() == 0 {
-            "%d %B %Y (UTC%:z)"
-        } else {
-            "%d %B %Y %H:%M:%S (UTC%:z)"
+use crate::{
+    error::{Error, Result},
+    format::Format,
+    locale::{Language, tr},
+    recurrence::{self, Calendar},
+};
+
+/// A date that someone was born on, and the calendar that it recurs in every year.
+///
+/// The date itself is always in the Gregorian calendar.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(from = "Persisted")]
+pub struct Birthday(pub DateTime<FixedOffset>, pub Calendar);
+
+// NOTE: Birthdays used to only be a date, and are persisted in queued announcements (see `outbox` and
+//       `undelivered`), so we still need to accept those when reading them back.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Persisted {
+    Gregorian(DateTime<FixedOffset>),
+    Calendar(DateTime<FixedOffset>, Calendar),
+}
+
+impl From<Persisted> for Birthday {
+    fn from(persisted: Persisted) -> Self {
+        match persisted {
+            Persisted::Gregorian(date) => Self(date, Calendar::Gregorian),
+            Persisted::Calendar(date, calendar) => Self(date, calendar),
+        }
+    }
+}
+
+impl Birthday {
+    /// Creates a birthday in the Gregorian calendar from the date and time parts provided by a user, ensuring that
+    /// it's before `now`.
+    pub fn from_parts(
+        (year, month, day): (i32, u32, u32),
+        (hour, minute, second): (u32, u32, u32),
+        timezone: FixedOffset,
+        now: DateTime<FixedOffset>,
+    ) -> Result<Self, Invalid> {
+        let date = NaiveDate::from_ymd_opt(year, month, day).ok_or(Invalid::Date)?;
+        let time = NaiveTime::from_hms_opt(hour, minute, second).ok_or(Invalid::Time)?;
+
+        // NOTE: Fixed offsets never skip or repeat times, so this only fails for dates at the very edges of what
+        //       can be represented.
+        let local = date.and_time(time);
+        let birthday = local
+            .and_local_timezone(timezone)
+            .single()
+            .map(|date| Self(date, Calendar::Gregorian))
+            .ok_or(Invalid::Ambiguous(local))?;
+
+        if birthday.0 >= now {
+            return Err(Invalid::Future(birthday));
+        }
+        Ok(birthday)
+    }
+
+    /// Returns the birthday recurring in another calendar instead.
+    pub fn in_calendar(self, calendar: Calendar) -> Self {
+        Self(self.0, calendar)
+    }
+
+    /// Returns how many full years of the birthday's calendar have passed since the birthday.
+    ///
+    /// Fails if the birthday is after `now`, which can only happen for data that wasn't validated when stored.
+    pub fn age(&self, now: DateTime<FixedOffset>) -> Result<u32> {
+        let age = match self.1 {
+            Calendar::Gregorian => now.years_since(self.0),
+            calendar => calendar.last(self.0, now).map(|(years, _)| years),
         };
+        age.ok_or_else(|| Error::InvalidData(format!("`{}` is in the future", self)))
+    }
+
+    /// Returns the most recent anniversary of the birthday that is not after `now`.
+    ///
+    /// Fails if the birthday is after `now` (see [`Birthday::age`]).
+    pub fn last_occurrence(&self, now: DateTime<FixedOffset>) -> Result<DateTime<FixedOffset>> {
+        let last = match self.1 {
+            Calendar::Gregorian => recurrence::last(self.0, now),
+            calendar => calendar.last(self.0, now).map(|(_, date)| date),
+        };
+        last.ok_or_else(|| Error::InvalidData(format!("`{}` is in the future", self)))
+    }
+
+    /// Returns the earliest anniversary of the birthday that is not before `now`.
+    ///
+    /// Fails if the anniversary is too far in the future to be represented.
+    pub fn next_occurrence(&self, now: DateTime<FixedOffset>) -> Result<DateTime<FixedOffset>> {
+        self.1
+            .next(self.0, now)
+            .ok_or_else(|| Error::InvalidData(format!("`{}` is too far in the future", self)))
+    }
+
+    /// Formats the birthday as plain text in the default format, with the month's name in a language.
+    pub fn localized(&self, language: Language) -> String {
+        self.with_calendar(language, Format::default().text(language, self.0))
+    }
+
+    /// Formats the birthday as Discord markdown in a format, with the month's name in a language.
+    pub fn formatted(&self, language: Language, format: Format) -> String {
+        self.with_calendar(language, format.markdown(language, self.0))
+    }
 
-        self.0.format(fmt).fmt(f)
+    // NOTE: The date is still written in the Gregorian calendar, so we mention the calendar it recurs in to explain
+    //       why its anniversaries fall on a different date every year.
+    fn with_calendar(&self, language: Language, date: String) -> String {
+        match self.1 {
+            Calendar::Gregorian => date,
+            calendar => tr!(
+                language,
+                "birthday-in-calendar",
+                date = date,
+                calendar = language.choice(calendar.name()),
+            ),
+        }
+    }
+}
+
+impl Display for Birthday {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localized(Language::English))
     }
 }
 
+/// Why the date and time parts provided by a user can't be used as a birthday.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Invalid {
+    /// The year, month, and day aren't a valid date.
+    Date,
+    /// The hour, minute, and second aren't a valid time.
+    Time,
+    /// The date and time doesn't exist in the timezone, or exists more than once.
+    Ambiguous(NaiveDateTime),
+    /// The date and time isn't in the past.
+    Future(Birthday),
+}
+
 // NOTE: `chrono` has a `Month` enum similar to this, and although it impls `FromStr`, it does not impl
 //       `poise::ChoiceParameter`. Using it as a command argument is therefore a sub-par experience and
 //       justifies the need for this type.
@@ -49,3 +178,248 @@ pub enum Month {
     #[name = "December"]
     Dec = 12,
 }
+
+/// Returns the name of a month (numbered from 1) in a language.
+pub fn month_name(language: Language, month: u32) -> String {
+    // PANICS: Months are always numbered from 1 to 12.
+    let month = chrono::Month::try_from(month as u8).unwrap();
+    language.choice(month.name())
+}
+
+/// Groups birthdays into embed fields by month, in the order they are provided.
+///
+/// Each birthday is labelled with who (or what) it belongs to, such as a user mention, and comes with the occurrence
+/// that it's grouped under. Birthdays in other calendars fall in a different month every year, so they are also
+/// shown as that occurrence rather than the date they were born on. The birthdays are expected to already be sorted
+/// by the month of their occurrences.
+pub fn group_by_month(
+    language: Language,
+    format: Format,
+    birthdays: impl IntoIterator<Item = (String, Birthday, DateTime<FixedOffset>)>,
+) -> Vec<(String, String, bool)> {
+    let mut fields = Vec::new();
+    let mut month = 0;
+    let mut field = String::new();
+    for (label, birthday, occurrence) in birthdays {
+        if month == 0 {
+            month = occurrence.month();
+        }
+
+        if occurrence.month() != month {
+            fields.push((month_name(language, month), mem::take(&mut field), false));
+
+            month = occurrence.month();
+            field.clear();
+        }
+
+        let shown = match birthday.1 {
+            Calendar::Gregorian => birthday,
+            calendar => Birthday(occurrence, calendar),
+        };
+        writeln!(
+            &mut field,
+            "{} ({})",
+            label,
+            shown.formatted(language, format)
+        )
+        .unwrap();
+    }
+
+    if month != 0 {
+        fields.push((month_name(language, month), field, false));
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Offset, Utc};
+
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap()
+    }
+
+    fn now() -> DateTime<FixedOffset> {
+        at("2024-06-01T00:00:00Z")
+    }
+
+    #[test]
+    fn from_parts_accepts_past_dates() {
+        let timezone = FixedOffset::east_opt(2 * 60 * 60).unwrap();
+        let birthday = Birthday::from_parts((2000, 3, 15), (8, 30, 0), timezone, now());
+        assert_eq!(
+            birthday,
+            Ok(Birthday(
+                at("2000-03-15T08:30:00+02:00"),
+                Calendar::Gregorian
+            ))
+        );
+    }
+
+    #[test]
+    fn from_parts_rejects_invalid_dates() {
+        let utc = Utc.fix();
+        assert_eq!(
+            Birthday::from_parts((2000, 2, 30), (0, 0, 0), utc, now()),
+            Err(Invalid::Date),
+        );
+        assert_eq!(
+            Birthday::from_parts((2000, 13, 1), (0, 0, 0), utc, now()),
+            Err(Invalid::Date),
+        );
+    }
+
+    #[test]
+    fn from_parts_only_accepts_leap_days_in_leap_years() {
+        let utc = Utc.fix();
+        assert!(Birthday::from_parts((2000, 2, 29), (0, 0, 0), utc, now()).is_ok());
+        assert_eq!(
+            Birthday::from_parts((1900, 2, 29), (0, 0, 0), utc, now()),
+            Err(Invalid::Date),
+        );
+        assert_eq!(
+            Birthday::from_parts((2023, 2, 29), (0, 0, 0), utc, now()),
+            Err(Invalid::Date),
+        );
+    }
+
+    #[test]
+    fn from_parts_rejects_invalid_times() {
+        assert_eq!(
+            Birthday::from_parts((2000, 3, 15), (24, 0, 0), Utc.fix(), now()),
+            Err(Invalid::Time),
+        );
+    }
+
+    #[test]
+    fn from_parts_rejects_future_dates() {
+        let utc = Utc.fix();
+        assert_eq!(
+            Birthday::from_parts((2030, 1, 1), (0, 0, 0), utc, now()),
+            Err(Invalid::Future(Birthday(
+                at("2030-01-01T00:00:00Z"),
+                Calendar::Gregorian
+            ))),
+        );
+        // NOTE: Being born right now doesn't count as being born yet.
+        assert_eq!(
+            Birthday::from_parts((2024, 6, 1), (0, 0, 0), utc, now()),
+            Err(Invalid::Future(Birthday(now(), Calendar::Gregorian))),
+        );
+    }
+
+    #[test]
+    fn age_counts_full_years() {
+        let birthday = Birthday(at("2000-06-02T00:00:00Z"), Calendar::Gregorian);
+        assert_eq!(birthday.age(now()).unwrap(), 23);
+        assert_eq!(birthday.age(at("2024-06-02T00:00:00Z")).unwrap(), 24);
+    }
+
+    #[test]
+    fn age_fails_for_future_birthdays() {
+        let birthday = Birthday(at("2030-01-01T00:00:00Z"), Calendar::Gregorian);
+        assert!(matches!(birthday.age(now()), Err(Error::InvalidData(_))));
+        assert!(matches!(
+            birthday.last_occurrence(now()),
+            Err(Error::InvalidData(_)),
+        ));
+    }
+
+    #[test]
+    fn group_by_month_splits_fields_by_month() {
+        let birthdays = [
+            ("a", "2000-01-05T00:00:00Z"),
+            ("b", "1990-01-20T00:00:00Z"),
+            ("c", "1995-03-01T12:00:00Z"),
+        ]
+        .map(|(label, date)| {
+            let birthday = Birthday(at(date), Calendar::Gregorian);
+            (label.to_owned(), birthday, birthday.0)
+        });
+
+        let fields = group_by_month(Language::English, Format::default(), birthdays);
+        assert_eq!(
+            fields,
+            [
+                (
+                    "January".to_owned(),
+                    "a (`05 January 2000 (UTC+00:00)`)\nb (`20 January 1990 (UTC+00:00)`)\n"
+                        .to_owned(),
+                    false,
+                ),
+                (
+                    "March".to_owned(),
+                    "c (`01 March 1995 12:00:00 (UTC+00:00)`)\n".to_owned(),
+                    false,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn group_by_month_uses_occurrences_of_other_calendars() {
+        // NOTE: Sorted by their next occurrences, as in digests. The Chinese birthday was born in February, but falls
+        //       in January in 2025.
+        let gregorian = Birthday(at("2000-01-05T00:00:00Z"), Calendar::Gregorian);
+        let chinese = Birthday(at("2000-02-05T00:00:00Z"), Calendar::Chinese);
+        let later = Birthday(at("1990-01-30T00:00:00Z"), Calendar::Gregorian);
+        let birthdays = [
+            ("a".to_owned(), gregorian, at("2025-01-05T00:00:00Z")),
+            ("b".to_owned(), chinese, at("2025-01-29T00:00:00Z")),
+            ("c".to_owned(), later, at("2025-01-30T00:00:00Z")),
+        ];
+
+        let fields = group_by_month(Language::English, Format::default(), birthdays);
+        assert_eq!(
+            fields,
+            [(
+                "January".to_owned(),
+                "a (`05 January 2000 (UTC+00:00)`)\nb (`29 January 2025 (UTC+00:00)` (Chinese \
+                 calendar))\nc (`30 January 1990 (UTC+00:00)`)\n"
+                    .to_owned(),
+                false,
+            )],
+        );
+    }
+
+    #[test]
+    fn group_by_month_handles_no_birthdays() {
+        assert!(group_by_month(Language::English, Format::default(), []).is_empty());
+    }
+
+    #[test]
+    fn ages_count_years_of_the_calendar() {
+        let birthday = Birthday(at("1990-01-01T00:00:00Z"), Calendar::Hijri);
+        assert_eq!(birthday.age(at("2024-01-01T00:00:00Z")).unwrap(), 35);
+        assert_eq!(
+            birthday.localized(Language::English),
+            "01 January 1990 (UTC+00:00) (Hijri calendar)",
+        );
+    }
+
+    #[test]
+    fn old_birthdays_are_deserialized_as_gregorian() {
+        let birthday: Birthday = serde_json::from_str("\"2000-03-05T00:00:00+01:00\"").unwrap();
+        assert_eq!(
+            birthday,
+            Birthday(at("2000-03-05T00:00:00+01:00"), Calendar::Gregorian),
+        );
+
+        let birthday = Birthday(at("2000-03-05T00:00:00+01:00"), Calendar::Chinese);
+        let json = serde_json::to_string(&birthday).unwrap();
+        assert_eq!(serde_json::from_str::<Birthday>(&json).unwrap(), birthday);
+    }
+
+    #[test]
+    fn month_names_are_localized() {
+        let birthday = Birthday(at("2000-03-05T00:00:00+01:00"), Calendar::Gregorian);
+        assert_eq!(birthday.to_string(), "05 March 2000 (UTC+01:00)");
+        assert_eq!(
+            birthday.localized(Language::German),
+            "05 März 2000 (UTC+01:00)",
+        );
+    }
+}
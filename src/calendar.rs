@@ -0,0 +1,139 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, TimeDelta, Utc};
+
+use poise::serenity_prelude as serenity;
+
+use serenity::{GuildId, UserId};
+
+use crate::{
+    birthday::Birthday,
+    locale::{Language, tr},
+    recurrence::Calendar,
+};
+
+// NOTE: RFC 5545 limits content lines to 75 octets, excluding the line break.
+const MAX_LINE_LENGTH: usize = 75;
+
+// NOTE: Calendar apps can only recur events in the Gregorian calendar, so birthdays in other calendars are exported
+//       as this many upcoming dates instead, and need to be exported again once they run out.
+const LISTED_OCCURRENCES: usize = 20;
+
+/// Renders a guild's birthdays as an RFC 5545 iCalendar file, with a yearly all-day event for each birthday.
+///
+/// Each birthday is given as the user it belongs to, the name to show for them, and the birthday itself.
+pub fn render(
+    language: Language,
+    guild_id: GuildId,
+    guild_name: &str,
+    birthdays: &[(UserId, String, Birthday)],
+    now: DateTime<FixedOffset>,
+) -> String {
+    let stamp = now.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        format!(
+            "PRODID:-//birthbot//birthbot {}//EN",
+            env!("CARGO_PKG_VERSION")
+        ),
+        "CALSCALE:GREGORIAN".to_owned(),
+        "METHOD:PUBLISH".to_owned(),
+        format!(
+            "X-WR-CALNAME:{}",
+            escape(&tr!(language, "calendar-name", guild = guild_name))
+        ),
+    ];
+
+    for (user_id, name, birthday) in birthdays {
+        // NOTE: Birthdays are all-day events on the date they fall on in their own timezone, so we ignore the time.
+        let (start, rule) = match birthday.1 {
+            Calendar::Gregorian => {
+                let date = birthday.0.date_naive();
+
+                // NOTE: A yearly rule starting on Feb 29 would only recur in leap years, so we instead recur on the
+                //       last day of February. This falls on Feb 28 in other years, which matches when the birthday
+                //       is announced.
+                let rule = if (date.month(), date.day()) == (2, 29) {
+                    "RRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=-1"
+                } else {
+                    "RRULE:FREQ=YEARLY"
+                };
+                (date, Some(rule.to_owned()))
+            },
+            _ => {
+                let (start, rest) = occurrences(birthday, now);
+                let rest = rest
+                    .iter()
+                    .map(|date| date.format("%Y%m%d").to_string())
+                    .collect::<Vec<_>>();
+                let rule =
+                    (!rest.is_empty()).then(|| format!("RDATE;VALUE=DATE:{}", rest.join(",")));
+                (start, rule)
+            },
+        };
+
+        lines.extend([
+            "BEGIN:VEVENT".to_owned(),
+            // NOTE: The UID must stay the same across exports so that re-importing the calendar updates events
+            //       instead of duplicating them.
+            format!("UID:birthday-{}-{}@birthbot", guild_id, user_id),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")),
+        ]);
+        lines.extend(rule);
+        lines.extend([
+            format!(
+                "SUMMARY:{}",
+                escape(&tr!(language, "calendar-summary", name = name.as_str())),
+            ),
+            "TRANSP:TRANSPARENT".to_owned(),
+            "END:VEVENT".to_owned(),
+        ]);
+    }
+
+    lines.push("END:VCALENDAR".to_owned());
+
+    lines.iter().fold(String::new(), |mut ics, line| {
+        fold(&mut ics, line);
+        ics
+    })
+}
+
+// NOTE: The list starts from the most recent occurrence so that this year's birthday still shows up once it has
+//       passed. Birthdays that can't be recurred fall back to just the date itself.
+fn occurrences(birthday: &Birthday, now: DateTime<FixedOffset>) -> (NaiveDate, Vec<NaiveDate>) {
+    let mut occurrence = birthday.last_occurrence(now).unwrap_or(birthday.0);
+    let start = occurrence.date_naive();
+    let mut dates = Vec::new();
+    while dates.len() < LISTED_OCCURRENCES - 1 {
+        let Ok(next) = birthday.next_occurrence(occurrence + TimeDelta::seconds(1)) else {
+            break;
+        };
+        occurrence = next;
+        dates.push(occurrence.date_naive());
+    }
+    (start, dates)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+// NOTE: Long lines are folded by inserting a line break followed by a single space, which counts towards the
+//       length of the continuation line. We also have to avoid splitting multi-byte characters.
+fn fold(ics: &mut String, line: &str) {
+    let mut length = 0;
+    for char in line.chars() {
+        if length + char.len_utf8() > MAX_LINE_LENGTH {
+            ics.push_str("\r\n ");
+            length = 1;
+        }
+        ics.push(char);
+        length += char.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
@@ -0,0 +1,581 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, bail};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+
+use clap::{Parser, Subcommand};
+
+use poise::serenity_prelude as serenity;
+
+use rusqlite::{
+    Connection,
+    DatabaseName,
+    types::{FromSql, ValueRef},
+};
+
+use serde_json::{Map, Number, Value};
+
+use serenity::Http;
+
+use crate::{Config, background::birthdays, backup, commands, db, registration};
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Maintain the database without connecting to Discord.
+    #[command(subcommand)]
+    Db(DbCommand),
+    /// Register commands with Discord without connecting to the gateway, then exit.
+    Register {
+        /// Remove stale global or guild commands. Defaults to `clear-stale-commands`.
+        #[arg(long)]
+        clear_stale: bool,
+    },
+    /// Show what would be announced on a day (in UTC) without announcing anything.
+    Simulate {
+        /// The day to simulate, such as `2024-02-29`.
+        date: NaiveDate,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DbCommand {
+    /// Create any missing tables.
+    Migrate,
+    /// Show how much data is stored.
+    Stats,
+    /// Export data as JSON.
+    Export {
+        /// Only export data belonging to this guild.
+        #[arg(long)]
+        guild: Option<u64>,
+        /// The file to export to. Defaults to stdout.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Import data previously exported as JSON, replacing any conflicting rows.
+    Import {
+        /// The file to import from.
+        input: PathBuf,
+    },
+    /// Rebuild the database to reclaim unused space.
+    Vacuum,
+    /// Check the database for corruption and invalid data.
+    Check,
+    /// Back up the database now, in addition to any scheduled backups.
+    Backup {
+        /// The directory to back up to. Defaults to `backup-dir`.
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        /// Compress the backup. Defaults to `backup-compress`.
+        #[arg(long)]
+        compress: bool,
+    },
+    /// Replace the database with a backup, after checking the backup for corruption. The bot must not be running.
+    Restore {
+        /// The backup to restore, which may be compressed.
+        backup: PathBuf,
+    },
+}
+
+/// Runs a database command against the configured database.
+pub fn run_db(command: DbCommand, config: &Config) -> anyhow::Result<()> {
+    let path = config.db.as_path();
+
+    // NOTE: The database may be corrupted when restoring, so we avoid opening it as usual.
+    if let DbCommand::Restore { backup } = command {
+        return restore(&backup, path);
+    }
+
+    let conn =
+        db::open(path).with_context(|| format!("failed to open database at {}", path.display()))?;
+
+    match command {
+        DbCommand::Migrate => {
+            // NOTE: Opening the database already runs every migration.
+            println!("Migrated database at {}", path.display());
+        },
+        DbCommand::Stats => stats(&conn, path)?,
+        DbCommand::Export { guild, output } => {
+            let writer: Box<dyn Write> = match output {
+                Some(output) => Box::new(BufWriter::new(File::create(output)?)),
+                None => Box::new(io::stdout().lock()),
+            };
+            export(&conn, guild, writer)?;
+        },
+        DbCommand::Import { input } => {
+            let reader = BufReader::new(File::open(input)?);
+            import(&conn, reader)?;
+        },
+        DbCommand::Vacuum => {
+            conn.execute("vacuum", ())?;
+            println!("Vacuumed database at {}", path.display());
+        },
+        DbCommand::Check => check(&conn)?,
+        DbCommand::Backup { dir, compress } => {
+            let dir = dir
+                .or_else(|| config.backup_dir.clone())
+                .context("either `--dir` or `backup-dir` must be set")?;
+            let backup = backup::create(path, &dir, compress || config.backup_compress)?;
+            println!("Backed up database to {}", backup.display());
+        },
+        DbCommand::Restore { .. } => unreachable!(), // PANICS: Restoring is handled above
+    }
+
+    Ok(())
+}
+
+/// Registers commands as the bot would when starting, using the configured token.
+pub async fn run_register(clear_stale: bool, config: &Config) -> anyhow::Result<()> {
+    let token = config
+        .token
+        .as_deref()
+        .context("`token` must be set to register commands")?;
+
+    // NOTE: Commands are registered for the application, which isn't known until the gateway connects, so we have to
+    //       look it up ourselves.
+    let http = Http::new(token);
+    let application = http.get_current_application_info().await?;
+    http.set_application_id(application.id);
+
+    let commands = commands::all();
+    let clear_stale = clear_stale || config.clear_stale_commands;
+    registration::register(&http, &commands, &config.dev_guilds, clear_stale).await?;
+
+    if config.dev_guilds.is_empty() {
+        println!("Registered {} command(s) globally", commands.len());
+    } else {
+        for guild_id in &config.dev_guilds {
+            println!(
+                "Registered {} command(s) in guild {}",
+                commands.len(),
+                guild_id
+            );
+        }
+    }
+    if clear_stale {
+        let stale = if config.dev_guilds.is_empty() {
+            "guild"
+        } else {
+            "global"
+        };
+        println!("Cleared stale {} commands", stale);
+    }
+
+    Ok(())
+}
+
+/// Replays what would have been announced on a day using the configured database.
+pub fn run_simulate(date: NaiveDate, config: &Config) -> anyhow::Result<()> {
+    let path = config.db.as_path();
+    let conn =
+        db::open(path).with_context(|| format!("failed to open database at {}", path.display()))?;
+
+    let simulated = birthdays::simulate(&conn, date)?;
+    if simulated.is_empty() {
+        println!("Nothing would be announced on {}", date);
+        return Ok(());
+    }
+
+    for announcement in &simulated {
+        println!(
+            "{} in guild {} (channel {}): {}",
+            announcement.at.format("%Y-%m-%d %H:%M UTC"),
+            announcement.guild_id,
+            announcement.channel_id,
+            announcement.occasions.join(", "),
+        );
+    }
+    println!(
+        "{} announcement(s) would be sent on {}",
+        simulated.len(),
+        date,
+    );
+    println!("Anniversaries need to be fetched from Discord, so they aren't simulated");
+
+    Ok(())
+}
+
+fn stats(conn: &Connection, path: &Path) -> anyhow::Result<()> {
+    let size = fs::metadata(path)?.len();
+    println!("Database: {} ({} bytes)", path.display(), size);
+
+    let guilds = conn.query_row(
+        "select count(distinct guild_id) from birthdays",
+        (),
+        |row| row.get::<_, i64>(0),
+    )?;
+    println!("Guilds with birthdays: {}", guilds);
+
+    for table in db::TABLES {
+        let query = format!("select count(*) from {}", table);
+        let rows = conn.query_row(&query, (), |row| row.get::<_, i64>(0))?;
+        println!("{:<20} {:>10} rows", table, rows);
+    }
+
+    Ok(())
+}
+
+// NOTE: Feed tokens are secrets, and can always be generated again, so we leave them out of exports. Undelivered
+//       and retried announcements only make sense for the bot that queued them.
+fn exported_tables() -> impl Iterator<Item = &'static str> {
+    db::TABLES
+        .into_iter()
+        .filter(|&table| !matches!(table, "feed_tokens" | "undelivered" | "outbox"))
+}
+
+fn export(conn: &Connection, guild: Option<u64>, writer: impl Write) -> anyhow::Result<()> {
+    let mut tables = Map::new();
+    for table in exported_tables() {
+        // NOTE: Every table except `user_formats` has a `guild_id` column, so we can filter them all the same way.
+        //       Formats belong to users rather than guilds, so a guild's export only includes those of its members
+        //       with birthdays.
+        let filter = match table {
+            "user_formats" => {
+                "?1 is null or user_id in (select user_id from birthdays where guild_id = ?1)"
+            },
+            _ => "?1 is null or guild_id = ?1",
+        };
+        let query = format!("select * from {} where {} order by rowid", table, filter);
+        let mut stmt = conn.prepare(&query)?;
+        let columns = stmt
+            .column_names()
+            .into_iter()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+
+        // NOTE: See the note in `birthday::get`.
+        let mut rows = stmt.query((guild.map(|id| id as i64),))?;
+        let mut objects = Vec::new();
+        while let Some(row) = rows.next()? {
+            let mut object = Map::new();
+            for (i, column) in columns.iter().enumerate() {
+                object.insert(column.clone(), to_json(row.get_ref(i)?)?);
+            }
+            objects.push(Value::Object(object));
+        }
+
+        tables.insert(table.to_owned(), Value::Array(objects));
+    }
+
+    serde_json::to_writer_pretty(writer, &tables)?;
+
+    Ok(())
+}
+
+fn import(conn: &Connection, reader: impl io::Read) -> anyhow::Result<()> {
+    let tables = serde_json::from_reader::<_, Map<String, Value>>(reader)?;
+
+    // NOTE: Importing is all-or-nothing so that a bad export can't leave the database half-imported.
+    let tx = conn.unchecked_transaction()?;
+    let mut imported = 0;
+    for (table, rows) in tables {
+        if !exported_tables().any(|exported| exported == table) {
+            bail!("unknown table `{}`", table);
+        }
+        let Value::Array(rows) = rows else {
+            bail!("table `{}` is not an array of rows", table);
+        };
+
+        for row in rows {
+            let Value::Object(row) = row else {
+                bail!("table `{}` contains a row that is not an object", table);
+            };
+
+            // NOTE: Column names can't be bound as parameters, so we only allow names that are plain identifiers.
+            if let Some(column) = row.keys().find(|column| {
+                !column
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            }) {
+                bail!("table `{}` contains an invalid column `{}`", table, column);
+            }
+
+            let columns = row.keys().cloned().collect::<Vec<_>>().join(", ");
+            let params = (1..=row.len())
+                .map(|i| format!("?{}", i))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let query = format!(
+                "insert or replace into {} ({}) values ({})",
+                table, columns, params,
+            );
+            let values = row
+                .values()
+                .map(from_json)
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            tx.execute(&query, rusqlite::params_from_iter(values))?;
+            imported += 1;
+        }
+    }
+    tx.commit()?;
+
+    println!("Imported {} rows", imported);
+
+    Ok(())
+}
+
+fn check(conn: &Connection) -> anyhow::Result<()> {
+    let mut problems = 0;
+
+    let integrity = conn.query_row("pragma integrity_check", (), |row| row.get::<_, String>(0))?;
+    if integrity != "ok" {
+        println!("Integrity check failed: {}", integrity);
+        problems += 1;
+    }
+
+    let now = Utc::now().fixed_offset();
+
+    // NOTE: We parse dates ourselves rather than propagating errors so that one invalid date doesn't stop us from
+    //       checking the rest.
+    let mut stmt = conn.prepare("select user_id, guild_id, birthday from birthdays")?;
+    let mut rows = stmt.query(())?;
+    while let Some(row) = rows.next()? {
+        let user_id = row.get::<_, i64>(0)? as u64; // NOTE: See the note in `birthday::get`.
+        let guild_id = row.get::<_, i64>(1)? as u64; // NOTE: See the note in `birthday::get`.
+        if let Some(problem) = check_date(row.get_ref(2)?, now) {
+            println!(
+                "Birthday of user {} in guild {} {}",
+                user_id, guild_id, problem
+            );
+            problems += 1;
+        }
+    }
+
+    let mut stmt = conn.prepare("select guild_id, name, date from events")?;
+    let mut rows = stmt.query(())?;
+    while let Some(row) = rows.next()? {
+        let guild_id = row.get::<_, i64>(0)? as u64; // NOTE: See the note in `birthday::get`.
+        let name = row.get::<_, String>(1)?;
+        if let Some(problem) = check_date(row.get_ref(2)?, now) {
+            println!("Event `{}` in guild {} {}", name, guild_id, problem);
+            problems += 1;
+        }
+    }
+
+    if problems > 0 {
+        bail!("found {} problem(s)", problems);
+    }
+
+    println!("No problems found");
+
+    Ok(())
+}
+
+fn restore(backup: &Path, path: &Path) -> anyhow::Result<()> {
+    let unpacked = backup::unpack(backup, path)
+        .with_context(|| format!("failed to read backup at {}", backup.display()))?;
+
+    let integrity = Connection::open(&unpacked).and_then(|conn| {
+        conn.query_row("pragma integrity_check", (), |row| row.get::<_, String>(0))
+    });
+    let integrity = match integrity {
+        Ok(integrity) => integrity,
+        Err(err) => {
+            fs::remove_file(&unpacked)?;
+            return Err(err).context("backup is not a valid database");
+        },
+    };
+    if integrity != "ok" {
+        fs::remove_file(&unpacked)?;
+        bail!("backup failed integrity check: {}", integrity);
+    }
+
+    // NOTE: We keep a copy of the database being replaced in case the wrong backup was restored. The files are
+    //       copied as they are rather than with SQLite's backup API, since the database being replaced is often
+    //       corrupt, which is why it's being restored in the first place.
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+        let previous = with_suffix(path, ".pre-restore");
+        fs::copy(path, &previous)?;
+        for suffix in ["-wal", "-shm"] {
+            let file = with_suffix(path, suffix);
+            if file.exists() {
+                fs::copy(&file, with_suffix(&previous, suffix))?;
+            }
+        }
+        println!("Saved previous database to {}", previous.display());
+    }
+
+    // NOTE: SQLite's backup API overwrites the database page by page, which is safer than replacing the file. It
+    //       can't overwrite a database that's too corrupt to be opened though, so we replace those instead.
+    let restored = overwrite(path, &unpacked).or_else(|err| {
+        println!("Replacing database that couldn't be overwritten: {}", err);
+        for suffix in ["", "-wal", "-shm"] {
+            let file = with_suffix(path, suffix);
+            if file.exists() {
+                fs::remove_file(file)?;
+            }
+        }
+        Ok::<_, anyhow::Error>(overwrite(path, &unpacked)?)
+    });
+    fs::remove_file(&unpacked)?;
+    restored?;
+
+    println!("Restored {} to {}", backup.display(), path.display());
+
+    Ok(())
+}
+
+fn overwrite(path: &Path, database: &Path) -> rusqlite::Result<()> {
+    Connection::open(path)?.restore(DatabaseName::Main, database, None::<fn(_)>)
+}
+
+// NOTE: SQLite names its journal files by appending to the database's file name, so these can't use `with_extension`.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+fn check_date(value: ValueRef<'_>, now: DateTime<FixedOffset>) -> Option<String> {
+    match DateTime::<FixedOffset>::column_result(value) {
+        Err(err) => {
+            let value = value
+                .as_str()
+                .map(str::to_owned)
+                .unwrap_or_else(|_| format!("{:?}", value.data_type()));
+            Some(format!("is not a valid date ({}): {}", err, value))
+        },
+        Ok(date) if date >= now => Some(format!("is in the future: {}", date)),
+        Ok(_) => None,
+    }
+}
+
+fn to_json(value: ValueRef<'_>) -> anyhow::Result<Value> {
+    let value = match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(int) => Value::Number(int.into()),
+        ValueRef::Real(real) => Number::from_f64(real)
+            .map(Value::Number)
+            .context("cannot export a non-finite number")?,
+        ValueRef::Text(text) => Value::String(String::from_utf8(text.to_vec())?),
+        ValueRef::Blob(_) => bail!("cannot export binary data"),
+    };
+    Ok(value)
+}
+
+fn from_json(value: &Value) -> anyhow::Result<rusqlite::types::Value> {
+    use rusqlite::types::Value as Sql;
+
+    let value = match value {
+        Value::Null => Sql::Null,
+        Value::Bool(bool) => Sql::Integer(i64::from(*bool)),
+        Value::Number(number) => match number.as_i64() {
+            Some(int) => Sql::Integer(int),
+            None => number
+                .as_f64()
+                .map(Sql::Real)
+                .context("cannot import a number that doesn't fit in 64 bits")?,
+        },
+        Value::String(string) => Sql::Text(string.clone()),
+        Value::Array(_) | Value::Object(_) => bail!("cannot import nested values"),
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("birthbot-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn backup_with_birthday(dir: &Path) -> PathBuf {
+        let source = dir.join("source.db");
+        let conn = db::open(&source).unwrap();
+        conn.execute(
+            "insert into birthdays (user_id, guild_id, birthday) values (1, 2, ?1)",
+            ("2000-01-01T00:00:00+00:00",),
+        )
+        .unwrap();
+        drop(conn);
+        backup::create(&source, &dir.join("backups"), false).unwrap()
+    }
+
+    #[test]
+    fn exports_can_be_imported() {
+        let conn = db::open_in_memory().unwrap();
+        conn.execute_batch(
+            "insert into birthdays (user_id, guild_id, birthday) values (1, 2, \
+             '2000-01-01T00:00:00+00:00'), (3, 4, '2001-02-03T00:00:00+00:00');
+             insert into user_formats (user_id, date, clock, show_offset) values (1, 'mdy', '12h', \
+             0), (3, 'iso', '24h', 1);",
+        )
+        .unwrap();
+
+        // NOTE: Every table is exported, so this also catches tables that can't be filtered by guild.
+        for guild in [None, Some(2)] {
+            let mut exported = Vec::new();
+            export(&conn, guild, &mut exported).unwrap();
+
+            let imported = db::open_in_memory().unwrap();
+            import(&imported, exported.as_slice()).unwrap();
+
+            let mut reexported = Vec::new();
+            export(&imported, None, &mut reexported).unwrap();
+            assert_eq!(
+                serde_json::from_slice::<Value>(&reexported).unwrap(),
+                serde_json::from_slice::<Value>(&exported).unwrap(),
+            );
+        }
+
+        let mut exported = Vec::new();
+        export(&conn, Some(2), &mut exported).unwrap();
+        let tables = serde_json::from_slice::<Map<String, Value>>(&exported).unwrap();
+        assert_eq!(tables["birthdays"].as_array().unwrap().len(), 1);
+        assert_eq!(tables["user_formats"][0]["user_id"], 1);
+    }
+
+    #[test]
+    fn restore_replaces_corrupt_databases() {
+        let dir = temp_dir("restore");
+        let backup = backup_with_birthday(&dir);
+
+        // NOTE: Both a file that isn't a database at all and a database cut off partway through should be replaced.
+        let valid = fs::read(dir.join("source.db")).unwrap();
+        let cases = [
+            b"definitely not a database".repeat(200),
+            valid[..valid.len() / 2].to_vec(),
+        ];
+        for corrupt in cases {
+            let path = dir.join("birthbot.db");
+            fs::write(&path, &corrupt).unwrap();
+
+            restore(&backup, &path).unwrap();
+
+            let conn = Connection::open(&path).unwrap();
+            let count = conn
+                .query_row("select count(*) from birthdays", (), |row| {
+                    row.get::<_, i64>(0)
+                })
+                .unwrap();
+            assert_eq!(count, 1);
+            assert_eq!(
+                fs::read(with_suffix(&path, ".pre-restore")).unwrap(),
+                corrupt
+            );
+
+            drop(conn);
+            fs::remove_file(&path).unwrap();
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
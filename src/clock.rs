@@ -0,0 +1,46 @@
+use std::{fmt::Debug, sync::Mutex};
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// A source of the current time.
+///
+/// Everything that depends on the time (such as when birthdays are announced) reads it from a clock instead of the
+/// system, so that it can be run at any other time, such as when simulating a date.
+pub trait Clock: Debug + Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// A clock that reads the time from the system.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that stays at the same time until it is moved.
+#[derive(Debug)]
+pub struct ManualClock(Mutex<DateTime<Utc>>);
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self(Mutex::new(now))
+    }
+
+    /// Moves the clock forwards (or backwards, if `delta` is negative).
+    pub fn advance(&self, delta: TimeDelta) {
+        // PANICS: Moving the clock never panics, so the lock can't be poisoned.
+        let mut now = self.0.lock().unwrap();
+        *now += delta;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        // PANICS: See the note in `ManualClock::advance`.
+        *self.0.lock().unwrap()
+    }
+}
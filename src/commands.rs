@@ -1,5 +1,6 @@
 use crate::{
     error::{Error, Result},
+    locale,
     state::State,
 };
 
@@ -7,18 +8,59 @@ type Context<'a> = poise::Context<'a, State, Error>;
 
 pub mod birthday;
 
+pub mod birthbot;
+
 #[poise::command(
     slash_command,
     subcommands(
         "birthday::get",
         "birthday::set",
+        "birthday::set_text",
         "birthday::unset",
         "birthday::list",
         "birthday::next",
+        "birthday::calendar",
+        "birthday::wish",
+        "birthday::privacy",
         "birthday::channel",
+        "birthday::locale",
+        "birthday::grouping",
+        "birthday::digest",
+        "birthday::anniversary",
+        "birthday::ping",
+        "birthday::automation",
+        "birthday::milestone",
+        "birthday::format",
+        "birthday::event",
         "birthday::help",
     )
 )]
 pub async fn birthday(_: Context<'_>) -> Result<()> {
     Ok(())
 }
+
+// NOTE: Only owners can use the commands in here, but Discord has no way to hide commands from everyone except the
+//       owners, so we hide them from everyone except administrators instead.
+#[poise::command(
+    slash_command,
+    subcommands("birthbot::admin"),
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn birthbot(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Returns every command, including subcommands that are only available with certain cargo features, with their
+/// names and descriptions translated.
+pub fn all() -> Vec<poise::Command<State, Error>> {
+    #[allow(unused_mut)]
+    let mut birthday = birthday();
+
+    // NOTE: `poise::command` doesn't support conditionally compiled subcommands, so we add them here instead.
+    #[cfg(feature = "http")]
+    birthday.subcommands.push(birthday::feed());
+
+    let mut commands = vec![birthday, birthbot()];
+    locale::localize_commands(&mut commands);
+    commands
+}
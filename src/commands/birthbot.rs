@@ -0,0 +1,20 @@
+use crate::error::Result;
+
+use super::Context;
+
+pub mod admin;
+
+#[poise::command(
+    slash_command,
+    owners_only,
+    subcommands(
+        "admin::stats",
+        "admin::scan",
+        "admin::simulate",
+        "admin::resend_changelog",
+        "admin::reload",
+    )
+)]
+pub async fn admin(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use std::time::Duration;
+
+use chrono::NaiveDate;
+
+use poise::serenity_prelude as serenity;
+
+use serenity::GuildId;
+
+use tokio::task;
+
+use crate::{
+    background::{birthdays, changelog},
+    commands::Context,
+    error::{Error, Result},
+    failure,
+    locale::{Language, tr},
+    logging,
+    neutral,
+    reply,
+    success,
+};
+
+/// Show statistics about the bot and its database.
+#[poise::command(slash_command, owners_only, ephemeral)]
+pub async fn stats(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let data = ctx.data();
+    let (birthdays, guilds_with_birthdays, size) = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        let (birthdays, guilds) = conn.query_row(
+            "select count(*), count(distinct guild_id) from birthdays",
+            (),
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+        )?;
+        // NOTE: The database's path isn't kept around, so we work out its size from its pages instead.
+        let size = conn.query_row(
+            "select page_count * page_size from pragma_page_count(), pragma_page_size()",
+            (),
+            |row| row.get::<_, i64>(0),
+        )?;
+        Ok::<_, Error>((birthdays, guilds, size))
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "stats-retrieved"))
+        .field(
+            tr!(language, "field-version"),
+            format!("`{}`", env!("CARGO_PKG_VERSION")),
+            true,
+        )
+        .field(
+            tr!(language, "field-uptime"),
+            uptime(data.started.elapsed()),
+            true,
+        )
+        .field(
+            tr!(language, "field-guilds"),
+            ctx.cache().guild_count().to_string(),
+            true,
+        )
+        .field(
+            tr!(language, "field-birthdays"),
+            birthdays.to_string(),
+            true,
+        )
+        .field(
+            tr!(language, "field-guilds-with-birthdays"),
+            guilds_with_birthdays.to_string(),
+            true,
+        )
+        .field(
+            tr!(language, "field-database-size"),
+            tr!(language, "stats-bytes", bytes = size),
+            true,
+        );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Check for birthdays, events, and anniversaries to announce right away.
+#[poise::command(slash_command, owners_only, ephemeral)]
+pub async fn scan(ctx: Context<'_>) -> Result<()> {
+    // NOTE: Only a single scan is queued no matter how many times this is used before it starts.
+    ctx.data().scan.notify_one();
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "scan-queued"))
+        .description(tr!(language, "scan-queued-description"));
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Show the birthdays and events that would be announced on a day, without announcing them.
+#[poise::command(slash_command, owners_only, ephemeral)]
+pub async fn simulate(
+    ctx: Context<'_>,
+    #[description = "The day to simulate (in UTC), such as `2024-02-29`."] date: NaiveDate,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let simulated = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        birthdays::simulate(&conn, date)
+    })?;
+
+    // TODO: Use pagination to allow displaying more announcements overall
+    // NOTE: The simulated occasions are shared with the `simulate` CLI command, so they're always in English.
+    let language = Language::of(ctx);
+    let embed = match simulated.len() {
+        0 => neutral(&tr!(language, "simulate-empty")).description(tr!(
+            language,
+            "simulate-empty-description",
+            date = date.to_string(),
+        )),
+        n if n > 25 => success(&tr!(language, "simulate-done")).description(tr!(
+            language,
+            "simulate-done-truncated-description",
+            count = n
+        )),
+        n => success(&tr!(language, "simulate-done")).description(tr!(
+            language,
+            "simulate-done-description",
+            count = n
+        )),
+    };
+    let embed = embed
+        .fields(simulated.iter().take(25).map(|simulated| {
+            (
+                simulated.at.format("%Y-%m-%d %H:%M UTC").to_string(),
+                format!(
+                    "{}\n{}",
+                    tr!(
+                        language,
+                        "simulate-channel",
+                        guild = simulated.guild_id.to_string(),
+                        channel = format!("<#{}>", simulated.channel_id),
+                    ),
+                    simulated.occasions.join("\n"),
+                ),
+                false,
+            )
+        }))
+        .field(
+            tr!(language, "field-note"),
+            tr!(language, "simulate-note"),
+            false,
+        );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Post the current changelog in a server again.
+#[poise::command(slash_command, owners_only, ephemeral, rename = "resend-changelog")]
+pub async fn resend_changelog(
+    ctx: Context<'_>,
+    #[description = "The ID of the server to post the changelog in. Defaults to this server."]
+    guild: Option<GuildId>,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let language = Language::of(ctx);
+    let Some(guild_id) = guild.or(ctx.guild_id()) else {
+        let embed = failure(&tr!(language, "error-context"))
+            .description(tr!(language, "resend-changelog-dm-description"));
+        ctx.send(reply(embed)).await?;
+        return Ok(());
+    };
+
+    let embed = match changelog::resend_changelog(ctx.data(), guild_id).await? {
+        Some(channel_id) => success(&tr!(language, "changelog-queued")).description(tr!(
+            language,
+            "changelog-queued-description",
+            channel = format!("<#{}>", channel_id),
+        )),
+        None => neutral(&tr!(language, "changelog-unavailable"))
+            .description(tr!(language, "changelog-unavailable-description")),
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Reload the log level and changelog from the config.
+#[poise::command(slash_command, owners_only, ephemeral)]
+pub async fn reload(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for reading the config
+    ctx.defer_ephemeral().await?;
+
+    let data = ctx.data();
+    let config = crate::load_config().map_err(|err| Error::Config(format!("{:#}", err)))?;
+    let changelog = task::block_in_place(|| crate::read_changelog(&config))?;
+
+    logging::set_level(&data.log_filter, &config.log_level)?;
+    let has_changelog = changelog.is_some();
+    *data.changelog.write()? = changelog;
+
+    // NOTE: Everything else is only read when starting, such as the database path and which intents to request.
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "reload-done"))
+        .description(tr!(language, "reload-done-description"))
+        .field(
+            tr!(language, "field-log-level"),
+            format!("`{}`", config.log_level),
+            true,
+        )
+        .field(
+            tr!(language, "field-changelog"),
+            if has_changelog {
+                tr!(language, "reload-changelog-set")
+            } else {
+                tr!(language, "none")
+            },
+            true,
+        );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+fn uptime(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    let (days, hours, minutes, secs) = (
+        secs / (24 * 60 * 60),
+        secs / (60 * 60) % 24,
+        secs / 60 % 60,
+        secs % 60,
+    );
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", secs),
+        (0, 0, _) => format!("{}m {}s", minutes, secs),
+        (0, ..) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
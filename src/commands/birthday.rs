@@ -1,29 +1,72 @@
-use std::{fmt::Write, mem};
+use std::{fmt::Write, time::Duration};
 
-use chrono::{Datelike, FixedOffset, Months, NaiveDate, NaiveTime, Offset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, Offset, Utc};
 
-use poise::serenity_prelude as serenity;
+use poise::{ChoiceParameter, serenity_prelude as serenity};
 
-use serenity::{Member, UserId};
+use serenity::{
+    ButtonStyle,
+    ComponentInteractionCollector,
+    CreateActionRow,
+    CreateAttachment,
+    CreateButton,
+    CreateEmbed,
+    CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+    Member,
+    UserId,
+};
 
 use tokio::task;
 
-use tracing::warn;
+use tracing::{error, warn};
 
 use crate::{
     announcement,
-    birthday::{Birthday, Month},
+    birthday::{self, Birthday, Invalid, Month},
+    calendar,
     error::{Error, Result},
     failure,
+    format::Format,
+    locale::{Language, tr},
     neutral,
+    parse::{self, Order},
+    recurrence::Calendar,
     reply,
     success,
+    wish,
 };
 
 use super::Context;
 
+// NOTE: Discord only lets us edit the response for 15 minutes, but a minute is plenty to press a button.
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub mod anniversary;
+
+pub mod automation;
+
 pub mod channel;
 
+pub mod digest;
+
+pub mod event;
+
+#[cfg(feature = "http")]
+pub mod feed;
+
+pub mod format;
+
+pub mod grouping;
+
+pub mod locale;
+
+pub mod milestone;
+
+pub mod ping;
+
+pub mod privacy;
+
 /// Get someone's (or your) birthday.
 #[poise::command(slash_command, guild_only, ephemeral)]
 #[tracing::instrument]
@@ -42,9 +85,11 @@ pub async fn get(
     let user_id = user.id;
     let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
 
-    let birthday = task::block_in_place(|| {
-        let conn = ctx.data().conn.lock().unwrap();
-        let query = "select birthday from birthdays where user_id = ?1 and guild_id = ?2";
+    let (birthday, format) = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let format = crate::format::load(&conn, guild_id, ctx.author().id)?;
+        let query = "select birthday, coalesce(calendar, 'gregorian') from birthdays left join \
+                     calendars using (user_id, guild_id) where user_id = ?1 and guild_id = ?2";
         let birthday = conn
             .prepare(query)?
             // NOTE: We need to cast the Discord IDs here since SQLite stores integers as `i64`, and
@@ -53,33 +98,46 @@ pub async fn get(
             //       produce the same results while also being infallible.
             .query((user_id.get() as i64, guild_id.get() as i64))?
             .next()?
-            .map(|row| row.get(0).map(Birthday))
+            .map(|row| Ok::<_, Error>(Birthday(row.get(0)?, row.get(1)?)))
             .transpose()?;
-        Ok::<_, Error>(birthday)
+        Ok::<_, Error>((birthday, format))
     })?;
 
+    let language = Language::of(ctx);
     let embed = match birthday {
         Some(birthday) => {
-            let now = Utc::now().fixed_offset();
-            let age = now.years_since(birthday.0).unwrap(); // PANICS: Future dates are rejected when setting birthdays
+            let now = ctx.data().clock.now().fixed_offset();
+            let age = birthday.age(now)?;
+            let birthday = birthday.formatted(language, format);
 
             // NOTE: We check if the user ID is the same as the author's ID rather than checking if `member` is `Some`
             //       because this way we can display the correct message even if the user passes in their own ID as the
             //       command argument.
-            success("Birthday retrieved")
+            success(&tr!(language, "birthday-retrieved"))
                 .description(if user_id == ctx.author().id {
-                    format!("You were born on `{}`.", birthday)
+                    tr!(language, "birthday-get-own", birthday = birthday)
                 } else {
-                    format!("<@{}> was born on `{}`.", user_id, birthday)
+                    tr!(
+                        language,
+                        "birthday-get-other",
+                        user = format!("<@{}>", user_id),
+                        birthday = birthday,
+                    )
                 })
-                .field("Age", age.to_string(), true)
+                .field(tr!(language, "field-age"), age.to_string(), true)
         },
         // NOTE: See above.
-        None => neutral("Birthday unavailable").description(if user_id == ctx.author().id {
-            "You haven't set a birthday yet. Use `/birthday help` for information.".to_owned()
-        } else {
-            format!("<@{}> hasn't set a birthday yet.", user_id)
-        }),
+        None => neutral(&tr!(language, "birthday-unavailable")).description(
+            if user_id == ctx.author().id {
+                tr!(language, "birthday-unavailable-own-help")
+            } else {
+                tr!(
+                    language,
+                    "birthday-unavailable-other",
+                    user = format!("<@{}>", user_id),
+                )
+            },
+        ),
     };
 
     ctx.send(reply(embed)).await?;
@@ -90,6 +148,7 @@ pub async fn get(
 /// Update or set your birthday.
 #[poise::command(slash_command, guild_only, ephemeral)]
 #[tracing::instrument]
+#[allow(clippy::too_many_arguments)]
 pub async fn set(
     ctx: Context<'_>,
     #[description = "The day you were born on."]
@@ -110,56 +169,215 @@ pub async fn set(
     #[description = "The timezone you were born in. Accepts offsets as `+00:00` or `-00:00`. \
                      Defaults to `+00:00` (UTC)."]
     timezone: Option<FixedOffset>,
+    #[description = "The calendar your birthday recurs in. The date is still Gregorian. Defaults \
+                     to Gregorian."]
+    calendar: Option<Calendar>,
 ) -> Result<()> {
     // Defer response to allow time for executing the query
     ctx.defer_ephemeral().await?;
 
-    // Ensure the date is valid
+    // NOTE: The time defaults to 00:00:00 and the timezone to UTC if not provided.
     let month = month as u32;
-    let Some(date) = NaiveDate::from_ymd_opt(year, month, day.into()) else {
-        let embed = failure("Invalid birthday")
-            .description("That's not a valid year-month-day combination.")
-            .field("Year", year.to_string(), true)
-            .field("Month", month.to_string(), true)
-            .field("Day", day.to_string(), true);
-        ctx.send(reply(embed)).await?;
-        return Ok(());
-    };
-
-    // Ensure the time is valid, defaulting to 00:00:00 if not provided
     let hour = hour.map(u32::from).unwrap_or(0);
     let minute = minute.map(u32::from).unwrap_or(0);
     let second = second.map(u32::from).unwrap_or(0);
-    let Some(time) = NaiveTime::from_hms_opt(hour, minute, second) else {
-        let embed = failure("Invalid birthday")
-            .description("That's not a valid hour-minute-second combination.")
-            .field("Hour", hour.to_string(), true)
-            .field("Minute", minute.to_string(), true)
-            .field("Second", second.to_string(), true);
+    let timezone = timezone.unwrap_or(Utc.fix());
+
+    let language = Language::of(ctx);
+    let now = ctx.data().clock.now().fixed_offset();
+    let birthday = match Birthday::from_parts(
+        (year, month, day.into()),
+        (hour, minute, second),
+        timezone,
+        now,
+    ) {
+        Ok(birthday) => birthday.in_calendar(calendar.unwrap_or_default()),
+        Err(invalid) => {
+            let embed = rejected(
+                language,
+                invalid,
+                (year, month, day.into()),
+                (hour, minute, second),
+            )?;
+            ctx.send(reply(embed)).await?;
+            return Ok(());
+        },
+    };
+
+    let format = store(ctx, birthday)?;
+    let embed = updated(language, birthday, format, now)?;
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Update or set your birthday by writing it out.
+#[poise::command(slash_command, guild_only, ephemeral, rename = "set-text")]
+#[tracing::instrument]
+pub async fn set_text(
+    ctx: Context<'_>,
+    #[description = "Your birthday, such as `14 March 1998`, `1998-03-14`, or `14/03/1998 10:30 \
+                     +05:30`."]
+    date: String,
+    #[description = "The calendar your birthday recurs in. The date is still Gregorian. Defaults \
+                     to Gregorian."]
+    calendar: Option<Calendar>,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let user_id = ctx.author().id;
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let chosen = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let chosen = match crate::format::load_user(&conn, user_id)? {
+            Some(format) => Some(format),
+            None => crate::format::load_guild(&conn, guild_id)?,
+        };
+        Ok::<_, Error>(chosen)
+    })?;
+    let format = chosen.unwrap_or_default();
+
+    // NOTE: Dates like `01/02/2000` are read in the same order that the user has chosen to have dates displayed in.
+    //       The default style is day first, so if nothing was chosen, the user's locale is a better guess.
+    let order = Order::of(chosen.map(|format| format.date), ctx.locale());
+
+    let language = Language::of(ctx);
+    let Some(parsed) = parse::parse(&date, language, order) else {
+        let embed = failure(&tr!(language, "birthday-invalid"))
+            .description(tr!(language, "date-unrecognized-description"))
+            .field(
+                tr!(language, "field-provided-date"),
+                format!("`{}`", date),
+                true,
+            );
         ctx.send(reply(embed)).await?;
         return Ok(());
     };
 
-    let timezone = timezone.unwrap_or(Utc.fix());
+    // NOTE: Ages are shown and announced, so we can't store a birthday without a year.
+    let (Some(year), month, day) = parsed.date else {
+        let embed = failure(&tr!(language, "birthday-invalid"))
+            .description(tr!(language, "date-year-missing-description"))
+            .field(
+                tr!(language, "field-provided-date"),
+                format!("`{}`", date),
+                true,
+            );
+        ctx.send(reply(embed)).await?;
+        return Ok(());
+    };
 
-    // PANICS: I don't want to think about the edge cases for this. If it fails it fails.
-    let birthday = Birthday(date.and_time(time).and_local_timezone(timezone).unwrap());
+    // NOTE: The time defaults to 00:00:00 and the timezone to UTC if not provided.
+    let timezone = parsed.timezone.unwrap_or(Utc.fix());
+    let now = ctx.data().clock.now().fixed_offset();
+    let birthday = match Birthday::from_parts((year, month, day), parsed.time, timezone, now) {
+        Ok(birthday) => birthday.in_calendar(calendar.unwrap_or_default()),
+        Err(invalid) => {
+            let embed = rejected(language, invalid, (year, month, day), parsed.time)?;
+            ctx.send(reply(embed)).await?;
+            return Ok(());
+        },
+    };
 
-    // Ensure the birthday is not in a future date because that would be silly
-    let now = Utc::now().fixed_offset();
-    if birthday.0 >= now {
-        let embed = failure("Invalid birthday")
-            .description("Time travel doesn't exist yet, so your birthday can't be in the future.")
-            .field("Provided birthday", format!("```\n{}\n```", birthday), true);
+    // NOTE: Unambiguous dates are shown back to the user once saved, so only ambiguous ones need confirming.
+    if parsed.alternative.is_none() {
+        let format = store(ctx, birthday)?;
+        let embed = updated(language, birthday, format, now)?;
         ctx.send(reply(embed)).await?;
         return Ok(());
     }
 
+    let confirm_id = format!("{}-confirm", ctx.id());
+    let cancel_id = format!("{}-cancel", ctx.id());
+    let embed = neutral(&tr!(language, "date-confirm")).description(tr!(
+        language,
+        "date-confirm-description",
+        birthday = birthday.formatted(language, format),
+    ));
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(&confirm_id)
+            .style(ButtonStyle::Success)
+            .label(tr!(language, "button-confirm")),
+        CreateButton::new(&cancel_id)
+            .style(ButtonStyle::Secondary)
+            .label(tr!(language, "button-cancel")),
+    ]);
+    let handle = ctx.send(reply(embed).components(vec![buttons])).await?;
+
+    let interaction = ComponentInteractionCollector::new(ctx)
+        .author_id(user_id)
+        .channel_id(ctx.channel_id())
+        .timeout(CONFIRMATION_TIMEOUT)
+        .filter(move |interaction| [&confirm_id, &cancel_id].contains(&&interaction.data.custom_id))
+        .await;
+
+    // NOTE: The buttons are removed once used (or expired) so that the birthday can't be saved twice.
+    let Some(interaction) = interaction else {
+        let embed = neutral(&tr!(language, "date-expired"))
+            .description(tr!(language, "date-expired-description"));
+        handle
+            .edit(ctx, reply(embed).components(Vec::new()))
+            .await?;
+        return Ok(());
+    };
+
+    let embed = if interaction.data.custom_id.ends_with("-confirm") {
+        let format = store(ctx, birthday)?;
+        updated(language, birthday, format, now)?
+    } else {
+        neutral(&tr!(language, "date-cancelled"))
+            .description(tr!(language, "date-cancelled-description"))
+    };
+    let response = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(Vec::new());
+    interaction
+        .create_response(ctx, CreateInteractionResponse::UpdateMessage(response))
+        .await?;
+
+    Ok(())
+}
+
+// NOTE: Both ways of setting a birthday reject the same kinds of invalid dates, so they share their responses.
+fn rejected(
+    language: Language,
+    invalid: Invalid,
+    (year, month, day): (i32, u32, u32),
+    (hour, minute, second): (u32, u32, u32),
+) -> Result<CreateEmbed> {
+    let title = tr!(language, "birthday-invalid");
+    let embed = match invalid {
+        Invalid::Date => failure(&title)
+            .description(tr!(language, "invalid-date-description"))
+            .field(tr!(language, "field-year"), year.to_string(), true)
+            .field(tr!(language, "field-month"), month.to_string(), true)
+            .field(tr!(language, "field-day"), day.to_string(), true),
+        Invalid::Time => failure(&title)
+            .description(tr!(language, "invalid-time-description"))
+            .field(tr!(language, "field-hour"), hour.to_string(), true)
+            .field(tr!(language, "field-minute"), minute.to_string(), true)
+            .field(tr!(language, "field-second"), second.to_string(), true),
+        Invalid::Ambiguous(local) => return Err(Error::AmbiguousLocalTime(local)),
+        // NOTE: A birthday in the future would be silly.
+        Invalid::Future(birthday) => failure(&title)
+            .description(tr!(language, "birthday-future-description"))
+            .field(
+                tr!(language, "field-provided-birthday"),
+                format!("```\n{}\n```", birthday.localized(language)),
+                true,
+            ),
+    };
+    Ok(embed)
+}
+
+fn store(ctx: Context<'_>, birthday: Birthday) -> Result<Format> {
     let user_id = ctx.author().id;
     let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
 
     task::block_in_place(|| {
-        let conn = ctx.data().conn.lock().unwrap();
+        let conn = ctx.data().conn.lock()?;
         let query = "insert into birthdays (user_id, guild_id, birthday) values (?1, ?2, ?3) on \
                      conflict (user_id, guild_id) do update set birthday = excluded.birthday";
         conn.execute(
@@ -167,17 +385,42 @@ pub async fn set(
             // NOTE: See the note in `birthday::get`.
             (user_id.get() as i64, guild_id.get() as i64, birthday.0),
         )?;
-        Ok::<_, Error>(())
-    })?;
 
-    let age = now.years_since(birthday.0).unwrap(); // PANICS: Future dates are rejected above
-    let embed = success("Birthday updated")
-        .description(format!("Your birthday has been updated to `{}`.", birthday,))
-        .field("Age", age.to_string(), true);
+        // NOTE: Most birthdays are Gregorian, so we only store a calendar for those that aren't.
+        // NOTE: See the note in `birthday::get`.
+        let ids = (user_id.get() as i64, guild_id.get() as i64);
+        match birthday.1 {
+            Calendar::Gregorian => {
+                let query = "delete from calendars where user_id = ?1 and guild_id = ?2";
+                conn.execute(query, ids)?
+            },
+            calendar => {
+                let query = "insert into calendars (user_id, guild_id, calendar) values (?1, ?2, \
+                             ?3) on conflict (user_id, guild_id) do update set calendar = \
+                             excluded.calendar";
+                conn.execute(query, (ids.0, ids.1, calendar))?
+            },
+        };
 
-    ctx.send(reply(embed)).await?;
+        Ok::<_, Error>(crate::format::load(&conn, guild_id, user_id)?)
+    })
+}
 
-    Ok(())
+fn updated(
+    language: Language,
+    birthday: Birthday,
+    format: Format,
+    now: DateTime<FixedOffset>,
+) -> Result<CreateEmbed> {
+    let age = birthday.age(now)?;
+    let embed = success(&tr!(language, "birthday-updated"))
+        .description(tr!(
+            language,
+            "birthday-updated-description",
+            birthday = birthday.formatted(language, format),
+        ))
+        .field(tr!(language, "field-age"), age.to_string(), true);
+    Ok(embed)
 }
 
 /// Remove your birthday.
@@ -191,10 +434,12 @@ pub async fn unset(ctx: Context<'_>) -> Result<()> {
     let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
 
     let deleted = task::block_in_place(|| {
-        let conn = ctx.data().conn.lock().unwrap();
+        let conn = ctx.data().conn.lock()?;
         let query = "delete from birthdays where user_id = ?1 and guild_id = ?2";
         // NOTE: See the note in `birthday::get`.
         let affected = conn.execute(query, (user_id.get() as i64, guild_id.get() as i64))?;
+        let query = "delete from calendars where user_id = ?1 and guild_id = ?2";
+        conn.execute(query, (user_id.get() as i64, guild_id.get() as i64))?; // NOTE: See the note in `birthday::get`.
 
         // NOTE: User IDs and guild IDs together uniquely identify a single entry, so if more than 1 row was deleted then
         //       something has gone wrong.
@@ -210,10 +455,13 @@ pub async fn unset(ctx: Context<'_>) -> Result<()> {
         Ok::<_, Error>(affected >= 1)
     })?;
 
+    let language = Language::of(ctx);
     ctx.send(reply(if deleted {
-        success("Birthday unset").description("Your birthday was removed.")
+        success(&tr!(language, "birthday-unset"))
+            .description(tr!(language, "birthday-unset-description"))
     } else {
-        neutral("Birthday unavailable").description("You haven't set a birthday yet.")
+        neutral(&tr!(language, "birthday-unavailable"))
+            .description(tr!(language, "birthday-unavailable-own"))
     }))
     .await?;
 
@@ -227,51 +475,75 @@ pub async fn list(ctx: Context<'_>) -> Result<()> {
     // Defer response to allow time for executing the query
     ctx.defer_ephemeral().await?;
 
+    let language = Language::of(ctx);
     let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
 
+    let now = ctx.data().clock.now().fixed_offset();
+
     // TODO: Use pagination to allow displaying more birthdays overall
     let fields = task::block_in_place(|| {
-        let conn = ctx.data().conn.lock().unwrap();
-        let query = "select user_id, birthday from birthdays where guild_id = ?1 order by \
+        let conn = ctx.data().conn.lock()?;
+        let query = "select user_id, birthday, coalesce(calendar, 'gregorian') from birthdays \
+                     left join calendars using (user_id, guild_id) where guild_id = ?1 order by \
                      month(birthday), day(birthday)";
         let mut stmt = conn.prepare(query)?;
         let mut rows = stmt.query((guild_id.get() as i64,))?; // NOTE: See the note in `birthday::get`.
 
-        let mut fields = Vec::new();
-        let mut month = 0;
-        let mut field = String::new();
+        let mut birthdays = Vec::new();
         while let Some(row) = rows.next()? {
             // NOTE: See the note in `birthday::get`.
             let user_id = row.get(0).map(|id: i64| UserId::new(id as u64))?;
-            let birthday = row.get(1).map(Birthday)?;
-
-            if month == 0 {
-                month = birthday.0.month();
-            }
 
-            if birthday.0.month() != month {
-                let month_name = chrono::Month::try_from(month as u8).unwrap().name();
-                fields.push((month_name, mem::take(&mut field), false));
+            // NOTE: See the note in `queue_birthday_announcements`.
+            let birthday = match row.get(1).and_then(|date| Ok(Birthday(date, row.get(2)?))) {
+                Ok(birthday) => birthday,
+                Err(err) => {
+                    error!(?err, ?guild_id, "skipping invalid birthday of {}", user_id);
+                    continue;
+                },
+            };
+            birthdays.push((format!("<@{}>", user_id), birthday));
+        }
 
-                month = birthday.0.month();
-                field.clear();
-            }
+        // NOTE: Events are listed alongside birthdays. Sorting is stable, so birthdays on the same day stay in the
+        //       same order as before.
+        let events = crate::event::load(&conn, guild_id)?;
+        birthdays.extend(events.into_iter().map(|event| (event.label(), event.date)));
 
-            writeln!(&mut field, "<@{}> (`{}`)", user_id, birthday).unwrap();
-        }
+        // NOTE: Birthdays in other calendars fall on a different Gregorian date every year, so they are listed under
+        //       their next occurrence instead. See the note in `next` about bad rows.
+        let mut birthdays = birthdays
+            .into_iter()
+            .filter_map(|(label, birthday)| {
+                let occurrence = match birthday.1 {
+                    Calendar::Gregorian => Ok(birthday.0),
+                    _ => birthday.next_occurrence(now),
+                };
+                match occurrence {
+                    Ok(occurrence) => Some((label, birthday, occurrence)),
+                    Err(err) => {
+                        error!(?err, ?guild_id, "skipping invalid birthday of {}", label);
+                        None
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+        birthdays.sort_by_key(|(_, _, occurrence)| (occurrence.month(), occurrence.day()));
 
-        if month != 0 {
-            let month_name = chrono::Month::try_from(month as u8).unwrap().name();
-            fields.push((month_name, field, false));
-        }
+        let format = crate::format::load(&conn, guild_id, ctx.author().id)?;
+        let fields = birthday::group_by_month(language, format, birthdays);
 
         Ok::<_, Error>(fields)
     })?;
 
     let embed = match fields.len() {
-        0 => neutral("Birthdays unavailable").description("No birthdays have been set yet."),
-        1 => success("Birthdays retrieved").description("Showing 1 birthday."),
-        n => success("Birthdays retrieved").description(format!("Showing {} birthdays.", n)),
+        0 => neutral(&tr!(language, "birthdays-unavailable"))
+            .description(tr!(language, "birthdays-unavailable-description")),
+        n => success(&tr!(language, "birthdays-retrieved")).description(tr!(
+            language,
+            "birthdays-retrieved-description",
+            count = n
+        )),
     };
 
     ctx.send(reply(if fields.is_empty() {
@@ -299,9 +571,10 @@ pub async fn next(
     let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
 
     // TODO: Use pagination to allow displaying more birthdays overall
-    let mut upcoming = task::block_in_place(|| {
-        let conn = ctx.data().conn.lock().unwrap();
-        let query = "select user_id, birthday from birthdays where guild_id = ?1";
+    let (upcoming, format) = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "select user_id, birthday, coalesce(calendar, 'gregorian') from birthdays \
+                     left join calendars using (user_id, guild_id) where guild_id = ?1";
         let mut stmt = conn.prepare(query)?;
         let mut rows = stmt.query((guild_id.get() as i64,))?; // NOTE: See the note in `birthday::get`.
 
@@ -309,23 +582,42 @@ pub async fn next(
         while let Some(row) = rows.next()? {
             // NOTE: See the note in `birthday::get`.
             let user_id = row.get(0).map(|id: i64| UserId::new(id as u64))?;
-            let birthday = row.get(1).map(Birthday)?;
-            upcoming.push((user_id, birthday));
+
+            // NOTE: See the note in `queue_birthday_announcements`.
+            let birthday = match row.get(1).and_then(|date| Ok(Birthday(date, row.get(2)?))) {
+                Ok(birthday) => birthday,
+                Err(err) => {
+                    error!(?err, ?guild_id, "skipping invalid birthday of {}", user_id);
+                    continue;
+                },
+            };
+            upcoming.push((format!("<@{}>", user_id), birthday));
         }
 
-        Ok::<_, Error>(upcoming)
+        // NOTE: Events are shown alongside birthdays.
+        let events = crate::event::load(&conn, guild_id)?;
+        upcoming.extend(events.into_iter().map(|event| (event.label(), event.date)));
+
+        let format = crate::format::load(&conn, guild_id, ctx.author().id)?;
+        Ok::<_, Error>((upcoming, format))
     })?;
 
-    let now = Utc::now().fixed_offset();
-    upcoming.sort_by_cached_key(|(_, birthday)| {
-        // NOTE: See the note in `announce.rs`.
-        let years = now.years_since(birthday.0).unwrap(); // PANICS: Birthdays are always in the past
-        let last_birthday = birthday
-            .0
-            .checked_add_months(Months::new(years * 12))
-            .unwrap();
-        last_birthday
-    });
+    let language = Language::of(ctx);
+    let now = ctx.data().clock.now().fixed_offset();
+    // NOTE: Birthdays in other calendars recur after a different number of days, so we sort by their next
+    //       occurrences rather than how long ago their last ones were.
+    //       A single bad row shouldn't stop every other birthday from being listed, so we skip it instead.
+    let mut upcoming = upcoming
+        .into_iter()
+        .filter_map(|(label, birthday)| match birthday.next_occurrence(now) {
+            Ok(next_birthday) => Some((next_birthday, label, birthday)),
+            Err(err) => {
+                error!(?err, ?guild_id, "skipping invalid birthday of {}", label);
+                None
+            },
+        })
+        .collect::<Vec<_>>();
+    upcoming.sort_by_key(|&(next_birthday, ..)| next_birthday);
 
     let limit = limit.unwrap_or(1);
     let len = upcoming.len().min(limit);
@@ -334,122 +626,328 @@ pub async fn next(
         upcoming
             .into_iter()
             .take(limit)
-            .fold(String::new(), |mut field, (user_id, birthday)| {
-                writeln!(&mut field, "<@{}> (`{}`)", user_id, birthday).unwrap();
+            .fold(String::new(), |mut field, (_, label, birthday)| {
+                let birthday = birthday.formatted(language, format);
+                writeln!(&mut field, "{} ({})", label, birthday).unwrap();
                 field
             });
 
     let embed = match len {
-        0 => neutral("Birthdays unavailable").description("No birthdays have been set yet."),
-        1 => success("Birthdays retrieved").description("Showing 1 birthday."),
-        n => success("Birthdays retrieved").description(format!("Showing {} birthdays.", n)),
+        0 => neutral(&tr!(language, "birthdays-unavailable"))
+            .description(tr!(language, "birthdays-unavailable-description")),
+        n => success(&tr!(language, "birthdays-retrieved")).description(tr!(
+            language,
+            "birthdays-retrieved-description",
+            count = n
+        )),
     };
 
     ctx.send(reply(if len == 0 {
         embed
     } else {
-        embed.field("Upcoming birthdays", field, false)
+        embed.field(tr!(language, "field-upcoming-birthdays"), field, false)
     }))
     .await?;
 
     Ok(())
 }
 
+/// Export everyone's birthdays as a calendar file.
+#[poise::command(slash_command, guild_only, ephemeral)]
+#[tracing::instrument]
+pub async fn calendar(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query and retrieving members
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let birthdays = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        Ok::<_, Error>(crate::privacy::exported_birthdays(&conn, guild_id)?)
+    })?;
+
+    let named = crate::privacy::with_names(ctx, guild_id, birthdays).await?;
+
+    let language = Language::of(ctx);
+    if named.is_empty() {
+        let embed = neutral(&tr!(language, "calendar-unavailable"))
+            .description(tr!(language, "calendar-unavailable-description"));
+        ctx.send(reply(embed)).await?;
+        return Ok(());
+    }
+
+    let guild_name = guild_id
+        .name(ctx)
+        .unwrap_or_else(|| tr!(language, "the-server"));
+    let now = ctx.data().clock.now().fixed_offset();
+    let ics = calendar::render(language, guild_id, &guild_name, &named, now);
+
+    let embed = success(&tr!(language, "calendar-exported")).description(tr!(
+        language,
+        "calendar-exported-description",
+        count = named.len(),
+    ));
+    let attachment = CreateAttachment::bytes(ics.into_bytes(), "birthdays.ics");
+
+    ctx.send(reply(embed).attachment(attachment)).await?;
+
+    Ok(())
+}
+
+/// Leave a birthday wish for someone, delivered to them on their birthday.
+#[poise::command(slash_command, guild_only, ephemeral)]
+#[tracing::instrument]
+pub async fn wish(
+    ctx: Context<'_>,
+    #[description = "Who to leave the wish for."] member: Member,
+    #[description = "Your birthday wish."]
+    #[max_length = 900]
+    message: String,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let user_id = member.user.id;
+    let author_id = ctx.author().id;
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let language = Language::of(ctx);
+    if user_id == author_id {
+        let embed = failure(&tr!(language, "wish-invalid"))
+            .description(tr!(language, "wish-invalid-description"));
+        ctx.send(reply(embed)).await?;
+        return Ok(());
+    }
+
+    let birthday = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "select birthday, coalesce(calendar, 'gregorian') from birthdays left join \
+                     calendars using (user_id, guild_id) where user_id = ?1 and guild_id = ?2";
+        let birthday = conn
+            .prepare(query)?
+            // NOTE: See the note in `birthday::get`.
+            .query((user_id.get() as i64, guild_id.get() as i64))?
+            .next()?
+            .map(|row| Ok::<_, Error>(Birthday(row.get(0)?, row.get(1)?)))
+            .transpose()?;
+        Ok::<_, Error>(birthday)
+    })?;
+
+    let Some(birthday) = birthday else {
+        let embed = neutral(&tr!(language, "birthday-unavailable")).description(tr!(
+            language,
+            "birthday-unavailable-other",
+            user = format!("<@{}>", user_id),
+        ));
+        ctx.send(reply(embed)).await?;
+        return Ok(());
+    };
+
+    // Ensure the birthday is close enough, since wishes are only delivered on the next birthday
+    let now = ctx.data().clock.now().fixed_offset();
+    let next_birthday = birthday.next_occurrence(now)?;
+    if next_birthday - now > wish::WINDOW {
+        let embed = failure(&tr!(language, "wish-early")).description(tr!(
+            language,
+            "wish-early-description",
+            days = wish::WINDOW.num_days(),
+            user = format!("<@{}>", user_id),
+            next = format!("<t:{}:R>", next_birthday.timestamp()),
+        ));
+        ctx.send(reply(embed)).await?;
+        return Ok(());
+    }
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into wishes (guild_id, user_id, author_id, message) values (?1, ?2, \
+                     ?3, ?4) on conflict (guild_id, user_id, author_id) do update set message = \
+                     excluded.message";
+        conn.execute(
+            query,
+            (
+                // NOTE: See the note in `birthday::get`.
+                guild_id.get() as i64,
+                user_id.get() as i64,
+                author_id.get() as i64,
+                &message,
+            ),
+        )?;
+        Ok::<_, Error>(())
+    })?;
+
+    let embed = success(&tr!(language, "wish-saved")).description(tr!(
+        language,
+        "wish-saved-description",
+        user = format!("<@{}>", user_id),
+    ));
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
 /// Show instructions for using birthday commands.
 #[poise::command(slash_command, guild_only, ephemeral)]
 #[tracing::instrument]
-pub async fn help(ctx: Context<'_>) -> Result<()> {
-    let embed = announcement("Help")
-        .description("Here's a list of available commands.")
-        .field(
-            "Display a user's birthday",
-            "\
-```less
-/birthday get [user?]
-```
-`[user?]` defaults to you if not specified.
-",
-            false,
-        )
-        .field(
-            "Set your birthday",
-            "\
-```less
-/birthday set [day] [month] [year] [hour?] [minute?] [second?] [timezone?]  
-```
-`[hour?]`, `[minute?]`, and `[second?]` default to 0 if not specified.
-`[timezone?]` defaults to UTC (`+00:00`) if not specified.
-",
-            false,
-        )
-        .field(
-            "Remove your birthday",
-            "\
-```less
-/birthday unset
-```
-",
-            false,
-        )
-        .field(
-            "List all birthdays",
-            "\
-```less
-/birthday list
-```
-",
-            false,
-        )
-        .field(
-            "List upcoming birthdays",
-            "\
-```less
-/birthday next [limit?]
-```
-`[limit?]` defaults to 1 if not specified.
-",
-            false,
-        )
-        .field(
-            "Display the birthday announcement channel",
-            "\
-```less
-/birthday channel get
-```
-",
-            false,
-        )
-        .field(
-            "Set the birthday announcement channel",
-            "\
-```less
-/birthday channel set [channel]
-```
-",
-            false,
-        )
-        .field(
-            "Remove the birthday announcement channel",
-            "\
-```less
-/birthday channel unset
-```
-",
+pub async fn help(
+    ctx: Context<'_>,
+    #[description = "What to show instructions for. Defaults to birthdays."] topic: Option<
+        HelpTopic,
+    >,
+) -> Result<()> {
+    // NOTE: Embeds are limited to 25 fields and 6000 characters, so the help message is split into topics.
+    let topic = topic.unwrap_or(HelpTopic::Birthdays);
+    let language = Language::of(ctx);
+    let commands = &ctx.framework().options().commands;
+
+    let embed = announcement(&tr!(language, "help")).description(tr!(
+        language,
+        "help-description",
+        topic = language.get(topic.id(), None),
+        usage = usage(language, commands, "birthday help"),
+    ));
+    let embed = embed.fields(topic.entries().iter().map(|&(id, names)| {
+        let usages = names
+            .iter()
+            .map(|name| usage(language, commands, name))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let notes = language.attribute(id, "notes").unwrap_or_default();
+        (
+            language.get(id, None),
+            format!("```less\n{}\n```\n{}", usages, notes),
             false,
         )
-        .field(
-            "Show this help message",
-            "\
-```less
-/birthday help
-```
-",
-            false,
-        );
+    }));
+
     ctx.send(reply(embed)).await?;
     Ok(())
 }
 
+#[derive(Debug, ChoiceParameter, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum HelpTopic {
+    #[name = "Birthdays"]
+    Birthdays,
+    #[name = "Announcements"]
+    Announcements,
+    #[name = "Customisation"]
+    Customisation,
+    #[name = "Events"]
+    Events,
+}
+
+impl HelpTopic {
+    fn id(self) -> &'static str {
+        match self {
+            Self::Birthdays => "help-topic-birthdays",
+            Self::Announcements => "help-topic-announcements",
+            Self::Customisation => "help-topic-customisation",
+            Self::Events => "help-topic-events",
+        }
+    }
+
+    // NOTE: Each entry is the message explaining some commands, and the qualified names of those commands. Their usage
+    //       is generated from the commands themselves, so that it always matches their (translated) names and
+    //       parameters.
+    fn entries(self) -> &'static [(&'static str, &'static [&'static str])] {
+        match self {
+            Self::Birthdays => &[
+                ("help-get", &["birthday get"]),
+                ("help-set", &["birthday set"]),
+                ("help-set-text", &["birthday set-text"]),
+                ("help-unset", &["birthday unset"]),
+                ("help-list", &["birthday list"]),
+                ("help-next", &["birthday next"]),
+                ("help-calendar", &["birthday calendar"]),
+                ("help-privacy-get", &["birthday privacy get"]),
+                ("help-privacy-set", &["birthday privacy set"]),
+                ("help-wish", &["birthday wish"]),
+                ("help-help", &["birthday help"]),
+            ],
+            Self::Announcements => &[
+                ("help-channel-get", &["birthday channel get"]),
+                ("help-channel-set", &["birthday channel set"]),
+                ("help-channel-unset", &["birthday channel unset"]),
+                ("help-locale-get", &["birthday locale get"]),
+                ("help-locale-set", &["birthday locale set"]),
+                ("help-locale-unset", &["birthday locale unset"]),
+                ("help-grouping-get", &["birthday grouping get"]),
+                ("help-grouping-set", &["birthday grouping set"]),
+                ("help-digest-get", &["birthday digest get"]),
+                ("help-digest-set", &["birthday digest set"]),
+                ("help-digest-unset", &["birthday digest unset"]),
+                ("help-anniversary-get", &["birthday anniversary get"]),
+                ("help-anniversary-set", &["birthday anniversary set"]),
+                ("help-anniversary-unset", &["birthday anniversary unset"]),
+                (
+                    "help-anniversary-opt",
+                    &[
+                        "birthday anniversary opt-out",
+                        "birthday anniversary opt-in",
+                    ],
+                ),
+            ],
+            Self::Customisation => &[
+                ("help-ping-get", &["birthday ping get"]),
+                ("help-ping-set", &["birthday ping set"]),
+                ("help-ping-unset", &["birthday ping unset"]),
+                ("help-automation-get", &["birthday automation get"]),
+                ("help-automation-set", &["birthday automation set"]),
+                ("help-automation-unset", &["birthday automation unset"]),
+                ("help-milestone-list", &["birthday milestone list"]),
+                ("help-milestone-add", &["birthday milestone add"]),
+                ("help-milestone-remove", &["birthday milestone remove"]),
+                ("help-format-get", &["birthday format get"]),
+                ("help-format-set", &["birthday format set"]),
+                ("help-format-unset", &["birthday format unset"]),
+                (
+                    "help-format-server",
+                    &["birthday format set-server", "birthday format unset-server"],
+                ),
+            ],
+            Self::Events => &[
+                ("help-event-list", &["birthday event list"]),
+                ("help-event-add", &["birthday event add"]),
+                ("help-event-remove", &["birthday event remove"]),
+            ],
+        }
+    }
+}
+
+/// Returns how to use a command, given its qualified name, with its name and parameters translated.
+fn usage<U, E>(language: Language, commands: &[poise::Command<U, E>], name: &str) -> String {
+    let locale = language.code();
+    let mut names = Vec::new();
+    let mut commands = commands;
+    let mut parameters = [].as_slice();
+    for name in name.split(' ') {
+        // PANICS: Every command in the help message exists (see the test below).
+        let command = commands
+            .iter()
+            .find(|command| command.name == name)
+            .unwrap();
+        names.push(
+            command
+                .name_localizations
+                .get(locale)
+                .unwrap_or(&command.name)
+                .as_str(),
+        );
+
+        commands = &command.subcommands;
+        parameters = &command.parameters;
+    }
+
+    let mut usage = format!("/{}", names.join(" "));
+    for param in parameters {
+        let name = param.name_localizations.get(locale).unwrap_or(&param.name);
+        let optional = if param.required { "" } else { "?" };
+        write!(&mut usage, " [{}{}]", name, optional).unwrap();
+    }
+    usage
+}
+
 #[poise::command(
     slash_command,
     subcommands("channel::get", "channel::set", "channel::unset")
@@ -457,3 +955,129 @@ pub async fn help(ctx: Context<'_>) -> Result<()> {
 pub async fn channel(_: Context<'_>) -> Result<()> {
     Ok(())
 }
+
+#[poise::command(
+    slash_command,
+    subcommands("locale::get", "locale::set", "locale::unset")
+)]
+pub async fn locale(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands(
+        "format::get",
+        "format::set",
+        "format::unset",
+        "format::set_server",
+        "format::unset_server",
+    )
+)]
+pub async fn format(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[poise::command(slash_command, subcommands("grouping::get", "grouping::set"))]
+pub async fn grouping(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("digest::get", "digest::set", "digest::unset")
+)]
+pub async fn digest(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[poise::command(slash_command, subcommands("ping::get", "ping::set", "ping::unset"))]
+pub async fn ping(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("automation::get", "automation::set", "automation::unset")
+)]
+pub async fn automation(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("milestone::list", "milestone::add", "milestone::remove")
+)]
+pub async fn milestone(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("event::list", "event::add", "event::remove")
+)]
+pub async fn event(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(feature = "http")]
+#[poise::command(slash_command, subcommands("feed::generate", "feed::revoke"))]
+pub async fn feed(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[poise::command(slash_command, subcommands("privacy::get", "privacy::set"))]
+pub async fn privacy(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands(
+        "anniversary::get",
+        "anniversary::set",
+        "anniversary::unset",
+        "anniversary::opt_out",
+        "anniversary::opt_in",
+    )
+)]
+pub async fn anniversary(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_help_entry_is_a_command() {
+        let commands = crate::commands::all();
+        let topics = [
+            HelpTopic::Birthdays,
+            HelpTopic::Announcements,
+            HelpTopic::Customisation,
+            HelpTopic::Events,
+        ];
+        for topic in topics {
+            for &(id, names) in topic.entries() {
+                assert_ne!(Language::English.get(id, None), id);
+                for name in names {
+                    usage(Language::English, &commands, name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn usage_is_translated() {
+        let commands = crate::commands::all();
+        assert_eq!(
+            usage(Language::English, &commands, "birthday next"),
+            "/birthday next [limit?]",
+        );
+        assert_eq!(
+            usage(Language::German, &commands, "birthday next"),
+            "/geburtstag nächste [anzahl?]",
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use tokio::task;
+
+use tracing::warn;
+
+use crate::{
+    anniversary,
+    commands::Context,
+    error::{Error, Result},
+    locale::{self, Language, tr},
+    neutral,
+    reply,
+    success,
+};
+
+/// Show whether server anniversaries are announced.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn get(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    // NOTE: The default template is shown in the language that it's announced in, rather than the user's language.
+    let template = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let announced_in = locale::load(&conn, ctx.serenity_context(), guild_id)?;
+        Ok::<_, Error>(anniversary::load_template(&conn, guild_id, announced_in)?)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = match template {
+        Some(template) => success(&tr!(language, "anniversary-retrieved"))
+            .description(tr!(language, "anniversary-retrieved-description"))
+            .field(
+                tr!(language, "field-template"),
+                template_field(&template),
+                false,
+            ),
+        None => neutral(&tr!(language, "anniversary-unavailable"))
+            .description(tr!(language, "anniversary-unavailable-description")),
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Announce the anniversaries of when members joined the server.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "The announcement message. Supports `{user}` and `{years}`. Defaults to a \
+                     generic message."]
+    #[max_length = 1000]
+    template: Option<String>,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let announced_in = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into anniversaries (guild_id, template) values (?1, ?2) on conflict \
+                     (guild_id) do update set template = excluded.template";
+        // NOTE: See the note in `birthday::get`.
+        conn.execute(query, (guild_id.get() as i64, &template))?;
+        Ok::<_, Error>(locale::load(&conn, ctx.serenity_context(), guild_id)?)
+    })?;
+
+    // NOTE: See the note in `get`.
+    let template = template.unwrap_or_else(|| anniversary::default_template(announced_in));
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "anniversary-updated"))
+        .description(tr!(language, "anniversary-updated-description"))
+        .field(
+            tr!(language, "field-template"),
+            template_field(&template),
+            false,
+        );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Stop announcing the anniversaries of when members joined the server.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn unset(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let deleted = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "delete from anniversaries where guild_id = ?1";
+        // NOTE: See the note in `birthday::get`.
+        let affected = conn.execute(query, (guild_id.get() as i64,))?;
+
+        // NOTE: Guild IDs uniquely identify a row, so if more than 1 row was deleted then something has gone wrong.
+        if affected > 1 {
+            warn!(
+                ?guild_id,
+                "{} rows affected by `birthday anniversary unset`", affected,
+            );
+        }
+
+        Ok::<_, Error>(affected >= 1)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = if deleted {
+        success(&tr!(language, "anniversary-unset"))
+            .description(tr!(language, "anniversary-unset-description"))
+    } else {
+        neutral(&tr!(language, "anniversary-unavailable"))
+            .description(tr!(language, "anniversary-unavailable-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Stop your server anniversary from being announced.
+#[poise::command(slash_command, guild_only, ephemeral, rename = "opt-out")]
+pub async fn opt_out(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let user_id = ctx.author().id;
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into anniversary_optouts (guild_id, user_id) values (?1, ?2) on \
+                     conflict (guild_id, user_id) do nothing";
+        // NOTE: See the note in `birthday::get`.
+        conn.execute(query, (guild_id.get() as i64, user_id.get() as i64))?;
+        Ok::<_, Error>(())
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "anniversary-opted-out"))
+        .description(tr!(language, "anniversary-opted-out-description"));
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Allow your server anniversary to be announced again.
+#[poise::command(slash_command, guild_only, ephemeral, rename = "opt-in")]
+pub async fn opt_in(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let user_id = ctx.author().id;
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let deleted = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "delete from anniversary_optouts where guild_id = ?1 and user_id = ?2";
+        // NOTE: See the note in `birthday::get`.
+        let affected = conn.execute(query, (guild_id.get() as i64, user_id.get() as i64))?;
+        Ok::<_, Error>(affected >= 1)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = if deleted {
+        success(&tr!(language, "anniversary-opted-in"))
+            .description(tr!(language, "anniversary-opted-in-description"))
+    } else {
+        neutral(&tr!(language, "anniversary-already-opted-in"))
+            .description(tr!(language, "anniversary-already-opted-in-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+// NOTE: See the note in `on_error` about zero-width spaces.
+fn template_field(template: &str) -> String {
+    format!(
+        "```\n{}\n```",
+        template.replace("```", "\u{200B}`\u{200B}`\u{200B}`"),
+    )
+}
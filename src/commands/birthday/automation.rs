@@ -0,0 +1,185 @@
+use poise::serenity_prelude as serenity;
+
+use serenity::CreateEmbed;
+
+use tokio::task;
+
+use tracing::warn;
+
+use crate::{
+    automation::{self, Automation},
+    commands::Context,
+    error::{Error, Result},
+    failure,
+    locale::{Language, tr},
+    neutral,
+    reply,
+    success,
+};
+
+/// Show what happens after a birthday is announced.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn get(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let automation = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        Ok::<_, Error>(Automation::load(&conn, guild_id)?)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = fields(
+        language,
+        success(&tr!(language, "automation-retrieved"))
+            .description(tr!(language, "automation-retrieved-description")),
+        &automation,
+    );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Update or set what happens after a birthday is announced.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Emojis to react to announcements with, separated by spaces. Defaults to none."]
+    reactions: Option<String>,
+    #[description = "Whether to open a thread on each announcement. Defaults to false."]
+    thread: Option<bool>,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    // Ensure the reactions are valid emojis
+    let language = Language::of(ctx);
+    let reactions = reactions.unwrap_or_default();
+    let parsed = match automation::parse_reactions(&reactions) {
+        Some(parsed) if parsed.len() <= automation::MAX_REACTIONS => parsed,
+        _ => {
+            let embed = failure(&tr!(language, "automation-invalid"))
+                .description(tr!(
+                    language,
+                    "automation-invalid-description",
+                    max = automation::MAX_REACTIONS,
+                ))
+                // NOTE: See the note in `on_error` about zero-width spaces.
+                .field(
+                    tr!(language, "field-provided-reactions"),
+                    format!(
+                        "```\n{}\n```",
+                        reactions.replace("```", "\u{200B}`\u{200B}`\u{200B}`"),
+                    ),
+                    true,
+                );
+            ctx.send(reply(embed)).await?;
+            return Ok(());
+        },
+    };
+
+    let automation = Automation {
+        reactions: parsed,
+        thread: thread.unwrap_or(false),
+    };
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into automations (guild_id, reactions, thread) values (?1, ?2, ?3) on \
+                     conflict (guild_id) do update set reactions = excluded.reactions, thread = \
+                     excluded.thread";
+        // NOTE: See the note in `birthday::get`.
+        conn.execute(
+            query,
+            (guild_id.get() as i64, &reactions, automation.thread),
+        )?;
+        Ok::<_, Error>(())
+    })?;
+
+    let embed = fields(
+        language,
+        success(&tr!(language, "automation-updated"))
+            .description(tr!(language, "automation-updated-description")),
+        &automation,
+    );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Stop following up on birthday announcements.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn unset(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let deleted = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "delete from automations where guild_id = ?1";
+        // NOTE: See the note in `birthday::get`.
+        let affected = conn.execute(query, (guild_id.get() as i64,))?;
+
+        // NOTE: Guild IDs uniquely identify a row, so if more than 1 row was deleted then something has gone wrong.
+        if affected > 1 {
+            warn!(
+                ?guild_id,
+                "{} rows affected by `birthday automation unset`", affected,
+            );
+        }
+
+        Ok::<_, Error>(affected >= 1)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = if deleted {
+        success(&tr!(language, "automation-unset"))
+            .description(tr!(language, "automation-unset-description"))
+    } else {
+        neutral(&tr!(language, "automation-unavailable"))
+            .description(tr!(language, "automation-unavailable-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+fn fields(language: Language, embed: CreateEmbed, automation: &Automation) -> CreateEmbed {
+    let reactions = if automation.reactions.is_empty() {
+        tr!(language, "none")
+    } else {
+        automation
+            .reactions
+            .iter()
+            .map(|reaction| reaction.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let thread = if automation.thread {
+        tr!(language, "yes")
+    } else {
+        tr!(language, "no")
+    };
+
+    embed
+        .field(tr!(language, "field-reactions"), reactions, true)
+        .field(tr!(language, "field-thread"), thread, true)
+}
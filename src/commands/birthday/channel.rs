@@ -9,6 +9,7 @@ use tracing::warn;
 use crate::{
     commands::Context,
     error::{Error, Result},
+    locale::{Language, tr},
     neutral,
     reply,
     success,
@@ -23,7 +24,7 @@ pub async fn get(ctx: Context<'_>) -> Result<()> {
     let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
 
     let channel_id = task::block_in_place(|| {
-        let conn = ctx.data().conn.lock().unwrap();
+        let conn = ctx.data().conn.lock()?;
         let query = "select channel_id from announcements where guild_id = ?1";
         let channel_id = conn
             .prepare(query)?
@@ -36,13 +37,15 @@ pub async fn get(ctx: Context<'_>) -> Result<()> {
         Ok::<_, Error>(channel_id)
     })?;
 
+    let language = Language::of(ctx);
     let embed = match channel_id {
-        Some(channel_id) => success("Channel retrieved").description(format!(
-            "Birthdays and updates are announced in <#{}>.",
-            channel_id,
+        Some(channel_id) => success(&tr!(language, "channel-retrieved")).description(tr!(
+            language,
+            "channel-retrieved-description",
+            channel = format!("<#{}>", channel_id),
         )),
-        None => neutral("Channel unavailable")
-            .description("A birthday announcement channel hasn't been set yet."),
+        None => neutral(&tr!(language, "channel-unavailable"))
+            .description(tr!(language, "channel-unavailable-description")),
     };
 
     ctx.send(reply(embed)).await?;
@@ -70,7 +73,7 @@ pub async fn set(
     let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
 
     task::block_in_place(|| {
-        let conn = ctx.data().conn.lock().unwrap();
+        let conn = ctx.data().conn.lock()?;
         let query = "insert into announcements (guild_id, channel_id) values (?1, ?2) on conflict \
                      (guild_id) do update set channel_id = excluded.channel_id";
         // NOTE: See the note in `birthday::get`.
@@ -78,9 +81,11 @@ pub async fn set(
         Ok::<_, Error>(())
     })?;
 
-    let embed = success("Channel updated").description(format!(
-        "The birthday announcement channel has been updated to <#{}>.",
-        channel_id,
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "channel-updated")).description(tr!(
+        language,
+        "channel-updated-description",
+        channel = format!("<#{}>", channel_id),
     ));
 
     ctx.send(reply(embed)).await?;
@@ -102,7 +107,7 @@ pub async fn unset(ctx: Context<'_>) -> Result<()> {
     let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
 
     let deleted = task::block_in_place(|| {
-        let conn = ctx.data().conn.lock().unwrap();
+        let conn = ctx.data().conn.lock()?;
         let query = "delete from announcements where guild_id = ?1";
         // NOTE: See the note in `birthday::get`.
         let affected = conn.execute(query, (guild_id.get() as i64,))?;
@@ -118,11 +123,13 @@ pub async fn unset(ctx: Context<'_>) -> Result<()> {
         Ok::<_, Error>(affected >= 1)
     })?;
 
+    let language = Language::of(ctx);
     let embed = if deleted {
-        success("Channel unset").description("Birthdays are no longer announced in any channel.")
+        success(&tr!(language, "channel-unset"))
+            .description(tr!(language, "channel-unset-description"))
     } else {
-        neutral("Channel unavailable")
-            .description("A birthday announcement channel hasn't been set yet.")
+        neutral(&tr!(language, "channel-unavailable"))
+            .description(tr!(language, "channel-unavailable-description"))
     };
 
     ctx.send(reply(embed)).await?;
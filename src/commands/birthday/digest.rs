@@ -0,0 +1,160 @@
+use poise::{ChoiceParameter, serenity_prelude as serenity};
+
+use serenity::CreateEmbed;
+
+use tokio::task;
+
+use tracing::warn;
+
+use crate::{
+    commands::Context,
+    digest::Frequency,
+    error::{Error, Result},
+    locale::{Language, tr},
+    neutral,
+    reply,
+    success,
+};
+
+/// Show how often birthday digests are posted.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn get(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let digest = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "select frequency, hour from digests where guild_id = ?1";
+        let digest = conn
+            .prepare(query)?
+            // NOTE: See the note in `birthday::get`.
+            .query((guild_id.get() as i64,))?
+            .next()?
+            .map(|row| Ok::<_, Error>((row.get::<_, Frequency>(0)?, row.get::<_, u32>(1)?)))
+            .transpose()?;
+        Ok::<_, Error>(digest)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = match digest {
+        Some((frequency, hour)) => fields(
+            language,
+            success(&tr!(language, "digest-retrieved"))
+                .description(tr!(language, "digest-retrieved-description")),
+            frequency,
+            hour,
+        ),
+        None => neutral(&tr!(language, "digest-unavailable"))
+            .description(tr!(language, "digest-unavailable-description")),
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Update or set how often birthday digests are posted.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "How often to post digests. Weekly digests are posted on Mondays, and monthly \
+                     digests on the 1st."]
+    frequency: Frequency,
+    #[description = "The hour (in UTC) to post digests at. Defaults to 0."]
+    #[max = 23]
+    hour: Option<u8>,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let hour = hour.map(u32::from).unwrap_or(0);
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into digests (guild_id, frequency, hour) values (?1, ?2, ?3) on \
+                     conflict (guild_id) do update set frequency = excluded.frequency, hour = \
+                     excluded.hour";
+        // NOTE: See the note in `birthday::get`.
+        conn.execute(query, (guild_id.get() as i64, frequency, hour))?;
+        Ok::<_, Error>(())
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = fields(
+        language,
+        success(&tr!(language, "digest-updated"))
+            .description(tr!(language, "digest-updated-description")),
+        frequency,
+        hour,
+    );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Stop posting birthday digests.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn unset(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let deleted = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "delete from digests where guild_id = ?1";
+        // NOTE: See the note in `birthday::get`.
+        let affected = conn.execute(query, (guild_id.get() as i64,))?;
+
+        // NOTE: Guild IDs uniquely identify a row, so if more than 1 row was deleted then something has gone wrong.
+        if affected > 1 {
+            warn!(
+                ?guild_id,
+                "{} rows affected by `birthday digest unset`", affected,
+            );
+        }
+
+        Ok::<_, Error>(affected >= 1)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = if deleted {
+        success(&tr!(language, "digest-unset"))
+            .description(tr!(language, "digest-unset-description"))
+    } else {
+        neutral(&tr!(language, "digest-unavailable"))
+            .description(tr!(language, "digest-unavailable-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+fn fields(language: Language, embed: CreateEmbed, frequency: Frequency, hour: u32) -> CreateEmbed {
+    embed
+        .field(
+            tr!(language, "field-frequency"),
+            language.choice(frequency.name()),
+            true,
+        )
+        .field(
+            tr!(language, "field-time"),
+            format!("`{:02}:00` (UTC)", hour),
+            true,
+        )
+}
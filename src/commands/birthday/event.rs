@@ -0,0 +1,239 @@
+use chrono::{FixedOffset, Offset, Utc};
+
+use poise::serenity_prelude as serenity;
+
+use serenity::User;
+
+use tokio::task;
+
+use tracing::warn;
+
+use crate::{
+    birthday::{Birthday, Invalid, Month},
+    commands::Context,
+    error::{Error, Result},
+    event::{self, Event},
+    failure,
+    format::{self, Format},
+    locale::{Language, tr},
+    neutral,
+    reply,
+    success,
+};
+
+/// List the server's recurring events.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn list(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let (events, format) = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        Ok::<_, Error>((
+            event::load(&conn, guild_id)?,
+            format::load(&conn, guild_id, ctx.author().id)?,
+        ))
+    })?;
+
+    // TODO: Use pagination to allow displaying more events overall
+    let language = Language::of(ctx);
+    let embed = match events.len() {
+        0 => neutral(&tr!(language, "events-unavailable"))
+            .description(tr!(language, "events-unavailable-description")),
+        n if n > 25 => success(&tr!(language, "events-retrieved")).description(tr!(
+            language,
+            "events-retrieved-truncated-description",
+            count = n
+        )),
+        n => success(&tr!(language, "events-retrieved")).description(tr!(
+            language,
+            "events-retrieved-description",
+            count = n
+        )),
+    };
+    let embed = embed.fields(
+        events
+            .iter()
+            .take(25)
+            .map(|event| (event.name.clone(), describe(language, format, event), false)),
+    );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Add or update a recurring event, such as the day the server was founded.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "The name of the event."]
+    #[max_length = 100]
+    name: String,
+    #[description = "The day the event first happened on."]
+    #[min = 1]
+    #[max = 31]
+    day: u8,
+    #[description = "The month the event first happened in."] month: Month,
+    #[description = "The year the event first happened in."] year: i32,
+    #[description = "The description shown when the event is announced. Defaults to a generic \
+                     message."]
+    #[max_length = 1000]
+    description: Option<String>,
+    #[description = "A user the event is about, who is pinged when it is announced. Defaults to \
+                     none."]
+    user: Option<User>,
+    #[description = "The timezone of the event. Accepts offsets as `+00:00` or `-00:00`. Defaults \
+                     to `+00:00` (UTC)."]
+    timezone: Option<FixedOffset>,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let month = month as u32;
+    let timezone = timezone.unwrap_or(Utc.fix());
+
+    let language = Language::of(ctx);
+    let now = ctx.data().clock.now().fixed_offset();
+    let date = match Birthday::from_parts((year, month, day.into()), (0, 0, 0), timezone, now) {
+        Ok(date) => date,
+        Err(invalid) => {
+            let title = tr!(language, "event-invalid");
+            let embed = match invalid {
+                Invalid::Date => failure(&title)
+                    .description(tr!(language, "invalid-date-description"))
+                    .field(tr!(language, "field-year"), year.to_string(), true)
+                    .field(tr!(language, "field-month"), month.to_string(), true)
+                    .field(tr!(language, "field-day"), day.to_string(), true),
+                Invalid::Time => unreachable!(), // PANICS: Midnight is always a valid time
+                Invalid::Ambiguous(local) => return Err(Error::AmbiguousLocalTime(local)),
+                // NOTE: An event in the future wouldn't have an anniversary yet.
+                Invalid::Future(date) => failure(&title)
+                    .description(tr!(language, "event-future-description"))
+                    .field(
+                        tr!(language, "field-provided-date"),
+                        format!("```\n{}\n```", date.localized(language)),
+                        true,
+                    ),
+            };
+            ctx.send(reply(embed)).await?;
+            return Ok(());
+        },
+    };
+
+    let event = Event {
+        name,
+        description,
+        user_id: user.map(|user| user.id),
+        date,
+    };
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let format = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into events (guild_id, name, description, user_id, date) values (?1, \
+                     ?2, ?3, ?4, ?5) on conflict (guild_id, name) do update set description = \
+                     excluded.description, user_id = excluded.user_id, date = excluded.date";
+        conn.execute(
+            query,
+            (
+                // NOTE: See the note in `birthday::get`.
+                guild_id.get() as i64,
+                &event.name,
+                &event.description,
+                event.user_id.map(|id| id.get() as i64),
+                event.date.0,
+            ),
+        )?;
+        Ok::<_, Error>(format::load(&conn, guild_id, ctx.author().id)?)
+    })?;
+
+    let embed = success(&tr!(language, "event-updated"))
+        .description(tr!(language, "event-updated-description"))
+        .field(
+            event.name.clone(),
+            describe(language, format, &event),
+            false,
+        );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Remove a recurring event.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "The name of the event."]
+    #[max_length = 100]
+    name: String,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let deleted = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "delete from events where guild_id = ?1 and name = ?2";
+        // NOTE: See the note in `birthday::get`.
+        let affected = conn.execute(query, (guild_id.get() as i64, &name))?;
+
+        // NOTE: Guild IDs and names together uniquely identify a row, so if more than 1 row was deleted then
+        //       something has gone wrong.
+        if affected > 1 {
+            warn!(
+                ?guild_id,
+                name, "{} rows affected by `birthday event remove`", affected,
+            );
+        }
+
+        Ok::<_, Error>(affected >= 1)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = if deleted {
+        success(&tr!(language, "event-removed"))
+            .description(tr!(language, "event-removed-description"))
+    } else {
+        neutral(&tr!(language, "event-unavailable"))
+            .description(tr!(language, "event-unavailable-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+fn describe(language: Language, format: Format, event: &Event) -> String {
+    let description = event
+        .description
+        .clone()
+        .unwrap_or_else(|| tr!(language, "default"));
+    let user = event
+        .user_id
+        .map(|user_id| format!("<@{}>", user_id))
+        .unwrap_or_else(|| tr!(language, "none"));
+    tr!(
+        language,
+        "event-details",
+        date = event.date.formatted(language, format),
+        description = description,
+        user = user,
+    )
+}
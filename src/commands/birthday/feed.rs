@@ -0,0 +1,86 @@
+use tokio::task;
+
+use crate::{
+    commands::Context,
+    error::{Error, Result},
+    feed,
+    locale::{Language, tr},
+    neutral,
+    reply,
+    success,
+};
+
+/// Generate a secret token for accessing the server's birthday feeds, replacing any previous token.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn generate(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let token = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        Ok::<_, Error>(feed::generate_token(&conn, guild_id)?)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "feed-generated"))
+        .description(tr!(language, "feed-generated-description"))
+        .field(
+            tr!(language, "field-token"),
+            format!("```\n{}\n```", token),
+            false,
+        )
+        .field(
+            tr!(language, "field-feeds"),
+            format!(
+                "```\n/guilds/{0}/calendar.ics?token={1}\n/guilds/{0}/birthdays\n/guilds/{0}/\
+                 upcoming\n```{2}",
+                guild_id,
+                token,
+                tr!(language, "feed-bearer"),
+            ),
+            false,
+        );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Revoke the secret token for accessing the server's birthday feeds.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn revoke(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let revoked = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        Ok::<_, Error>(feed::revoke_token(&conn, guild_id)?)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = if revoked {
+        success(&tr!(language, "feed-revoked"))
+            .description(tr!(language, "feed-revoked-description"))
+    } else {
+        neutral(&tr!(language, "feed-unavailable"))
+            .description(tr!(language, "feed-unavailable-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
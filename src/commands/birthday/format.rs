@@ -0,0 +1,294 @@
+use poise::{ChoiceParameter, serenity_prelude as serenity};
+
+use serenity::CreateEmbed;
+
+use tokio::task;
+
+use tracing::warn;
+
+use crate::{
+    commands::Context,
+    error::{Error, Result},
+    format::{self, Clock, DateStyle, Format},
+    locale::{Language, tr},
+    neutral,
+    reply,
+    success,
+};
+
+/// Show how dates are displayed to you.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn get(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let user_id = ctx.author().id;
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let (user, guild) = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        Ok::<_, Error>((
+            format::load_user(&conn, user_id)?,
+            format::load_guild(&conn, guild_id)?,
+        ))
+    })?;
+
+    let language = Language::of(ctx);
+    let (description, format) = match (user, guild) {
+        (Some(format), _) => (tr!(language, "format-retrieved-own-description"), format),
+        (None, Some(format)) => (tr!(language, "format-retrieved-server-description"), format),
+        (None, None) => (
+            tr!(language, "format-retrieved-default-description"),
+            Format::default(),
+        ),
+    };
+    let embed = fields(
+        ctx,
+        language,
+        success(&tr!(language, "format-retrieved")).description(description),
+        format,
+    );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Update or set how dates are displayed to you.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "The order to write dates in, or Discord timestamps to use your client's \
+                     settings."]
+    date: DateStyle,
+    #[description = "Whether to write times with 24 or 12 hours. Defaults to 24-hour."]
+    clock: Option<Clock>,
+    #[description = "Whether to show the UTC offset of dates. Defaults to true."] offset: Option<
+        bool,
+    >,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let format = Format {
+        date,
+        clock: clock.unwrap_or_default(),
+        offset: offset.unwrap_or(true),
+    };
+
+    let user_id = ctx.author().id;
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into user_formats (user_id, date, clock, show_offset) values (?1, ?2, \
+                     ?3, ?4) on conflict (user_id) do update set date = excluded.date, clock = \
+                     excluded.clock, show_offset = excluded.show_offset";
+        // NOTE: See the note in `birthday::get`.
+        conn.execute(
+            query,
+            (
+                user_id.get() as i64,
+                format.date,
+                format.clock,
+                format.offset,
+            ),
+        )?;
+        Ok::<_, Error>(())
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = fields(
+        ctx,
+        language,
+        success(&tr!(language, "format-updated"))
+            .description(tr!(language, "format-updated-description")),
+        format,
+    );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Display dates to you in the server's format again.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn unset(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let user_id = ctx.author().id;
+
+    let deleted = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "delete from user_formats where user_id = ?1";
+        // NOTE: See the note in `birthday::get`.
+        let affected = conn.execute(query, (user_id.get() as i64,))?;
+
+        // NOTE: User IDs uniquely identify a row, so if more than 1 row was deleted then something has gone wrong.
+        if affected > 1 {
+            warn!(
+                ?user_id,
+                "{} rows affected by `birthday format unset`", affected,
+            );
+        }
+
+        Ok::<_, Error>(affected >= 1)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = if deleted {
+        success(&tr!(language, "format-unset"))
+            .description(tr!(language, "format-unset-description"))
+    } else {
+        neutral(&tr!(language, "format-unavailable"))
+            .description(tr!(language, "format-unavailable-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Update or set how dates are displayed in announcements, and to members without their own format.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR",
+    rename = "set-server"
+)]
+pub async fn set_server(
+    ctx: Context<'_>,
+    #[description = "The order to write dates in, or Discord timestamps to use each member's \
+                     settings."]
+    date: DateStyle,
+    #[description = "Whether to write times with 24 or 12 hours. Defaults to 24-hour."]
+    clock: Option<Clock>,
+    #[description = "Whether to show the UTC offset of dates. Defaults to true."] offset: Option<
+        bool,
+    >,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let format = Format {
+        date,
+        clock: clock.unwrap_or_default(),
+        offset: offset.unwrap_or(true),
+    };
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into guild_formats (guild_id, date, clock, show_offset) values (?1, \
+                     ?2, ?3, ?4) on conflict (guild_id) do update set date = excluded.date, clock \
+                     = excluded.clock, show_offset = excluded.show_offset";
+        // NOTE: See the note in `birthday::get`.
+        conn.execute(
+            query,
+            (
+                guild_id.get() as i64,
+                format.date,
+                format.clock,
+                format.offset,
+            ),
+        )?;
+        Ok::<_, Error>(())
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = fields(
+        ctx,
+        language,
+        success(&tr!(language, "format-server-updated"))
+            .description(tr!(language, "format-server-updated-description")),
+        format,
+    );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Display dates in the server in the default format again.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR",
+    rename = "unset-server"
+)]
+pub async fn unset_server(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let deleted = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "delete from guild_formats where guild_id = ?1";
+        // NOTE: See the note in `birthday::get`.
+        let affected = conn.execute(query, (guild_id.get() as i64,))?;
+
+        // NOTE: Guild IDs uniquely identify a row, so if more than 1 row was deleted then something has gone wrong.
+        if affected > 1 {
+            warn!(
+                ?guild_id,
+                "{} rows affected by `birthday format unset-server`", affected,
+            );
+        }
+
+        Ok::<_, Error>(affected >= 1)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = if deleted {
+        success(&tr!(language, "format-server-unset"))
+            .description(tr!(language, "format-server-unset-description"))
+    } else {
+        neutral(&tr!(language, "format-server-unavailable"))
+            .description(tr!(language, "format-server-unavailable-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+fn fields(ctx: Context<'_>, language: Language, embed: CreateEmbed, format: Format) -> CreateEmbed {
+    let offset = if format.offset {
+        tr!(language, "yes")
+    } else {
+        tr!(language, "no")
+    };
+    let now = ctx.data().clock.now().fixed_offset();
+
+    let embed = embed
+        .field(
+            tr!(language, "field-date"),
+            language.choice(format.date.name()),
+            true,
+        )
+        .field(
+            tr!(language, "field-clock"),
+            language.choice(format.clock.name()),
+            true,
+        )
+        .field(tr!(language, "field-offset"), offset, true)
+        .field(
+            tr!(language, "field-example"),
+            format.markdown(language, now),
+            false,
+        );
+
+    // NOTE: Discord timestamps are rendered by each viewer's client, so the other preferences don't apply to them.
+    match format.date {
+        DateStyle::Discord => embed.field(
+            tr!(language, "field-note"),
+            tr!(language, "format-discord-note"),
+            false,
+        ),
+        _ => embed,
+    }
+}
@@ -0,0 +1,82 @@
+use tokio::task;
+
+use crate::{
+    commands::Context,
+    error::{Error, Result},
+    locale::{Language, tr},
+    reply,
+    success,
+};
+
+/// Show whether birthdays found in the same hourly check are announced together.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn get(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let grouped = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "select grouped from groupings where guild_id = ?1";
+        let grouped = conn
+            .prepare(query)?
+            // NOTE: See the note in `birthday::get`.
+            .query((guild_id.get() as i64,))?
+            .next()?
+            .map(|row| row.get(0))
+            .transpose()?
+            .unwrap_or(false);
+        Ok::<_, Error>(grouped)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "grouping-retrieved")).description(if grouped {
+        tr!(language, "grouping-retrieved-grouped")
+    } else {
+        tr!(language, "grouping-retrieved-separate")
+    });
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Update whether birthdays found in the same hourly check are announced together.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Whether to announce birthdays found in the same hourly check in a single \
+                     message."]
+    grouped: bool,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into groupings (guild_id, grouped) values (?1, ?2) on conflict \
+                     (guild_id) do update set grouped = excluded.grouped";
+        // NOTE: See the note in `birthday::get`.
+        conn.execute(query, (guild_id.get() as i64, grouped))?;
+        Ok::<_, Error>(())
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "grouping-updated")).description(if grouped {
+        tr!(language, "grouping-updated-grouped")
+    } else {
+        tr!(language, "grouping-updated-separate")
+    });
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
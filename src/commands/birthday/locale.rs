@@ -0,0 +1,131 @@
+use poise::ChoiceParameter;
+
+use tokio::task;
+
+use tracing::warn;
+
+use crate::{
+    commands::Context,
+    error::{Error, Result},
+    locale::{self, Language, tr},
+    neutral,
+    reply,
+    success,
+};
+
+/// Show the language that announcements are made in.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn get(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let (chosen, current) = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        Ok::<_, Error>((
+            locale::load_chosen(&conn, guild_id)?,
+            locale::load(&conn, ctx.serenity_context(), guild_id)?,
+        ))
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "locale-retrieved")).description(match chosen {
+        Some(_) => tr!(
+            language,
+            "locale-retrieved-description",
+            language = current.name(),
+        ),
+        None => tr!(
+            language,
+            "locale-retrieved-default-description",
+            language = current.name(),
+        ),
+    });
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Update or set the language that announcements are made in.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "The language to make announcements in."] language: Language,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into locales (guild_id, locale) values (?1, ?2) on conflict \
+                     (guild_id) do update set locale = excluded.locale";
+        // NOTE: See the note in `birthday::get`.
+        conn.execute(query, (guild_id.get() as i64, language))?;
+        Ok::<_, Error>(())
+    })?;
+
+    let chosen = language;
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "locale-updated")).description(tr!(
+        language,
+        "locale-updated-description",
+        language = chosen.name(),
+    ));
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Make announcements in the server's preferred language again.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn unset(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let deleted = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "delete from locales where guild_id = ?1";
+        // NOTE: See the note in `birthday::get`.
+        let affected = conn.execute(query, (guild_id.get() as i64,))?;
+
+        // NOTE: Guild IDs uniquely identify a row, so if more than 1 row was deleted then something has gone wrong.
+        if affected > 1 {
+            warn!(
+                ?guild_id,
+                "{} rows affected by `birthday locale unset`", affected,
+            );
+        }
+
+        Ok::<_, Error>(affected >= 1)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = if deleted {
+        success(&tr!(language, "locale-unset"))
+            .description(tr!(language, "locale-unset-description"))
+    } else {
+        neutral(&tr!(language, "locale-unavailable"))
+            .description(tr!(language, "locale-unavailable-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use poise::serenity_prelude as serenity;
+
+use serenity::Role;
+
+use tokio::task;
+
+use tracing::warn;
+
+use crate::{
+    commands::Context,
+    error::{Error, Result},
+    failure,
+    locale::{Language, tr},
+    milestone::{self, Milestone, Rule},
+    neutral,
+    reply,
+    success,
+};
+
+/// List the ages that are celebrated with special announcements.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn list(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let milestones = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        Ok::<_, Error>(milestone::load(&conn, guild_id)?)
+    })?;
+
+    // NOTE: Embeds can have at most 25 fields, and it's unlikely that any guild needs more milestones than that.
+    let language = Language::of(ctx);
+    let embed = match milestones.len() {
+        0 => neutral(&tr!(language, "milestones-unavailable"))
+            .description(tr!(language, "milestones-unavailable-description")),
+        n => success(&tr!(language, "milestones-retrieved")).description(tr!(
+            language,
+            "milestones-retrieved-description",
+            count = n
+        )),
+    };
+    let embed = embed.fields(milestones.iter().take(25).map(|milestone| {
+        (
+            milestone.describe(language),
+            describe(language, milestone),
+            false,
+        )
+    }));
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Add or update an age that is celebrated with special announcements.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "Which ages the milestone applies to."] rule: Rule,
+    #[description = "The exact age, or the age to celebrate multiples of. Not needed for decades."]
+    #[min = 1]
+    age: Option<u32>,
+    #[description = "The colour of the announcement, such as `#FFD700`. Defaults to the usual \
+                     colour."]
+    colour: Option<String>,
+    #[description = "The title of the announcement. Supports `{age}`. Defaults to the usual title."]
+    #[max_length = 256]
+    title: Option<String>,
+    #[description = "A role to ping in the announcement. Defaults to none."] role: Option<Role>,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let language = Language::of(ctx);
+    let Some(age) = rule.age(age) else {
+        let embed = failure(&tr!(language, "milestone-invalid"))
+            .description(tr!(language, "milestone-invalid-age-description"));
+        ctx.send(reply(embed)).await?;
+        return Ok(());
+    };
+
+    let colour = match colour.as_deref().map(milestone::parse_colour) {
+        None => None,
+        Some(Some(colour)) => Some(colour),
+        Some(None) => {
+            let embed = failure(&tr!(language, "milestone-invalid"))
+                .description(tr!(language, "milestone-invalid-colour-description"));
+            ctx.send(reply(embed)).await?;
+            return Ok(());
+        },
+    };
+
+    let milestone = Milestone {
+        rule,
+        age,
+        colour,
+        title,
+        role_id: role.map(|role| role.id),
+    };
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into milestones (guild_id, rule, age, colour, title, role_id) values \
+                     (?1, ?2, ?3, ?4, ?5, ?6) on conflict (guild_id, rule, age) do update set \
+                     colour = excluded.colour, title = excluded.title, role_id = excluded.role_id";
+        conn.execute(
+            query,
+            (
+                // NOTE: See the note in `birthday::get`.
+                guild_id.get() as i64,
+                milestone.rule,
+                milestone.age,
+                milestone.colour.map(|colour| colour.0),
+                &milestone.title,
+                milestone.role_id.map(|id| id.get() as i64),
+            ),
+        )?;
+        Ok::<_, Error>(())
+    })?;
+
+    let embed = success(&tr!(language, "milestone-updated"))
+        .description(tr!(language, "milestone-updated-description"))
+        .field(
+            milestone.describe(language),
+            describe(language, &milestone),
+            false,
+        );
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Remove an age that is celebrated with special announcements.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Which ages the milestone applies to."] rule: Rule,
+    #[description = "The exact age, or the age to celebrate multiples of. Not needed for decades."]
+    #[min = 1]
+    age: Option<u32>,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let language = Language::of(ctx);
+    let Some(age) = rule.age(age) else {
+        let embed = failure(&tr!(language, "milestone-invalid"))
+            .description(tr!(language, "milestone-invalid-age-description"));
+        ctx.send(reply(embed)).await?;
+        return Ok(());
+    };
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let deleted = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "delete from milestones where guild_id = ?1 and rule = ?2 and age = ?3";
+        // NOTE: See the note in `birthday::get`.
+        let affected = conn.execute(query, (guild_id.get() as i64, rule, age))?;
+
+        // NOTE: Guild IDs, rules, and ages together uniquely identify a row, so if more than 1 row was deleted then
+        //       something has gone wrong.
+        if affected > 1 {
+            warn!(
+                ?guild_id,
+                ?rule,
+                age,
+                "{} rows affected by `birthday milestone remove`",
+                affected,
+            );
+        }
+
+        Ok::<_, Error>(affected >= 1)
+    })?;
+
+    let embed = if deleted {
+        success(&tr!(language, "milestone-removed"))
+            .description(tr!(language, "milestone-removed-description"))
+    } else {
+        neutral(&tr!(language, "milestone-unavailable"))
+            .description(tr!(language, "milestone-unavailable-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+fn describe(language: Language, milestone: &Milestone) -> String {
+    let colour = milestone
+        .colour
+        .map(|colour| format!("`#{}`", colour.hex()))
+        .unwrap_or_else(|| tr!(language, "default"));
+    let title = milestone
+        .title
+        .clone()
+        .unwrap_or_else(|| tr!(language, "default"));
+    let role = milestone
+        .role_id
+        .map(|role_id| format!("<@&{}>", role_id))
+        .unwrap_or_else(|| tr!(language, "none"));
+    tr!(
+        language,
+        "milestone-details",
+        colour = colour,
+        title = title,
+        role = role,
+    )
+}
@@ -0,0 +1,185 @@
+use poise::serenity_prelude as serenity;
+
+use serenity::{CreateEmbed, Role};
+
+use tokio::task;
+
+use tracing::warn;
+
+use crate::{
+    commands::Context,
+    error::{Error, Result},
+    locale::{Language, tr},
+    neutral,
+    ping::{self, Pings},
+    reply,
+    success,
+};
+
+/// Show who gets pinged when birthdays are announced.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn get(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let pings = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        Ok::<_, Error>(Pings::load(&conn, guild_id)?)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "ping-retrieved"))
+        .description(tr!(language, "ping-retrieved-description"));
+
+    ctx.send(reply(fields(language, embed, &pings))).await?;
+
+    Ok(())
+}
+
+/// Update or set who gets pinged when birthdays are announced.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Whether to ping the members whose birthday it is. Defaults to true."]
+    celebrants: Option<bool>,
+    #[description = "A role to ping in every announcement. Defaults to none."] role: Option<Role>,
+    #[description = "Whether to ping `@here` in every announcement. Defaults to false."]
+    here: Option<bool>,
+    #[description = "Whether the template is allowed to ping `@everyone`. Defaults to false."]
+    everyone: Option<bool>,
+    #[description = "The announcement message. Supports `{users}`, `{role}`, and `{here}`. \
+                     Defaults to mentioning all of them."]
+    #[max_length = 1000]
+    template: Option<String>,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let defaults = Pings::default();
+    let pings = Pings {
+        celebrants: celebrants.unwrap_or(defaults.celebrants),
+        role_id: role.map(|role| role.id),
+        here: here.unwrap_or(defaults.here),
+        everyone: everyone.unwrap_or(defaults.everyone),
+        template,
+    };
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into pings (guild_id, celebrants, role_id, here, everyone, template) \
+                     values (?1, ?2, ?3, ?4, ?5, ?6) on conflict (guild_id) do update set \
+                     celebrants = excluded.celebrants, role_id = excluded.role_id, here = \
+                     excluded.here, everyone = excluded.everyone, template = excluded.template";
+        conn.execute(
+            query,
+            (
+                // NOTE: See the note in `birthday::get`.
+                guild_id.get() as i64,
+                pings.celebrants,
+                pings.role_id.map(|id| id.get() as i64),
+                pings.here,
+                pings.everyone,
+                &pings.template,
+            ),
+        )?;
+        Ok::<_, Error>(())
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "ping-updated"))
+        .description(tr!(language, "ping-updated-description"));
+
+    ctx.send(reply(fields(language, embed, &pings))).await?;
+
+    Ok(())
+}
+
+/// Reset who gets pinged when birthdays are announced.
+#[poise::command(
+    slash_command,
+    guild_only,
+    ephemeral,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn unset(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let deleted = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "delete from pings where guild_id = ?1";
+        // NOTE: See the note in `birthday::get`.
+        let affected = conn.execute(query, (guild_id.get() as i64,))?;
+
+        // NOTE: Guild IDs uniquely identify a row, so if more than 1 row was deleted then something has gone wrong.
+        if affected > 1 {
+            warn!(
+                ?guild_id,
+                "{} rows affected by `birthday ping unset`", affected,
+            );
+        }
+
+        Ok::<_, Error>(affected >= 1)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = if deleted {
+        success(&tr!(language, "ping-unset")).description(tr!(language, "ping-unset-description"))
+    } else {
+        neutral(&tr!(language, "ping-unavailable"))
+            .description(tr!(language, "ping-unavailable-description"))
+    };
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+fn fields(language: Language, embed: CreateEmbed, pings: &Pings) -> CreateEmbed {
+    let yes_no = |enabled| {
+        if enabled {
+            tr!(language, "yes")
+        } else {
+            tr!(language, "no")
+        }
+    };
+    let role = pings
+        .role_id
+        .map(|role_id| format!("<@&{}>", role_id))
+        .unwrap_or_else(|| tr!(language, "none"));
+    let template = pings.template.as_deref().unwrap_or(ping::DEFAULT_TEMPLATE);
+
+    // NOTE: See the note in `on_error` about zero-width spaces.
+    embed
+        .field(
+            tr!(language, "field-celebrants"),
+            yes_no(pings.celebrants),
+            true,
+        )
+        .field(tr!(language, "field-role"), role, true)
+        .field("`@here`", yes_no(pings.here), true)
+        .field(
+            tr!(language, "field-everyone-allowed"),
+            yes_no(pings.everyone),
+            true,
+        )
+        .field(
+            tr!(language, "field-template"),
+            format!(
+                "```\n{}\n```",
+                template.replace("```", "\u{200B}`\u{200B}`\u{200B}`"),
+            ),
+            false,
+        )
+}
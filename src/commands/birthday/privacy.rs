@@ -0,0 +1,73 @@
+use tokio::task;
+
+use crate::{
+    commands::Context,
+    error::{Error, Result},
+    locale::{Language, tr},
+    privacy,
+    reply,
+    success,
+};
+
+/// Show whether your birthday can be exported outside of Discord.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn get(ctx: Context<'_>) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let user_id = ctx.author().id;
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    let exported = task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        Ok::<_, Error>(privacy::is_exported(&conn, guild_id, user_id)?)
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "privacy-retrieved")).description(if exported {
+        tr!(language, "privacy-retrieved-exported")
+    } else {
+        tr!(language, "privacy-retrieved-hidden")
+    });
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Update whether your birthday can be exported outside of Discord.
+#[poise::command(slash_command, guild_only, ephemeral)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "Whether to include your birthday when the server's birthdays are exported."]
+    exported: bool,
+) -> Result<()> {
+    // Defer response to allow time for executing the query
+    ctx.defer_ephemeral().await?;
+
+    let user_id = ctx.author().id;
+    let guild_id = ctx.guild_id().unwrap(); // PANICS: Always exists as the command is guild-only
+
+    task::block_in_place(|| {
+        let conn = ctx.data().conn.lock()?;
+        let query = "insert into privacy (guild_id, user_id, exported) values (?1, ?2, ?3) on \
+                     conflict (guild_id, user_id) do update set exported = excluded.exported";
+        // NOTE: See the note in `birthday::get`.
+        conn.execute(
+            query,
+            (guild_id.get() as i64, user_id.get() as i64, exported),
+        )?;
+        Ok::<_, Error>(())
+    })?;
+
+    let language = Language::of(ctx);
+    let embed = success(&tr!(language, "privacy-updated")).description(if exported {
+        tr!(language, "privacy-updated-exported")
+    } else {
+        tr!(language, "privacy-updated-hidden")
+    });
+
+    ctx.send(reply(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use chrono::{DateTime, Datelike, FixedOffset};
+
+use rusqlite::{Connection, functions::FunctionFlags};
+
+/// Every table in the database, in the order they are created.
+pub const TABLES: [&str; 20] = [
+    "birthdays",
+    "announcements",
+    "groupings",
+    "digests",
+    "pings",
+    "automations",
+    "messages",
+    "wishes",
+    "milestones",
+    "anniversaries",
+    "anniversary_optouts",
+    "events",
+    "privacy",
+    "feed_tokens",
+    "undelivered",
+    "outbox",
+    "locales",
+    "user_formats",
+    "guild_formats",
+    "calendars",
+];
+
+const MIGRATIONS: [&str; TABLES.len()] = [
+    include_str!("../init/create-birthdays.sql"),
+    include_str!("../init/create-announcements.sql"),
+    include_str!("../init/create-groupings.sql"),
+    include_str!("../init/create-digests.sql"),
+    include_str!("../init/create-pings.sql"),
+    include_str!("../init/create-automations.sql"),
+    include_str!("../init/create-messages.sql"),
+    include_str!("../init/create-wishes.sql"),
+    include_str!("../init/create-milestones.sql"),
+    include_str!("../init/create-anniversaries.sql"),
+    include_str!("../init/create-anniversary-optouts.sql"),
+    include_str!("../init/create-events.sql"),
+    include_str!("../init/create-privacy.sql"),
+    include_str!("../init/create-feed-tokens.sql"),
+    include_str!("../init/create-undelivered.sql"),
+    include_str!("../init/create-outbox.sql"),
+    include_str!("../init/create-locales.sql"),
+    include_str!("../init/create-user-formats.sql"),
+    include_str!("../init/create-guild-formats.sql"),
+    include_str!("../init/create-calendars.sql"),
+];
+
+/// Opens the database, creating any missing tables and registering the custom functions used in queries.
+pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Connection> {
+    prepare(Connection::open(path)?)
+}
+
+/// Opens a fresh database that only exists in memory, for tests.
+#[cfg(test)]
+pub fn open_in_memory() -> rusqlite::Result<Connection> {
+    prepare(Connection::open_in_memory()?)
+}
+
+fn prepare(conn: Connection) -> rusqlite::Result<Connection> {
+    migrate(&conn)?;
+
+    // Register custom functions used for sorting birthdays (see `birthday::list`)
+    let flags = FunctionFlags::SQLITE_DETERMINISTIC | FunctionFlags::SQLITE_INNOCUOUS;
+    conn.create_scalar_function("day", 1, flags, |ctx| {
+        let birthday = ctx.get::<DateTime<FixedOffset>>(0)?;
+        Ok(birthday.day())
+    })?;
+    conn.create_scalar_function("month", 1, flags, |ctx| {
+        let birthday = ctx.get::<DateTime<FixedOffset>>(0)?;
+        Ok(birthday.month())
+    })?;
+
+    Ok(conn)
+}
+
+/// Creates any missing tables.
+///
+/// Every migration only creates tables if they don't already exist, so this is safe to run on existing databases.
+pub fn migrate(conn: &Connection) -> rusqlite::Result<()> {
+    for migration in MIGRATIONS {
+        conn.prepare(migration)?.execute(())?;
+    }
+    Ok(())
+}
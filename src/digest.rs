@@ -0,0 +1,68 @@
+use std::ops::Range;
+
+use chrono::{DateTime, Datelike, FixedOffset, Months, TimeDelta, Weekday};
+
+use poise::ChoiceParameter;
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use crate::locale::{Language, tr};
+
+#[derive(Debug, ChoiceParameter, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Frequency {
+    #[name = "Daily"]
+    Daily,
+    #[name = "Weekly"]
+    Weekly,
+    #[name = "Monthly"]
+    Monthly,
+}
+
+impl Frequency {
+    /// Returns the span of time covered by a digest posted on the day starting at `today`, or `None` if no digest
+    /// is due on that day.
+    ///
+    /// Daily digests are due every day, weekly digests every Monday, and monthly digests on the 1st of every month.
+    pub fn window(self, today: DateTime<FixedOffset>) -> Option<Range<DateTime<FixedOffset>>> {
+        let end = match self {
+            Self::Daily => today + TimeDelta::days(1),
+            Self::Weekly if today.weekday() == Weekday::Mon => today + TimeDelta::weeks(1),
+            // NOTE: This only overflows for dates hundreds of thousands of years in the future.
+            Self::Monthly if today.day() == 1 => today.checked_add_months(Months::new(1))?,
+            _ => return None,
+        };
+        Some(today..end)
+    }
+
+    pub fn title(self, language: Language) -> String {
+        match self {
+            Self::Daily => tr!(language, "digest-daily"),
+            Self::Weekly => tr!(language, "digest-weekly"),
+            Self::Monthly => tr!(language, "digest-monthly"),
+        }
+    }
+}
+
+// NOTE: We store frequencies as text rather than integers so that the database stays readable (and so that
+//       re-ordering the enum variants doesn't silently change existing digests).
+impl ToSql for Frequency {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let freq = match self {
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+        };
+        Ok(freq.into())
+    }
+}
+
+impl FromSql for Frequency {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "daily" => Ok(Self::Daily),
+            "weekly" => Ok(Self::Weekly),
+            "monthly" => Ok(Self::Monthly),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
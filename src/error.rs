@@ -1,11 +1,43 @@
+use std::sync::PoisonError;
+
+use chrono::NaiveDateTime;
+
 use poise::serenity_prelude as serenity;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("SQLite error: {}", .0)]
     Sqlite(#[from] rusqlite::Error),
+    // NOTE: `serenity::Error` is rather large, so we box it to keep `Result`s small.
     #[error("Discord API error: {}", .0)]
-    Discord(#[from] serenity::Error),
+    Discord(Box<serenity::Error>),
+    #[error("I/O error: {}", .0)]
+    Io(#[from] std::io::Error),
+    #[error("JSON error: {}", .0)]
+    Json(#[from] serde_json::Error),
+    // NOTE: Data is validated before it is stored, so this only happens if the database is edited by hand or the
+    //       system clock moves backwards.
+    #[error("invalid stored data: {}", .0)]
+    InvalidData(String),
+    #[error("the database is unavailable because a previous query panicked")]
+    PoisonedLock,
+    #[error("invalid configuration: {}", .0)]
+    Config(String),
+    #[error("`{}` is ambiguous or doesn't exist in that timezone", .0)]
+    AmbiguousLocalTime(NaiveDateTime),
+}
+
+impl From<serenity::Error> for Error {
+    fn from(err: serenity::Error) -> Self {
+        Self::Discord(Box::new(err))
+    }
+}
+
+// NOTE: The guard inside the error isn't needed, and keeping it would make `Error` generic over lifetimes.
+impl<T> From<PoisonError<T>> for Error {
+    fn from(_: PoisonError<T>) -> Self {
+        Self::PoisonedLock
+    }
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -0,0 +1,49 @@
+use poise::serenity_prelude as serenity;
+
+use rusqlite::Connection;
+
+use serde::{Deserialize, Serialize};
+
+use serenity::{GuildId, UserId};
+
+use crate::{birthday::Birthday, recurrence::Calendar};
+
+/// A recurring annual date registered by a guild, such as the day it was founded.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Event {
+    pub name: String,
+    pub description: Option<String>,
+    pub user_id: Option<UserId>,
+    // NOTE: Events recur every year just like birthdays, so we reuse `Birthday` for its formatting and recurrence.
+    pub date: Birthday,
+}
+
+impl Event {
+    /// Returns how the event is referred to in lists alongside birthdays.
+    pub fn label(&self) -> String {
+        format!("**{}**", self.name)
+    }
+}
+
+/// Retrieves all of a guild's events, ordered by name.
+pub fn load(conn: &Connection, guild_id: GuildId) -> rusqlite::Result<Vec<Event>> {
+    let query =
+        "select name, description, user_id, date from events where guild_id = ?1 order by name";
+    let mut stmt = conn.prepare(query)?;
+    let mut rows = stmt.query((guild_id.get() as i64,))?; // NOTE: See the note in `birthday::get`.
+
+    let mut events = Vec::new();
+    while let Some(row) = rows.next()? {
+        events.push(Event {
+            name: row.get(0)?,
+            description: row.get(1)?,
+            // NOTE: See the note in `birthday::get`.
+            user_id: row
+                .get::<_, Option<i64>>(2)?
+                .map(|id| UserId::new(id as u64)),
+            date: row.get(3).map(|date| Birthday(date, Calendar::Gregorian))?,
+        });
+    }
+
+    Ok(events)
+}
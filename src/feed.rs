@@ -0,0 +1,36 @@
+use poise::serenity_prelude as serenity;
+
+use rusqlite::Connection;
+
+use serenity::GuildId;
+
+/// Generates a new secret token for a guild's birthday feeds, replacing any previous token.
+pub fn generate_token(conn: &Connection, guild_id: GuildId) -> rusqlite::Result<String> {
+    // NOTE: SQLite's `randomblob` uses a cryptographically secure random number generator, so we don't need to pull
+    //       in another dependency just to generate tokens.
+    let query = "insert into feed_tokens (guild_id, token) values (?1, \
+                 lower(hex(randomblob(32)))) on conflict (guild_id) do update set token = \
+                 excluded.token returning token";
+    // NOTE: See the note in `birthday::get`.
+    conn.query_row(query, (guild_id.get() as i64,), |row| row.get(0))
+}
+
+/// Revokes a guild's feed token, returning whether it had one.
+pub fn revoke_token(conn: &Connection, guild_id: GuildId) -> rusqlite::Result<bool> {
+    let query = "delete from feed_tokens where guild_id = ?1";
+    // NOTE: See the note in `birthday::get`.
+    let affected = conn.execute(query, (guild_id.get() as i64,))?;
+    Ok(affected >= 1)
+}
+
+/// Checks whether a token grants access to a guild's birthday feeds.
+pub fn authorize(conn: &Connection, guild_id: GuildId, token: &str) -> rusqlite::Result<bool> {
+    let query = "select 1 from feed_tokens where guild_id = ?1 and token = ?2";
+    let authorized = conn
+        .prepare(query)?
+        // NOTE: See the note in `birthday::get`.
+        .query((guild_id.get() as i64, token))?
+        .next()?
+        .is_some();
+    Ok(authorized)
+}
@@ -0,0 +1,296 @@
+use chrono::{DateTime, Datelike, FixedOffset, Timelike};
+
+use poise::{ChoiceParameter, serenity_prelude as serenity};
+
+use rusqlite::{
+    Connection,
+    types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef},
+};
+
+use serenity::{GuildId, UserId};
+
+use crate::{birthday, locale::Language};
+
+/// The order that the parts of a date are written in.
+#[derive(Debug, ChoiceParameter, Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum DateStyle {
+    #[default]
+    #[name = "Day Month Year"]
+    Dmy,
+    #[name = "Month Day Year"]
+    Mdy,
+    #[name = "ISO 8601"]
+    Iso,
+    #[name = "Discord timestamp"]
+    Discord,
+}
+
+/// Whether times are written with 24 or 12 hours.
+#[derive(Debug, ChoiceParameter, Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Clock {
+    #[default]
+    #[name = "24-hour"]
+    TwentyFour,
+    #[name = "12-hour"]
+    Twelve,
+}
+
+/// How dates and times are displayed to a user, or in a guild's announcements.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Format {
+    pub date: DateStyle,
+    pub clock: Clock,
+    pub offset: bool,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            date: DateStyle::default(),
+            clock: Clock::default(),
+            offset: true,
+        }
+    }
+}
+
+impl Format {
+    /// Formats a date and time as plain text, such as for code blocks and exports.
+    ///
+    /// Times at midnight are left out, since most birthdays and events don't have a time. Discord timestamps can't
+    /// be written as plain text, so they are written as day-month-year instead.
+    pub fn text(self, language: Language, datetime: DateTime<FixedOffset>) -> String {
+        let month = birthday::month_name(language, datetime.month());
+        let mut text = match self.date {
+            DateStyle::Dmy | DateStyle::Discord => {
+                format!(
+                    "{} {} {}",
+                    datetime.format("%d"),
+                    month,
+                    datetime.format("%Y")
+                )
+            },
+            DateStyle::Mdy => format!("{} {}", month, datetime.format("%d, %Y")),
+            DateStyle::Iso => datetime.format("%Y-%m-%d").to_string(),
+        };
+
+        if datetime.hour() != 0 || datetime.minute() != 0 || datetime.second() != 0 {
+            let time = match self.clock {
+                Clock::TwentyFour => "%H:%M:%S",
+                Clock::Twelve => "%I:%M:%S %p",
+            };
+            text.push(' ');
+            text.push_str(&datetime.format(time).to_string());
+        }
+
+        if self.offset {
+            text.push_str(&datetime.format(" (UTC%:z)").to_string());
+        }
+
+        text
+    }
+
+    /// Formats a date and time as Discord markdown, ready to be included in a message.
+    ///
+    /// Discord timestamps are shown in each viewer's own locale and timezone, so the clock and offset preferences
+    /// don't apply to them.
+    pub fn markdown(self, language: Language, datetime: DateTime<FixedOffset>) -> String {
+        match self.date {
+            // NOTE: Timestamps are converted to the viewer's timezone, so a birthday at midnight can appear on the
+            //       previous or next day for viewers far enough from its offset. We only show the time when there
+            //       is one, since that's the only case where the exact instant is meaningful.
+            DateStyle::Discord => {
+                let style = if datetime.num_seconds_from_midnight() == 0 {
+                    'D'
+                } else {
+                    'F'
+                };
+                format!("<t:{}:{}>", datetime.timestamp(), style)
+            },
+            _ => format!("`{}`", self.text(language, datetime)),
+        }
+    }
+}
+
+// NOTE: We store date styles and clocks as text for the same reasons as `digest::Frequency`.
+impl ToSql for DateStyle {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let style = match self {
+            Self::Dmy => "dmy",
+            Self::Mdy => "mdy",
+            Self::Iso => "iso",
+            Self::Discord => "discord",
+        };
+        Ok(style.into())
+    }
+}
+
+impl FromSql for DateStyle {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "dmy" => Ok(Self::Dmy),
+            "mdy" => Ok(Self::Mdy),
+            "iso" => Ok(Self::Iso),
+            "discord" => Ok(Self::Discord),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+impl ToSql for Clock {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let clock = match self {
+            Self::TwentyFour => "24h",
+            Self::Twelve => "12h",
+        };
+        Ok(clock.into())
+    }
+}
+
+impl FromSql for Clock {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "24h" => Ok(Self::TwentyFour),
+            "12h" => Ok(Self::Twelve),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// Retrieves the format that a user chose, if any.
+///
+/// Users choose a single format that applies in every guild.
+pub fn load_user(conn: &Connection, user_id: UserId) -> rusqlite::Result<Option<Format>> {
+    let query = "select date, clock, show_offset from user_formats where user_id = ?1";
+    conn.prepare(query)?
+        // NOTE: See the note in `birthday::get`.
+        .query((user_id.get() as i64,))?
+        .next()?
+        .map(|row| {
+            Ok(Format {
+                date: row.get(0)?,
+                clock: row.get(1)?,
+                offset: row.get(2)?,
+            })
+        })
+        .transpose()
+}
+
+/// Retrieves the format that a guild chose for its announcements, if any.
+pub fn load_guild(conn: &Connection, guild_id: GuildId) -> rusqlite::Result<Option<Format>> {
+    let query = "select date, clock, show_offset from guild_formats where guild_id = ?1";
+    conn.prepare(query)?
+        // NOTE: See the note in `birthday::get`.
+        .query((guild_id.get() as i64,))?
+        .next()?
+        .map(|row| {
+            Ok(Format {
+                date: row.get(0)?,
+                clock: row.get(1)?,
+                offset: row.get(2)?,
+            })
+        })
+        .transpose()
+}
+
+/// Retrieves the format that dates are shown to a user in.
+///
+/// Users who haven't chosen a format see the guild's format, or the default format if the guild hasn't chosen one
+/// either.
+pub fn load(conn: &Connection, guild_id: GuildId, user_id: UserId) -> rusqlite::Result<Format> {
+    match load_user(conn, user_id)? {
+        Some(format) => Ok(format),
+        None => Ok(load_guild(conn, guild_id)?.unwrap_or_default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::db;
+
+    fn at(rfc3339: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap()
+    }
+
+    fn format(date: DateStyle, clock: Clock, offset: bool) -> Format {
+        Format {
+            date,
+            clock,
+            offset,
+        }
+    }
+
+    #[test]
+    fn dates_are_written_in_the_chosen_order() {
+        let date = at("1998-03-14T00:00:00+05:30");
+        let cases = [
+            (DateStyle::Dmy, "14 March 1998 (UTC+05:30)"),
+            (DateStyle::Mdy, "March 14, 1998 (UTC+05:30)"),
+            (DateStyle::Iso, "1998-03-14 (UTC+05:30)"),
+        ];
+        for (style, expected) in cases {
+            let text = format(style, Clock::TwentyFour, true).text(Language::English, date);
+            assert_eq!(text, expected);
+        }
+    }
+
+    #[test]
+    fn times_follow_the_clock() {
+        let date = at("1998-03-14T22:30:05Z");
+        let twenty_four = format(DateStyle::Iso, Clock::TwentyFour, false);
+        let twelve = format(DateStyle::Iso, Clock::Twelve, false);
+        assert_eq!(
+            twenty_four.text(Language::English, date),
+            "1998-03-14 22:30:05"
+        );
+        assert_eq!(
+            twelve.text(Language::English, date),
+            "1998-03-14 10:30:05 PM"
+        );
+    }
+
+    #[test]
+    fn default_matches_the_original_format() {
+        let date = at("2000-03-05T08:30:00+01:00");
+        assert_eq!(
+            Format::default().text(Language::English, date),
+            "05 March 2000 08:30:00 (UTC+01:00)",
+        );
+    }
+
+    #[test]
+    fn discord_timestamps_use_markup() {
+        let discord = format(DateStyle::Discord, Clock::TwentyFour, true);
+        let date = at("2000-01-01T00:00:00Z");
+        assert_eq!(discord.markdown(Language::English, date), "<t:946684800:D>");
+        let time = at("2000-01-01T12:00:00Z");
+        assert_eq!(discord.markdown(Language::English, time), "<t:946728000:F>");
+
+        let iso = format(DateStyle::Iso, Clock::TwentyFour, false);
+        assert_eq!(iso.markdown(Language::English, date), "`2000-01-01`");
+    }
+
+    #[test]
+    fn users_override_guilds() {
+        let conn = db::open_in_memory().unwrap();
+        let guild_id = GuildId::new(1);
+        let user_id = UserId::new(2);
+        assert_eq!(load(&conn, guild_id, user_id).unwrap(), Format::default());
+
+        let query = "insert into guild_formats (guild_id, date, clock, show_offset) values (?1, \
+                     ?2, ?3, ?4)";
+        conn.execute(query, (1, DateStyle::Mdy, Clock::Twelve, false))
+            .unwrap();
+        let guild = format(DateStyle::Mdy, Clock::Twelve, false);
+        assert_eq!(load(&conn, guild_id, user_id).unwrap(), guild);
+
+        let query =
+            "insert into user_formats (user_id, date, clock, show_offset) values (?1, ?2, ?3, ?4)";
+        conn.execute(query, (2, DateStyle::Discord, Clock::TwentyFour, true))
+            .unwrap();
+        let user = format(DateStyle::Discord, Clock::TwentyFour, true);
+        assert_eq!(load(&conn, guild_id, user_id).unwrap(), user);
+        assert_eq!(load(&conn, guild_id, UserId::new(3)).unwrap(), guild);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{fmt::Debug, net::SocketAddr};
+
+use axum::{
+    Json,
+    Router,
+    extract::{self, Path, Query},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+
+use poise::serenity_prelude as serenity;
+
+use serde::{Deserialize, Serialize};
+
+use serenity::{Context, GuildId, UserId};
+
+use tokio::{net::TcpListener, task};
+
+use tracing::error;
+
+use crate::{
+    birthday::Birthday,
+    calendar,
+    error::Error,
+    feed,
+    locale,
+    privacy,
+    recurrence::Calendar,
+    shutdown::Phase,
+    state::State,
+};
+
+const DEFAULT_UPCOMING_LIMIT: usize = 10;
+
+#[derive(Debug, Clone)]
+struct App {
+    ctx: Context,
+    data: State,
+}
+
+/// Serves read-only birthday feeds for guilds that have generated a feed token.
+#[tracing::instrument]
+pub async fn serve(ctx: Context, data: State, address: SocketAddr) {
+    let shutdown = data.shutdown.clone();
+    let router = Router::new()
+        .route("/guilds/{guild_id}/calendar.ics", get(calendar))
+        .route("/guilds/{guild_id}/birthdays", get(birthdays))
+        .route("/guilds/{guild_id}/upcoming", get(upcoming))
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/metrics", get(metrics))
+        .with_state(App { ctx, data });
+
+    let listener = match TcpListener::bind(address).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            error!(?err, "failed to bind HTTP server to {}", address);
+            return;
+        },
+    };
+
+    // NOTE: The server stops accepting requests once shutting down, but still finishes responding to current ones.
+    let server = axum::serve(listener, router)
+        .with_graceful_shutdown(async move { shutdown.reached(Phase::Draining).await });
+    if let Err(err) = server.await {
+        error!(?err, "HTTP server stopped unexpectedly");
+    }
+}
+
+// NOTE: Calendar apps can't send headers when subscribing to a feed, so tokens can also be passed as a query
+//       parameter.
+#[derive(Debug, Deserialize)]
+struct Params {
+    token: Option<String>,
+    limit: Option<usize>,
+}
+
+// NOTE: Discord IDs don't fit in JavaScript's safe integer range, so we serialize them as strings.
+#[derive(Debug, Serialize)]
+struct BirthdayEntry {
+    user_id: String,
+    name: String,
+    birthday: String,
+    calendar: Calendar,
+    age: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct UpcomingEntry {
+    user_id: String,
+    name: String,
+    birthday: String,
+    calendar: Calendar,
+    next: String,
+    age: u32,
+}
+
+// NOTE: The bot is healthy as long as it can respond at all.
+async fn healthz() -> &'static str {
+    "ok"
+}
+
+async fn readyz(extract::State(app): extract::State<App>) -> (StatusCode, &'static str) {
+    if !app.data.metrics.gateway_connected() {
+        return (StatusCode::SERVICE_UNAVAILABLE, "gateway disconnected");
+    }
+
+    let reachable = task::block_in_place(|| {
+        let conn = app.data.conn.lock()?;
+        conn.query_row("select 1", (), |_| Ok(()))?;
+        Ok::<_, Error>(())
+    });
+    match reachable {
+        Ok(()) => (StatusCode::OK, "ok"),
+        Err(err) => {
+            error!(?err, "failed to reach database");
+            (StatusCode::SERVICE_UNAVAILABLE, "database unreachable")
+        },
+    }
+}
+
+async fn metrics(extract::State(app): extract::State<App>) -> Response {
+    let text = app.data.metrics.render();
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], text).into_response()
+}
+
+async fn calendar(
+    extract::State(app): extract::State<App>,
+    Path(guild_id): Path<u64>,
+    Query(params): Query<Params>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let guild_id = authorize(&app.data, guild_id, &params, &headers)?;
+    let birthdays = exported_birthdays(&app, guild_id).await?;
+
+    // NOTE: Feeds aren't requested from Discord, so they're in the same language as the guild's announcements.
+    let language = task::block_in_place(|| {
+        let conn = app.data.conn.lock()?;
+        Ok::<_, Error>(locale::load(&conn, &app.ctx, guild_id)?)
+    })
+    .map_err(internal)?;
+
+    let guild_name = guild_id
+        .name(&app.ctx)
+        .unwrap_or_else(|| locale::tr!(language, "the-server"));
+    let now = app.data.clock.now().fixed_offset();
+    let ics = calendar::render(language, guild_id, &guild_name, &birthdays, now);
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        ics,
+    )
+        .into_response())
+}
+
+async fn birthdays(
+    extract::State(app): extract::State<App>,
+    Path(guild_id): Path<u64>,
+    Query(params): Query<Params>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<BirthdayEntry>>, StatusCode> {
+    let guild_id = authorize(&app.data, guild_id, &params, &headers)?;
+    let birthdays = exported_birthdays(&app, guild_id).await?;
+
+    let now = app.data.clock.now().fixed_offset();
+    // NOTE: A single bad row shouldn't fail the whole feed, so we skip it instead.
+    let entries = birthdays
+        .into_iter()
+        .filter_map(|(user_id, name, birthday)| {
+            let age = skip_invalid(guild_id, user_id, birthday.age(now))?;
+            Some(BirthdayEntry {
+                user_id: user_id.to_string(),
+                name,
+                birthday: birthday.0.to_rfc3339(),
+                calendar: birthday.1,
+                age,
+            })
+        })
+        .collect();
+
+    Ok(Json(entries))
+}
+
+async fn upcoming(
+    extract::State(app): extract::State<App>,
+    Path(guild_id): Path<u64>,
+    Query(params): Query<Params>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<UpcomingEntry>>, StatusCode> {
+    let guild_id = authorize(&app.data, guild_id, &params, &headers)?;
+    let birthdays = exported_birthdays(&app, guild_id).await?;
+
+    let now = app.data.clock.now().fixed_offset();
+    // NOTE: See the note in `birthdays`.
+    let mut birthdays = birthdays
+        .into_iter()
+        .filter_map(|(user_id, name, birthday)| {
+            let next = skip_invalid(guild_id, user_id, birthday.next_occurrence(now))?;
+            Some((next, user_id, name, birthday))
+        })
+        .collect::<Vec<_>>();
+    birthdays.sort_by_key(|&(next, ..)| next);
+
+    let limit = params.limit.unwrap_or(DEFAULT_UPCOMING_LIMIT);
+    let entries = birthdays
+        .into_iter()
+        .take(limit)
+        .filter_map(|(next, user_id, name, birthday)| {
+            let age = skip_invalid(guild_id, user_id, birthday.age(next))?;
+            Some(UpcomingEntry {
+                user_id: user_id.to_string(),
+                name,
+                birthday: birthday.0.to_rfc3339(),
+                calendar: birthday.1,
+                next: next.to_rfc3339(),
+                age,
+            })
+        })
+        .collect();
+
+    Ok(Json(entries))
+}
+
+fn authorize(
+    data: &State,
+    guild_id: u64,
+    params: &Params,
+    headers: &HeaderMap,
+) -> Result<GuildId, StatusCode> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let token = bearer.or(params.token.as_deref());
+
+    // NOTE: We respond the same way to unknown guilds and invalid tokens so that guild IDs can't be probed.
+    let (Some(token), true) = (token, guild_id != 0) else {
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+    let guild_id = GuildId::new(guild_id);
+
+    let authorized = task::block_in_place(|| {
+        let conn = data.conn.lock()?;
+        Ok::<_, Error>(feed::authorize(&conn, guild_id, token)?)
+    })
+    .map_err(internal)?;
+
+    if authorized {
+        Ok(guild_id)
+    } else {
+        Err(StatusCode::UNAUTHORIZED)
+    }
+}
+
+async fn exported_birthdays(
+    app: &App,
+    guild_id: GuildId,
+) -> Result<Vec<(UserId, String, Birthday)>, StatusCode> {
+    let birthdays = task::block_in_place(|| {
+        let conn = app.data.conn.lock()?;
+        Ok::<_, Error>(privacy::exported_birthdays(&conn, guild_id)?)
+    })
+    .map_err(internal)?;
+
+    privacy::with_names(&app.ctx, guild_id, birthdays)
+        .await
+        .map_err(internal)
+}
+
+fn skip_invalid<T>(guild_id: GuildId, user_id: UserId, result: Result<T, Error>) -> Option<T> {
+    result
+        .inspect_err(|err| error!(?err, ?guild_id, "skipping invalid birthday of {}", user_id))
+        .ok()
+}
+
+fn internal(err: impl Debug) -> StatusCode {
+    error!(?err, "failed to handle HTTP request");
+    StatusCode::INTERNAL_SERVER_ERROR
+}
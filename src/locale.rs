@@ -0,0 +1,406 @@
+use std::sync::LazyLock;
+
+use fluent_bundle::{FluentArgs, FluentResource, concurrent::FluentBundle};
+
+use poise::{ChoiceParameter, serenity_prelude as serenity};
+
+use rusqlite::{
+    Connection,
+    types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef},
+};
+
+use serenity::{Cache, GuildId};
+
+use tracing::warn;
+
+use unic_langid::LanguageIdentifier;
+
+static BUNDLES: LazyLock<Vec<FluentBundle<FluentResource>>> =
+    LazyLock::new(|| Language::ALL.into_iter().map(Language::bundle).collect());
+
+/// A language that responses and announcements can be translated into.
+#[derive(Debug, ChoiceParameter, Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Language {
+    #[default]
+    #[name = "English"]
+    English,
+    #[name = "Deutsch"]
+    German,
+}
+
+impl Language {
+    /// Every supported language, in the same order as their bundles.
+    pub const ALL: [Self; 2] = [Self::English, Self::German];
+
+    /// Returns the Discord locale that the language is used for.
+    ///
+    /// Discord has separate locales for some variants of a language (such as `en-US` and `en-GB`), in which case
+    /// this is the one that the translations are written in.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en-US",
+            Self::German => "de",
+        }
+    }
+
+    /// Returns the language used for a Discord locale, falling back to English for unsupported locales.
+    pub fn from_locale(locale: &str) -> Self {
+        match locale.split('-').next() {
+            Some("de") => Self::German,
+            _ => Self::English,
+        }
+    }
+
+    /// Returns the language of the user who invoked a command.
+    pub fn of<U, E>(ctx: poise::Context<'_, U, E>) -> Self {
+        ctx.locale().map(Self::from_locale).unwrap_or_default()
+    }
+
+    /// Formats a message, falling back to English if it hasn't been translated.
+    ///
+    /// Prefer the [`tr!`] macro, which builds the arguments.
+    pub fn get(self, id: &str, args: Option<&FluentArgs<'_>>) -> String {
+        self.lookup(id, None, args)
+            .or_else(|| Self::English.lookup(id, None, args))
+            .unwrap_or_else(|| {
+                warn!("missing message `{}`", id);
+                id.to_owned()
+            })
+    }
+
+    /// Formats an attribute of a message, falling back to English if it hasn't been translated.
+    ///
+    /// Unlike [`Language::get`], this returns `None` if the attribute doesn't exist, since attributes are often
+    /// optional.
+    pub fn attribute(self, id: &str, attribute: &str) -> Option<String> {
+        self.lookup(id, Some(attribute), None)
+            .or_else(|| Self::English.lookup(id, Some(attribute), None))
+    }
+
+    /// Returns the translated name of a choice, given its English name.
+    pub fn choice(self, name: &str) -> String {
+        self.lookup(&choice_id(name), None, None)
+            .unwrap_or_else(|| name.to_owned())
+    }
+
+    fn lookup(
+        self,
+        id: &str,
+        attribute: Option<&str>,
+        args: Option<&FluentArgs<'_>>,
+    ) -> Option<String> {
+        let bundle = &BUNDLES[self as usize];
+        let message = bundle.get_message(id)?;
+        let pattern = match attribute {
+            Some(attribute) => message.get_attribute(attribute)?.value(),
+            None => message.value()?,
+        };
+
+        let mut errors = Vec::new();
+        let formatted = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            warn!(
+                ?errors,
+                "failed to format message `{}` in {}",
+                id,
+                self.code()
+            );
+        }
+        Some(formatted.into_owned())
+    }
+
+    fn bundle(self) -> FluentBundle<FluentResource> {
+        let source = match self {
+            Self::English => include_str!("../locales/en-US.ftl"),
+            Self::German => include_str!("../locales/de.ftl"),
+        };
+
+        // PANICS: The resources are checked by the tests below, so they always parse.
+        let resource = FluentResource::try_new(source.to_owned()).unwrap();
+        let id = self.code().parse::<LanguageIdentifier>().unwrap();
+
+        let mut bundle = FluentBundle::new_concurrent(vec![id]);
+        // NOTE: Fluent wraps arguments in invisible Unicode isolation marks by default, which Discord doesn't strip.
+        //       They would end up inside mentions and code blocks and break them, so we turn them off.
+        bundle.set_use_isolating(false);
+        // PANICS: See above.
+        bundle.add_resource(resource).unwrap();
+        bundle
+    }
+}
+
+impl ToSql for Language {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(self.code().into())
+    }
+}
+
+impl FromSql for Language {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == value.as_str().unwrap_or_default())
+            .ok_or(FromSqlError::InvalidType)
+    }
+}
+
+/// Formats a message in a [`Language`], with optional named arguments.
+///
+/// ```ignore
+/// tr!(language, "birthday-get-own", birthday = birthday.localized(language))
+/// ```
+macro_rules! tr {
+    ($language:expr, $id:literal $(,)?) => {
+        $language.get($id, None)
+    };
+    ($language:expr, $id:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $language.get($id, Some(&args))
+    }};
+}
+
+pub(crate) use tr;
+
+/// Retrieves the language that a guild chose for its announcements, if any.
+pub fn load_chosen(conn: &Connection, guild_id: GuildId) -> rusqlite::Result<Option<Language>> {
+    let query = "select locale from locales where guild_id = ?1";
+    conn.prepare(query)?
+        // NOTE: See the note in `birthday::get`.
+        .query((guild_id.get() as i64,))?
+        .next()?
+        .map(|row| row.get(0))
+        .transpose()
+}
+
+/// Retrieves the language that a guild's announcements are made in.
+///
+/// Guilds that haven't chosen a language use their community's preferred locale if it's supported, and English
+/// otherwise.
+pub fn load(
+    conn: &Connection,
+    cache: impl AsRef<Cache>,
+    guild_id: GuildId,
+) -> rusqlite::Result<Language> {
+    let language = load_chosen(conn, guild_id)?.unwrap_or_else(|| {
+        guild_id
+            .to_guild_cached(&cache)
+            .map(|guild| Language::from_locale(&guild.preferred_locale))
+            .unwrap_or_default()
+    });
+    Ok(language)
+}
+
+/// Adds the translations of every command's name and description (and those of their parameters and choices) so
+/// that Discord shows them in each user's language.
+///
+/// English is written in the commands' doc comments and attributes, so only the other languages are added here.
+pub fn localize_commands<U, E>(commands: &mut [poise::Command<U, E>]) {
+    for language in Language::ALL {
+        if language != Language::English {
+            localize(language, "cmd", commands);
+        }
+    }
+}
+
+fn localize<U, E>(language: Language, parent: &str, commands: &mut [poise::Command<U, E>]) {
+    let locale = language.code();
+    for command in commands {
+        let id = command_id(parent, &command.name);
+        if let Some(name) = language.lookup(&id, None, None) {
+            command.name_localizations.insert(locale.to_owned(), name);
+        }
+        if let Some(description) = language.lookup(&id, Some("description"), None) {
+            command
+                .description_localizations
+                .insert(locale.to_owned(), description);
+        }
+
+        // NOTE: Parameter names are suffixed since a parameter can itself be called `description`.
+        for param in &mut command.parameters {
+            let attribute = format!("{}-name", param.name);
+            if let Some(name) = language.lookup(&id, Some(&attribute), None) {
+                param.name_localizations.insert(locale.to_owned(), name);
+            }
+            let attribute = format!("{}-description", param.name);
+            if let Some(description) = language.lookup(&id, Some(&attribute), None) {
+                param
+                    .description_localizations
+                    .insert(locale.to_owned(), description);
+            }
+            for choice in &mut param.choices {
+                if let Some(name) = language.lookup(&choice_id(&choice.name), None, None) {
+                    choice.localizations.insert(locale.to_owned(), name);
+                }
+            }
+        }
+
+        localize(language, &id, &mut command.subcommands);
+    }
+}
+
+// NOTE: Commands are identified by their qualified names (such as `cmd-birthday-anniversary-opt-out`), since poise
+//       only fills in `Command::qualified_name` once the framework is built.
+fn command_id(parent: &str, name: &str) -> String {
+    format!("{}-{}", parent, name)
+}
+
+// NOTE: Choices are identified by their English names (such as `choice-every-decade`), since that's all poise
+//       keeps around once a choice parameter is built.
+fn choice_id(name: &str) -> String {
+    let slug = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    format!("choice-{}", slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use fluent_bundle::FluentResource;
+
+    use fluent_syntax::ast::Entry;
+
+    use super::*;
+
+    fn ids(language: Language) -> BTreeSet<String> {
+        let source = match language {
+            Language::English => include_str!("../locales/en-US.ftl"),
+            Language::German => include_str!("../locales/de.ftl"),
+        };
+        let resource = FluentResource::try_new(source.to_owned()).unwrap_or_else(|(_, errors)| {
+            panic!("{} failed to parse: {:?}", language.code(), errors)
+        });
+
+        let mut ids = BTreeSet::new();
+        for entry in resource.entries() {
+            if let Entry::Message(message) = entry {
+                ids.insert(message.id.name.to_owned());
+                for attribute in &message.attributes {
+                    ids.insert(format!("{}.{}", message.id.name, attribute.id.name));
+                }
+            }
+        }
+        ids
+    }
+
+    #[test]
+    fn every_language_has_the_same_messages() {
+        // NOTE: English command names and descriptions are in the commands themselves.
+        let english = ids(Language::English);
+        for language in Language::ALL {
+            let ids = ids(language)
+                .into_iter()
+                .filter(|id| !id.starts_with("cmd-"))
+                .collect::<BTreeSet<_>>();
+            assert_eq!(
+                english.difference(&ids).collect::<Vec<_>>(),
+                Vec::<&String>::new(),
+                "missing from {}",
+                language.code(),
+            );
+            assert_eq!(
+                ids.difference(&english).collect::<Vec<_>>(),
+                Vec::<&String>::new(),
+                "only in {}",
+                language.code(),
+            );
+        }
+    }
+
+    #[test]
+    fn every_command_is_localized() {
+        fn check<U, E>(language: Language, commands: &[poise::Command<U, E>]) {
+            let locale = language.code();
+            for command in commands {
+                let name = command.name_localizations.get(locale);
+                assert!(
+                    name.is_some(),
+                    "`{}` has no name in {}",
+                    command.name,
+                    locale
+                );
+                assert!(
+                    is_valid_name(name.unwrap()),
+                    "`{}` has an invalid name in {}",
+                    command.name,
+                    locale
+                );
+
+                // NOTE: Commands with subcommands don't have descriptions in Discord.
+                if command.subcommands.is_empty() {
+                    let description = command.description_localizations.get(locale);
+                    assert!(
+                        description.is_some_and(|description| is_valid_description(description))
+                    );
+                }
+
+                for param in &command.parameters {
+                    let name = param.name_localizations.get(locale);
+                    assert!(
+                        name.is_some_and(|name| is_valid_name(name)),
+                        "`{}` in `{}`",
+                        param.name,
+                        command.name
+                    );
+                    let description = param.description_localizations.get(locale);
+                    assert!(
+                        description.is_some_and(|description| is_valid_description(description)),
+                        "`{}` in `{}`",
+                        param.name,
+                        command.name,
+                    );
+                }
+
+                check(language, &command.subcommands);
+            }
+        }
+
+        let commands = crate::commands::all();
+        for language in Language::ALL {
+            if language != Language::English {
+                check(language, &commands);
+            }
+        }
+    }
+
+    // NOTE: Discord rejects names that aren't lowercase words of up to 32 characters, and descriptions longer than
+    //       100 characters.
+    fn is_valid_name(name: &str) -> bool {
+        (1..=32).contains(&name.chars().count())
+            && name
+                .chars()
+                .all(|c| c == '-' || c == '_' || c.is_lowercase() || c.is_numeric())
+    }
+
+    fn is_valid_description(description: &str) -> bool {
+        (1..=100).contains(&description.chars().count())
+    }
+
+    #[test]
+    fn arguments_are_not_isolated() {
+        let greeting = tr!(Language::English, "thread-birthday", names = "<@1>");
+        assert_eq!(greeting, "Happy birthday, <@1>!");
+    }
+
+    #[test]
+    fn untranslated_locales_fall_back_to_english() {
+        assert_eq!(Language::from_locale("de"), Language::German);
+        assert_eq!(Language::from_locale("en-GB"), Language::English);
+        assert_eq!(Language::from_locale("fr"), Language::English);
+    }
+
+    #[test]
+    fn choices_are_translated_by_their_english_name() {
+        assert_eq!(Language::German.choice("Every decade"), "Jedes Jahrzehnt");
+        assert_eq!(Language::German.choice("Not a choice"), "Not a choice");
+    }
+}
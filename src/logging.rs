@@ -0,0 +1,190 @@
+use std::{
+    fs,
+    io::{self, Write},
+};
+
+use anyhow::Context;
+
+use rolling_file::{BasicRollingFileAppender, RollingConditionBasic};
+
+use serde::Deserialize;
+
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{self, RollingFileAppender},
+};
+
+use tracing_subscriber::{
+    EnvFilter,
+    Layer as _,
+    Registry,
+    fmt::{Layer, format::FmtSpan},
+    layer::SubscriberExt,
+    reload,
+    util::SubscriberInitExt,
+};
+
+use crate::{
+    Config,
+    error::{Error, Result},
+};
+
+const FILE_NAME: &str = "birthbot.log";
+
+/// Returns the filter used when none is configured.
+///
+/// Commands and background tasks are instrumented with `INFO`-level spans, so we enable those for this crate to
+/// show which command or task a warning or error came from.
+pub fn default_level() -> String {
+    "warn,birthbot=info".to_owned()
+}
+
+/// Returns the maximum size of a log file in bytes used when none is configured, when rotating logs by size.
+pub fn default_max_size() -> u64 {
+    10 * 1024 * 1024
+}
+
+// NOTE: `rolling_file` renames every file up to its limit each time it rotates, even those that don't exist, so
+//       rotating by size needs a small limit even when no retention is configured.
+const DEFAULT_SIZE_RETENTION: usize = 10;
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Output {
+    #[default]
+    File,
+    Stdout,
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rotation {
+    #[default]
+    Daily,
+    Hourly,
+    Size,
+}
+
+/// A handle for changing which logs are recorded while the bot is running.
+pub type Filter = reload::Handle<EnvFilter, Registry>;
+
+/// Sets up logging as described by the config.
+///
+/// The returned guard flushes any remaining logs when dropped, so it must be kept alive for as long as the bot runs.
+pub fn init(config: &Config) -> anyhow::Result<(WorkerGuard, Filter)> {
+    let filter = EnvFilter::try_new(&config.log_level)
+        .with_context(|| format!("invalid `log-level`: {}", config.log_level))?;
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let (writer, guard) = match config.log_output {
+        Output::Stdout => tracing_appender::non_blocking(io::stdout()),
+        Output::File => tracing_appender::non_blocking(appender(config)?),
+    };
+
+    // NOTE: Spans are logged when they close so that commands show up along with how long they took, even when
+    //       nothing inside them was logged.
+    let layer = Layer::new()
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_span_events(FmtSpan::CLOSE);
+    let layer = match config.log_format {
+        Format::Text => layer.with_filter(filter).boxed(),
+        Format::Json => layer.json().with_filter(filter).boxed(),
+    };
+    tracing_subscriber::registry().with(layer).try_init()?;
+
+    Ok((guard, handle))
+}
+
+fn appender(config: &Config) -> anyhow::Result<Box<dyn Write + Send>> {
+    let log_dir = config
+        .log_dir
+        .as_ref()
+        .context("`log-dir` must be set when logging to a file")?;
+    match config.log_rotation {
+        Rotation::Daily | Rotation::Hourly => {
+            let rotation = match config.log_rotation {
+                Rotation::Hourly => rolling::Rotation::HOURLY,
+                _ => rolling::Rotation::DAILY,
+            };
+            // NOTE: This appender doesn't treat a limit of 0 as unlimited, but only ever lists the files that exist,
+            //       so the largest possible limit keeps all of them.
+            let appender = RollingFileAppender::builder()
+                .rotation(rotation)
+                .filename_prefix(FILE_NAME)
+                .max_log_files(config.log_retention.unwrap_or(usize::MAX))
+                .build(log_dir)?;
+            Ok(Box::new(appender))
+        },
+        Rotation::Size => {
+            // NOTE: Unlike `tracing_appender`, `rolling_file` doesn't create the log directory itself.
+            fs::create_dir_all(log_dir)?;
+            let condition = RollingConditionBasic::new().max_size(config.log_max_size);
+            let appender = BasicRollingFileAppender::new(
+                log_dir.join(FILE_NAME),
+                condition,
+                config.log_retention.unwrap_or(DEFAULT_SIZE_RETENTION),
+            )?;
+            Ok(Box::new(appender))
+        },
+    }
+}
+
+/// Replaces the log level set up by [`init`].
+pub fn set_level(filter: &Filter, level: &str) -> Result<()> {
+    let level = EnvFilter::try_new(level)
+        .map_err(|err| Error::Config(format!("invalid `log-level` {}: {}", level, err)))?;
+    filter
+        .reload(level)
+        .map_err(|err| Error::Config(format!("failed to change the log level: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use figment::{
+        Figment,
+        providers::{Format as _, Toml},
+    };
+
+    use super::*;
+
+    fn config(name: &str, toml: &str) -> Config {
+        let log_dir = env::temp_dir().join(format!("birthbot-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&log_dir);
+        let toml = format!("db = \"birthbot.db\"\nlog-dir = {:?}\n{}", log_dir, toml);
+        Figment::new().merge(Toml::string(&toml)).extract().unwrap()
+    }
+
+    #[test]
+    fn appender_builds_from_default_config() {
+        let config = config("default", "");
+        let mut appender = appender(&config).unwrap();
+        appender.write_all(b"log\n").unwrap();
+        fs::remove_dir_all(config.log_dir.unwrap()).unwrap();
+    }
+
+    // NOTE: This used to hang on the first rotation, since no retention meant renaming `usize::MAX` files.
+    #[test]
+    fn size_rotation_without_retention_rotates() {
+        let config = config("size", "log-rotation = \"size\"\nlog-max-size = 1");
+        let mut appender = appender(&config).unwrap();
+        for _ in 0..3 {
+            appender.write_all(b"log\n").unwrap();
+            appender.flush().unwrap();
+        }
+
+        let log_dir = config.log_dir.unwrap();
+        assert!(fs::read_dir(&log_dir).unwrap().count() > 1);
+        fs::remove_dir_all(log_dir).unwrap();
+    }
+}
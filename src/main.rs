@@ -2,12 +2,15 @@
 
 use std::{
     fs,
-    mem,
+    num::NonZeroU64,
     path::PathBuf,
-    sync::{Arc, Mutex},
+    sync::{Arc, RwLock},
+    time::Instant,
 };
 
-use chrono::Datelike;
+use anyhow::Context as _;
+
+use clap::Parser;
 
 use figment::{
     Figment,
@@ -22,69 +25,202 @@ use poise::{
     serenity_prelude as serenity,
 };
 
-use rusqlite::{Connection, functions::FunctionFlags};
-
 use serde::Deserialize;
 
 use serenity::{
     Client,
+    ConnectionStage,
     CreateEmbed,
+    FullEvent,
     GatewayIntents,
+    GuildId,
     colours::{
         branding::{BLURPLE, FUCHSIA},
         css::{DANGER, POSITIVE},
     },
 };
 
-use tracing::{error, level_filters::LevelFilter};
+use tokio::sync::mpsc::{self, Receiver};
+
+use tracing::{error, info, warn};
 
-use tracing_appender::rolling;
+mod anniversary;
 
-use tracing_subscriber::{Layer as _, fmt::Layer, layer::SubscriberExt, util::SubscriberInitExt};
+mod automation;
+
+mod backup;
 
 mod birthday;
-use birthday::Birthday;
+
+mod calendar;
+
+mod clock;
+use clock::SystemClock;
+
+mod db;
+
+mod digest;
+
+mod event;
+
+#[cfg(feature = "http")]
+mod feed;
+
+mod format;
+
+#[cfg(feature = "http")]
+mod http;
+
+mod locale;
+use locale::{Language, tr};
+
+mod logging;
+
+mod metrics;
+use metrics::Metrics;
+
+mod milestone;
+
+mod outbox;
+
+mod parse;
+
+mod ping;
+
+mod privacy;
+
+mod recurrence;
+
+mod registration;
+
+mod shutdown;
+use shutdown::{Phase, Shutdown};
 
 mod state;
-use state::State;
+use state::{SharedConnection, State};
+
+mod undelivered;
+
+mod wish;
 
 mod error;
 use error::{Error, Result};
 
+mod cli;
+use cli::{Cli, Command};
+
 mod commands;
 
 mod background;
-use background::{birthdays::watch_birthdays, changelog::announce_updates};
+use background::{
+    backups::{Backups, watch_backups},
+    birthdays::watch_birthdays,
+    changelog::{Post, announce_updates},
+    digests::watch_digests,
+};
 
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "kebab-case")]
 struct Config {
-    token: String,
+    token: Option<String>,
     db: PathBuf,
-    log_dir: PathBuf,
+    log_dir: Option<PathBuf>,
+    #[serde(default = "logging::default_level")]
+    log_level: String,
+    #[serde(default)]
+    log_format: logging::Format,
+    #[serde(default)]
+    log_output: logging::Output,
+    #[serde(default)]
+    log_rotation: logging::Rotation,
+    #[serde(default = "logging::default_max_size")]
+    log_max_size: u64,
+    log_retention: Option<usize>,
+    backup_dir: Option<PathBuf>,
+    #[serde(default = "backup::default_interval")]
+    backup_interval: NonZeroU64,
+    #[serde(default = "backup::default_retention")]
+    backup_retention: usize,
+    #[serde(default)]
+    backup_compress: bool,
     changelog_file: Option<PathBuf>,
+    #[serde(default)]
+    members_intent: bool,
+    #[serde(default)]
+    dev_guilds: Vec<GuildId>,
+    #[serde(default)]
+    clear_stale_commands: bool,
+    #[cfg(feature = "http")]
+    http_address: Option<std::net::SocketAddr>,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let mut config = Figment::new()
-        .merge(Toml::file("birthbot.toml"))
-        .merge(Env::prefixed("BIRTHBOT_"))
-        .extract::<Config>()?;
+    let cli = Cli::parse();
+
+    let mut config = load_config()?;
+
+    // NOTE: Database commands, simulations, and registering commands don't connect to the gateway, and print their
+    //       results instead of logging them.
+    match cli.command {
+        Some(Command::Db(command)) => return cli::run_db(command, &config),
+        Some(Command::Simulate { date }) => return cli::run_simulate(date, &config),
+        Some(Command::Register { clear_stale }) => {
+            return cli::run_register(clear_stale, &config).await;
+        },
+        None => {},
+    }
 
     // Setup logging before anything else so that we can log whatever goes wrong
-    let writer = rolling::daily(&config.log_dir, "birthbot.log");
-    let layer = Layer::new()
-        .with_writer(writer)
-        .with_filter(LevelFilter::WARN);
-    tracing_subscriber::registry().with(layer).try_init()?;
+    let (_guard, log_filter) = logging::init(&config)?;
+
+    let token = config
+        .token
+        .take()
+        .context("`token` must be set to run the bot")?;
+
+    // NOTE: Anniversaries need to know when members joined, which can be read from the member cache if the privileged
+    //       `GUILD_MEMBERS` intent is requested.
+    let intents = if config.members_intent {
+        GatewayIntents::non_privileged() | GatewayIntents::GUILD_MEMBERS
+    } else {
+        GatewayIntents::non_privileged()
+    };
+
+    // NOTE: The changelog is read up front so that a bad path is reported before connecting to Discord.
+    let changelog = read_changelog(&config)?;
+
+    let conn = db::open(&config.db)?;
 
-    let token = mem::take(&mut config.token);
+    let metrics = Arc::<Metrics>::default();
+    let (updates, updates_rx) = mpsc::channel(100);
+    let data = State {
+        conn: Arc::new(SharedConnection::new(conn, metrics.clone())),
+        members_intent: config.members_intent,
+        metrics,
+        shutdown: Shutdown::default(),
+        clock: Arc::new(SystemClock),
+        started: Instant::now(),
+        scan: Arc::default(),
+        changelog: Arc::new(RwLock::new(changelog)),
+        updates,
+        log_filter,
+    };
 
     let framework = Framework::builder()
-        .setup(|ctx, _, framework| Box::pin(setup(ctx, framework, config)))
+        .setup({
+            let data = data.clone();
+            |ctx, _, framework| Box::pin(setup(ctx, framework, config, data, updates_rx))
+        })
         .options(FrameworkOptions {
-            commands: vec![commands::birthday()],
+            commands: commands::all(),
+            // NOTE: Commands are rejected while shutting down, since their replies may never be sent.
+            command_check: Some(|ctx| {
+                Box::pin(async move { Ok(ctx.data().shutdown.phase() == Phase::Running) })
+            }),
+            pre_command: |ctx| Box::pin(ctx.set_invocation_data(Instant::now())),
+            post_command: |ctx| Box::pin(record_command(ctx, true)),
+            event_handler: |_, event, _, data| Box::pin(on_event(event, data)),
             on_error: |err| {
                 Box::pin(async {
                     if let Err(err) = on_error(err).await {
@@ -96,84 +232,179 @@ async fn main() -> anyhow::Result<()> {
         })
         .build();
 
-    let mut bot = Client::builder(token, GatewayIntents::non_privileged())
-        .framework(framework)
-        .await?;
+    let mut bot = Client::builder(token, intents).framework(framework).await?;
 
-    bot.start().await?;
+    // NOTE: The shards are shut down last, once everything queued has been sent. We can't rely on that making the
+    //       client return, since shutting down does nothing if no shards have connected yet.
+    let shard_manager = bot.shard_manager.clone();
+    tokio::select! {
+        started = bot.start() => started?,
+        _ = shutdown::on_signal(data.shutdown.clone()) => shard_manager.shutdown_all().await,
+    }
+
+    // NOTE: Every background task has finished by now, so the framework should hold the only other reference to the
+    //       connection. It may not have been released yet if the shards never connected, in which case the
+    //       connection is closed when the process exits instead. Either way, every committed write is already saved.
+    drop(bot);
+    drop(shard_manager);
+    match Arc::try_unwrap(data.conn) {
+        Ok(conn) => conn.close()?,
+        Err(_) => warn!("database connection is still shared, so it will be closed on exit"),
+    }
+    info!("shut down");
 
     Ok(())
 }
 
+/// Loads the config from `birthbot.toml` and env vars.
+fn load_config() -> anyhow::Result<Config> {
+    let config = Figment::new()
+        .merge(Toml::file("birthbot.toml"))
+        // NOTE: Config keys are kebab-case, but env vars can't contain dashes, so we map underscores to dashes.
+        .merge(Env::prefixed("BIRTHBOT_").map(|key| key.as_str().replace('_', "-").into()))
+        .extract()?;
+    Ok(config)
+}
+
+/// Reads the changelog file set in the config, if any.
+fn read_changelog(config: &Config) -> Result<Option<String>> {
+    config
+        .changelog_file
+        .as_ref()
+        .map(|path| {
+            fs::read_to_string(path).map_err(|err| {
+                Error::Config(format!(
+                    "failed to read `changelog-file` at {}: {}",
+                    path.display(),
+                    err,
+                ))
+            })
+        })
+        .transpose()
+}
+
 async fn setup(
     ctx: &serenity::Context,
     framework: &Framework<State, Error>,
     config: Config,
+    data: State,
+    updates: Receiver<Post>,
 ) -> Result<State> {
     let commands = &framework.options().commands;
-    poise::builtins::register_globally(ctx, commands).await?;
-
-    let conn = Connection::open(&config.db)?;
-    conn.prepare(include_str!("../init/create-birthdays.sql"))?
-        .execute(())?;
-    conn.prepare(include_str!("../init/create-announcements.sql"))?
-        .execute(())?;
-
-    // Register custom functions used for sorting birthdays (see `birthday::list`)
-    let flags = FunctionFlags::SQLITE_DETERMINISTIC | FunctionFlags::SQLITE_INNOCUOUS;
-    conn.create_scalar_function("day", 1, flags, |ctx| {
-        let birthday = ctx.get(0).map(Birthday)?;
-        Ok(birthday.0.day())
-    })?;
-    conn.create_scalar_function("month", 1, flags, |ctx| {
-        let birthday = ctx.get(0).map(Birthday)?;
-        Ok(birthday.0.month())
-    })?;
+    registration::register(
+        &ctx.http,
+        commands,
+        &config.dev_guilds,
+        config.clear_stale_commands,
+    )
+    .await?;
+
+    let shutdown = &data.shutdown;
+    shutdown.spawn(watch_birthdays(ctx.clone(), data.clone()));
+    shutdown.spawn(watch_digests(ctx.clone(), data.clone()));
+
+    if let Some(dir) = config.backup_dir {
+        let backups = Backups {
+            db: config.db,
+            dir,
+            interval: config.backup_interval,
+            retention: config.backup_retention,
+            compress: config.backup_compress,
+        };
+        shutdown.spawn(watch_backups(backups, shutdown.clone()));
+    }
 
-    let data = State {
-        conn: Arc::new(Mutex::new(conn)),
-    };
+    #[cfg(feature = "http")]
+    if let Some(address) = config.http_address {
+        shutdown.spawn(http::serve(ctx.clone(), data.clone(), address));
+    }
 
-    tokio::spawn(watch_birthdays(ctx.clone(), data.clone()));
+    // NOTE: Changelogs that weren't delivered before the last shutdown are still posted even without a new one.
+    shutdown.spawn(announce_updates(ctx.clone(), data.clone(), updates));
 
-    if let Some(changelog_file) = config.changelog_file {
-        // PANICS: This realistically won't panic, and I don't want to add a variant to the error enum just for this
-        let changelog = fs::read_to_string(changelog_file).unwrap();
-        tokio::spawn(announce_updates(ctx.clone(), data.clone(), changelog));
+    Ok(data)
+}
+
+async fn on_event(event: &FullEvent, data: &State) -> Result<()> {
+    match event {
+        FullEvent::Ready { .. } | FullEvent::Resume { .. } => {
+            data.metrics.set_gateway_connected(true);
+        },
+        FullEvent::ShardStageUpdate { event } => {
+            let connected = event.new == ConnectionStage::Connected;
+            data.metrics.set_gateway_connected(connected);
+        },
+        _ => {},
     }
+    Ok(())
+}
 
-    Ok(data)
+async fn record_command(ctx: poise::Context<'_, State, Error>, succeeded: bool) {
+    // NOTE: The start time is only set once a command starts running, so commands that fail before then (such as
+    //       due to invalid arguments) aren't recorded.
+    let Some(started) = ctx
+        .invocation_data::<Instant>()
+        .await
+        .map(|started| *started)
+    else {
+        return;
+    };
+    let name = &ctx.command().qualified_name;
+    ctx.data()
+        .metrics
+        .record_command(name, succeeded, started.elapsed());
 }
 
 async fn on_error(err: FrameworkError<'_, State, Error>) -> Result<()> {
     match err {
         FrameworkError::Command { error, ctx, .. } => {
             error!("failed to execute command: {}", error);
-            let embed = failure("Command failed").description(format!(
-                "An error occurred while handling the command: `{}`",
-                error,
-            ));
+            record_command(ctx, false).await;
+            let language = Language::of(ctx);
+            let embed = match &error {
+                Error::InvalidData(reason) => failure(&tr!(language, "error-invalid-data"))
+                    .description(tr!(language, "error-invalid-data-description"))
+                    .field(tr!(language, "field-problem"), reason, false),
+                Error::PoisonedLock => failure(&tr!(language, "error-poisoned-lock"))
+                    .description(tr!(language, "error-poisoned-lock-description")),
+                Error::AmbiguousLocalTime(local) => failure(&tr!(language, "error-ambiguous-time"))
+                    .description(tr!(language, "error-ambiguous-time-description"))
+                    .field(
+                        tr!(language, "field-provided-time"),
+                        format!("```\n{}\n```", local),
+                        true,
+                    ),
+                Error::Config(reason) => failure(&tr!(language, "error-config"))
+                    .description(tr!(language, "error-config-description"))
+                    .field(tr!(language, "field-problem"), reason, false),
+                error => failure(&tr!(language, "error-command")).description(tr!(
+                    language,
+                    "error-command-description",
+                    error = error.to_string(),
+                )),
+            };
             ctx.send(reply(embed)).await?;
         },
         FrameworkError::CommandPanic { payload, ctx, .. } => {
             error!("command handler panicked: {:?}", payload);
-            let embed = failure("Command panicked").description(
-                "Something went wrong and the command handler panicked. This indicates a bug in \
-                 my code - please [file an issue on GitHub](<https://github.com/Carnagion/birthbot/issues>).",
-            );
+            record_command(ctx, false).await;
+            let language = Language::of(ctx);
+            let embed = failure(&tr!(language, "error-panic"))
+                .description(tr!(language, "error-panic-description"));
             ctx.send(reply(embed)).await?;
         },
         FrameworkError::ArgumentParse {
             error, input, ctx, ..
         } => {
             error!("invalid argument: {}", error);
-            let embed =
-                failure("Invalid input").description("One of the command arguments is invalid.");
+            let language = Language::of(ctx);
+            let embed = failure(&tr!(language, "error-argument"))
+                .description(tr!(language, "error-argument-description"));
             let embed = match input {
                 None => embed,
                 // NOTE: We place zero-width spaces between codefences in the input to avoid possible injection.
                 Some(arg) => embed.field(
-                    "Value provided",
+                    tr!(language, "field-provided-value"),
                     format!(
                         "```\n{}\n```",
                         arg.replace("```", "\u{200B}`\u{200B}`\u{200B}`"),
@@ -190,10 +421,9 @@ async fn on_error(err: FrameworkError<'_, State, Error>) -> Result<()> {
                 "mismatched command structure for {}: {}",
                 ctx.command.name, description,
             );
-            let embed = failure("Invalid command").description(
-                "That command structure doesn't match what I expected. This indicates that my \
-                 latest commands may not have been registered with Discord yet.",
-            );
+            let language = Language::from_locale(&ctx.interaction.locale);
+            let embed = failure(&tr!(language, "error-structure"))
+                .description(tr!(language, "error-structure-description"));
             ctx.send(reply(embed)).await?;
         },
         FrameworkError::CooldownHit {
@@ -201,9 +431,11 @@ async fn on_error(err: FrameworkError<'_, State, Error>) -> Result<()> {
             ctx,
             ..
         } => {
-            let embed = failure("Cooldown active").description(format!(
-                "You're too fast. Please wait {} before retrying.",
-                remaining_cooldown.as_secs(),
+            let language = Language::of(ctx);
+            let embed = failure(&tr!(language, "error-cooldown")).description(tr!(
+                language,
+                "error-cooldown-description",
+                seconds = remaining_cooldown.as_secs(),
             ));
             ctx.send(reply(embed)).await?;
         },
@@ -212,10 +444,11 @@ async fn on_error(err: FrameworkError<'_, State, Error>) -> Result<()> {
             ctx,
             ..
         } => {
-            let embed = failure("Unauthorised")
-                .description("I lack the necessary permissions to execute that command.")
+            let language = Language::of(ctx);
+            let embed = failure(&tr!(language, "error-unauthorised"))
+                .description(tr!(language, "error-bot-permissions-description"))
                 .field(
-                    "Missing permissions",
+                    tr!(language, "field-missing-permissions"),
                     missing_permissions.to_string(),
                     false,
                 );
@@ -226,31 +459,50 @@ async fn on_error(err: FrameworkError<'_, State, Error>) -> Result<()> {
             ctx,
             ..
         } => {
-            let embed = failure("Unauthorised")
-                .description("You lack the necessary permissions to issue that command.");
+            let language = Language::of(ctx);
+            let embed = failure(&tr!(language, "error-unauthorised"))
+                .description(tr!(language, "error-user-permissions-description"));
             let embed = match missing_permissions {
                 None => embed,
-                Some(perms) => embed.field("Missing permissions", perms.to_string(), false),
+                Some(perms) => embed.field(
+                    tr!(language, "field-missing-permissions"),
+                    perms.to_string(),
+                    false,
+                ),
             };
             ctx.send(reply(embed)).await?;
         },
+        // NOTE: The only check is whether the bot is shutting down (see `main`).
+        FrameworkError::CommandCheckFailed {
+            error: None, ctx, ..
+        } => {
+            let language = Language::of(ctx);
+            let embed = failure(&tr!(language, "error-unavailable"))
+                .description(tr!(language, "error-unavailable-description"));
+            ctx.send(reply(embed)).await?;
+        },
         FrameworkError::NotAnOwner { ctx, .. } => {
-            let embed = failure("Unauthorised").description("Only owners can use that command.");
+            let language = Language::of(ctx);
+            let embed = failure(&tr!(language, "error-unauthorised"))
+                .description(tr!(language, "error-not-an-owner-description"));
             ctx.send(reply(embed)).await?;
         },
         FrameworkError::GuildOnly { ctx, .. } => {
-            let embed = failure("Invalid context")
-                .description("You can only use that command in a guild (i.e. server).");
+            let language = Language::of(ctx);
+            let embed = failure(&tr!(language, "error-context"))
+                .description(tr!(language, "error-guild-only-description"));
             ctx.send(reply(embed)).await?;
         },
         FrameworkError::DmOnly { ctx, .. } => {
-            let embed =
-                failure("Invalid context").description("You can only use that command in DMs.");
+            let language = Language::of(ctx);
+            let embed = failure(&tr!(language, "error-context"))
+                .description(tr!(language, "error-dm-only-description"));
             ctx.send(reply(embed)).await?;
         },
         FrameworkError::NsfwOnly { ctx, .. } => {
-            let embed = failure("Invalid context")
-                .description("You can only use that command in NSFW channels (:flushed:).");
+            let language = Language::of(ctx);
+            let embed = failure(&tr!(language, "error-context"))
+                .description(tr!(language, "error-nsfw-only-description"));
             ctx.send(reply(embed)).await?;
         },
         error => error!("error: {:?}", error),
@@ -0,0 +1,212 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+#[cfg(feature = "http")]
+use std::fmt::Write;
+
+// NOTE: Bucket bounds are in seconds, and span everything from fast queries to slow Discord requests.
+const BUCKETS: [f64; 12] = [
+    0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Runtime statistics about the bot, exposed in the Prometheus text format.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    gateway_connected: AtomicBool,
+    // NOTE: Keyed by qualified command name and whether the command succeeded.
+    commands: Mutex<BTreeMap<(String, bool), Histogram>>,
+    queries: Mutex<Histogram>,
+    lock_waits: Mutex<Histogram>,
+    announcements_sent: AtomicU64,
+    announcements_failed: AtomicU64,
+    scans: Mutex<Histogram>,
+}
+
+impl Metrics {
+    pub fn set_gateway_connected(&self, connected: bool) {
+        self.gateway_connected.store(connected, Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "http")]
+    pub fn gateway_connected(&self) -> bool {
+        self.gateway_connected.load(Ordering::Relaxed)
+    }
+
+    pub fn record_command(&self, name: &str, succeeded: bool, elapsed: Duration) {
+        // PANICS: Observing a histogram never panics, so the lock can't be poisoned.
+        let mut commands = self.commands.lock().unwrap();
+        commands
+            .entry((name.to_owned(), succeeded))
+            .or_default()
+            .observe(elapsed);
+    }
+
+    pub fn record_query(&self, elapsed: Duration) {
+        // PANICS: See the note in `record_command`.
+        self.queries.lock().unwrap().observe(elapsed);
+    }
+
+    pub fn record_lock_wait(&self, elapsed: Duration) {
+        // PANICS: See the note in `record_command`.
+        self.lock_waits.lock().unwrap().observe(elapsed);
+    }
+
+    pub fn record_announcement(&self, sent: bool) {
+        let counter = if sent {
+            &self.announcements_sent
+        } else {
+            &self.announcements_failed
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_scan(&self, elapsed: Duration) {
+        // PANICS: See the note in `record_command`.
+        self.scans.lock().unwrap().observe(elapsed);
+    }
+
+    /// Renders every metric in the Prometheus text exposition format.
+    #[cfg(feature = "http")]
+    pub fn render(&self) -> String {
+        let mut text = String::new();
+
+        header(
+            &mut text,
+            "birthbot_gateway_connected",
+            "gauge",
+            "Whether the Discord gateway connection is up.",
+        );
+        writeln!(
+            &mut text,
+            "birthbot_gateway_connected {}",
+            u8::from(self.gateway_connected()),
+        )
+        .unwrap();
+
+        header(
+            &mut text,
+            "birthbot_command_duration_seconds",
+            "histogram",
+            "How long commands took to run, by command and outcome.",
+        );
+        // PANICS: See the note in `record_command`.
+        for ((name, succeeded), histogram) in self.commands.lock().unwrap().iter() {
+            let outcome = if *succeeded { "success" } else { "failure" };
+            let labels = format!("command=\"{}\",outcome=\"{}\"", name, outcome);
+            histogram.render(&mut text, "birthbot_command_duration_seconds", &labels);
+        }
+
+        header(
+            &mut text,
+            "birthbot_db_query_duration_seconds",
+            "histogram",
+            "How long the database connection was held for.",
+        );
+        // PANICS: See the note in `record_command`.
+        self.queries
+            .lock()
+            .unwrap()
+            .render(&mut text, "birthbot_db_query_duration_seconds", "");
+
+        header(
+            &mut text,
+            "birthbot_db_lock_wait_seconds",
+            "histogram",
+            "How long was spent waiting to acquire the database connection.",
+        );
+        // PANICS: See the note in `record_command`.
+        self.lock_waits
+            .lock()
+            .unwrap()
+            .render(&mut text, "birthbot_db_lock_wait_seconds", "");
+
+        header(
+            &mut text,
+            "birthbot_announcements_total",
+            "counter",
+            "How many announcements were sent or failed to be sent.",
+        );
+        writeln!(
+            &mut text,
+            "birthbot_announcements_total{{outcome=\"sent\"}} \
+             {}\nbirthbot_announcements_total{{outcome=\"failed\"}} {}",
+            self.announcements_sent.load(Ordering::Relaxed),
+            self.announcements_failed.load(Ordering::Relaxed),
+        )
+        .unwrap();
+
+        header(
+            &mut text,
+            "birthbot_scan_duration_seconds",
+            "histogram",
+            "How long scanning the database for birthdays took.",
+        );
+        // PANICS: See the note in `record_command`.
+        self.scans
+            .lock()
+            .unwrap()
+            .render(&mut text, "birthbot_scan_duration_seconds", "");
+
+        text
+    }
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+    // NOTE: Each bucket only counts its own observations, and they are summed up when rendering.
+    buckets: [u64; BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        if let Some(bucket) = BUCKETS.iter().position(|&bound| secs <= bound) {
+            self.buckets[bucket] += 1;
+        }
+        self.count += 1;
+        self.sum += secs;
+    }
+
+    #[cfg(feature = "http")]
+    fn render(&self, text: &mut String, name: &str, labels: &str) {
+        let separator = if labels.is_empty() { "" } else { "," };
+
+        let mut cumulative = 0;
+        for (bound, count) in BUCKETS.iter().zip(self.buckets) {
+            cumulative += count;
+            writeln!(
+                text,
+                "{}_bucket{{{}{}le=\"{}\"}} {}",
+                name, labels, separator, bound, cumulative,
+            )
+            .unwrap();
+        }
+        writeln!(
+            text,
+            "{}_bucket{{{}{}le=\"+Inf\"}} {}",
+            name, labels, separator, self.count,
+        )
+        .unwrap();
+
+        let labels = if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{{{}}}", labels)
+        };
+        writeln!(text, "{}_sum{} {}", name, labels, self.sum).unwrap();
+        writeln!(text, "{}_count{} {}", name, labels, self.count).unwrap();
+    }
+}
+
+#[cfg(feature = "http")]
+fn header(text: &mut String, name: &str, kind: &str, help: &str) {
+    writeln!(text, "# HELP {} {}\n# TYPE {} {}", name, help, name, kind).unwrap();
+}
@@ -0,0 +1,131 @@
+use poise::{ChoiceParameter, serenity_prelude as serenity};
+
+use rusqlite::{
+    Connection,
+    types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef},
+};
+
+use serenity::{Colour, GuildId, RoleId};
+
+use crate::locale::{Language, tr};
+
+#[derive(Debug, ChoiceParameter, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Rule {
+    #[name = "Exact age"]
+    Exact,
+    #[name = "Every decade"]
+    Decade,
+    #[name = "Every multiple of an age"]
+    Multiple,
+}
+
+impl Rule {
+    /// Returns the age to store for this rule, given the age provided by the user (if any).
+    ///
+    /// Decades are just multiples of 10, so they don't need an age to be provided.
+    pub fn age(self, age: Option<u32>) -> Option<u32> {
+        match self {
+            Self::Decade => Some(10),
+            Self::Exact | Self::Multiple => age.filter(|&age| age > 0),
+        }
+    }
+}
+
+// NOTE: See the note on `Frequency`'s `ToSql` impl.
+impl ToSql for Rule {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let rule = match self {
+            Self::Exact => "exact",
+            Self::Decade => "decade",
+            Self::Multiple => "multiple",
+        };
+        Ok(rule.into())
+    }
+}
+
+impl FromSql for Rule {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "exact" => Ok(Self::Exact),
+            "decade" => Ok(Self::Decade),
+            "multiple" => Ok(Self::Multiple),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// A special age that is celebrated differently from other birthdays.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Milestone {
+    pub rule: Rule,
+    pub age: u32,
+    pub colour: Option<Colour>,
+    pub title: Option<String>,
+    pub role_id: Option<RoleId>,
+}
+
+impl Milestone {
+    pub fn matches(&self, age: u32) -> bool {
+        match self.rule {
+            Rule::Exact => age == self.age,
+            Rule::Decade | Rule::Multiple => age > 0 && age.is_multiple_of(self.age),
+        }
+    }
+
+    /// Returns the title to use for announcements, with `{age}` replaced by the celebrant's age.
+    pub fn title(&self, age: u32) -> Option<String> {
+        self.title
+            .as_ref()
+            .map(|title| title.replace("{age}", &age.to_string()))
+    }
+
+    /// Describes the ages that this milestone applies to.
+    pub fn describe(&self, language: Language) -> String {
+        match self.rule {
+            Rule::Exact => tr!(language, "milestone-exact", age = self.age),
+            Rule::Decade => tr!(language, "milestone-decade"),
+            Rule::Multiple => tr!(language, "milestone-multiple", age = self.age),
+        }
+    }
+}
+
+/// Retrieves all of a guild's milestones, ordered by precedence.
+///
+/// Exact ages take precedence over multiples, and larger multiples take precedence over smaller ones (so a
+/// milestone for every 50 years wins over one for every decade).
+pub fn load(conn: &Connection, guild_id: GuildId) -> rusqlite::Result<Vec<Milestone>> {
+    let query = "select rule, age, colour, title, role_id from milestones where guild_id = ?1 \
+                 order by rule = 'exact' desc, age desc";
+    let mut stmt = conn.prepare(query)?;
+    let mut rows = stmt.query((guild_id.get() as i64,))?; // NOTE: See the note in `birthday::get`.
+
+    let mut milestones = Vec::new();
+    while let Some(row) = rows.next()? {
+        milestones.push(Milestone {
+            rule: row.get(0)?,
+            age: row.get(1)?,
+            colour: row.get::<_, Option<u32>>(2)?.map(Colour::new),
+            title: row.get(3)?,
+            // NOTE: See the note in `birthday::get`.
+            role_id: row
+                .get::<_, Option<i64>>(4)?
+                .map(|id| RoleId::new(id as u64)),
+        });
+    }
+
+    Ok(milestones)
+}
+
+/// Finds the milestone with the highest precedence that applies to an age.
+pub fn find(milestones: &[Milestone], age: u32) -> Option<&Milestone> {
+    milestones.iter().find(|milestone| milestone.matches(age))
+}
+
+/// Parses a hex colour code such as `#FFD700`.
+pub fn parse_colour(colour: &str) -> Option<Colour> {
+    let hex = colour.strip_prefix('#').unwrap_or(colour);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().map(Colour::new)
+}
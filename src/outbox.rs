@@ -0,0 +1,104 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+use poise::serenity_prelude as serenity;
+
+use rusqlite::Connection;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use serenity::{ChannelId, GuildId, HttpError};
+
+use tracing::error;
+
+use crate::error::Result;
+
+/// How many times sending something is attempted before giving up on it.
+pub const MAX_ATTEMPTS: u32 = 8;
+
+const BASE_DELAY: TimeDelta = TimeDelta::minutes(1);
+
+const MAX_DELAY: TimeDelta = TimeDelta::hours(6);
+
+/// Returns whether a failure to send something is worth retrying.
+///
+/// Rate limits, server errors, and network errors usually go away on their own, while other errors (such as a
+/// missing channel or missing permissions) need someone to fix them first.
+pub fn is_transient(err: &serenity::Error) -> bool {
+    match err {
+        serenity::Error::Http(err) => match err.status_code() {
+            Some(status) => status == 429 || status.is_server_error(),
+            // NOTE: Requests that failed without a response timed out or couldn't connect.
+            None => matches!(err, HttpError::Request(_)),
+        },
+        serenity::Error::Io(_) | serenity::Error::Gateway(_) | serenity::Error::Tungstenite(_) => {
+            true
+        },
+        _ => false,
+    }
+}
+
+/// Returns how long to wait before retrying something that has failed to be sent `attempts` times.
+///
+/// The delay doubles with every attempt, starting at a minute and capped at six hours.
+pub fn backoff(attempts: u32) -> TimeDelta {
+    let factor = 2i32.saturating_pow(attempts.saturating_sub(1));
+    BASE_DELAY
+        .checked_mul(factor)
+        .map_or(MAX_DELAY, |delay| delay.min(MAX_DELAY))
+}
+
+/// Schedules something that failed to be sent at `now` to be retried after a backoff.
+#[allow(clippy::too_many_arguments)]
+pub fn schedule(
+    conn: &Connection,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    kind: &str,
+    item: &impl Serialize,
+    attempts: u32,
+    last_error: &str,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>> {
+    let payload = serde_json::to_string(item)?;
+    let next_attempt = now + backoff(attempts);
+    let query = "insert into outbox (guild_id, channel_id, kind, payload, attempts, next_attempt, \
+                 last_error) values (?1, ?2, ?3, ?4, ?5, ?6, ?7)";
+    conn.execute(
+        query,
+        (
+            // NOTE: See the note in `birthday::get`.
+            guild_id.get() as i64,
+            channel_id.get() as i64,
+            kind,
+            payload,
+            attempts,
+            next_attempt.fixed_offset(),
+            last_error,
+        ),
+    )?;
+    Ok(next_attempt)
+}
+
+/// Removes and returns everything of a kind that is due to be retried by `now`.
+///
+/// Anything that can no longer be read (such as after its format changed in an update) is discarded.
+pub fn take_due<T: DeserializeOwned>(
+    conn: &Connection,
+    kind: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<T>> {
+    // NOTE: Attempt times are always stored in UTC, so comparing them as text orders them correctly.
+    let query = "delete from outbox where kind = ?1 and next_attempt <= ?2 returning payload";
+    let mut stmt = conn.prepare(query)?;
+    let mut rows = stmt.query((kind, now.fixed_offset()))?;
+
+    let mut items = Vec::new();
+    while let Some(row) = rows.next()? {
+        let payload = row.get::<_, String>(0)?;
+        match serde_json::from_str(&payload) {
+            Ok(item) => items.push(item),
+            Err(err) => error!(?err, "discarding unreadable {} in outbox", kind),
+        }
+    }
+    Ok(items)
+}
@@ -0,0 +1,405 @@
+use std::{iter::Peekable, str::SplitWhitespace};
+
+use chrono::{DateTime, Datelike, FixedOffset, Offset, Timelike, Utc};
+
+use crate::{birthday, format::DateStyle, locale::Language};
+
+/// Which of the first two numbers is the day when a date is written with numbers only, such as `01/02/2000`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Order {
+    DayFirst,
+    MonthFirst,
+}
+
+impl Order {
+    /// Returns the order for a date style that a user or guild has chosen, or for a Discord locale if none was chosen.
+    ///
+    /// Only US English writes the month first by default, so other locales read the day first.
+    pub fn of(style: Option<DateStyle>, locale: Option<&str>) -> Self {
+        match (style, locale) {
+            (Some(DateStyle::Mdy), _) => Self::MonthFirst,
+            (Some(DateStyle::Dmy), _) => Self::DayFirst,
+            (_, Some("en-US")) => Self::MonthFirst,
+            _ => Self::DayFirst,
+        }
+    }
+}
+
+/// The parts of a date and time written as free text, ready for [`birthday::Birthday::from_parts`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Parsed {
+    /// The year, month, and day. The year is `None` if it was left out.
+    pub date: (Option<i32>, u32, u32),
+    /// The hour, minute, and second, which are 0 if left out.
+    pub time: (u32, u32, u32),
+    /// The timezone, if one was written.
+    pub timezone: Option<FixedOffset>,
+    /// The date with its day and month swapped, if it was written with numbers only and could be read either way.
+    pub alternative: Option<(Option<i32>, u32, u32)>,
+}
+
+/// Parses a date, optionally followed by a time and a UTC offset.
+///
+/// Dates can be written as `1998-03-14`, `14/03/1998`, `14 March 1998`, or `March 14, 1998`, with month names in
+/// English or in `language`. Times can be written as `10:30`, `10:30:15`, or `10:30 pm`, and offsets as `+05:30`,
+/// `UTC+05:30`, or `Z`. Returns `None` if the text doesn't match any of these.
+///
+/// Dates written with numbers only are read in `order`, unless one of the numbers can only be a day.
+pub fn parse(text: &str, language: Language, order: Order) -> Option<Parsed> {
+    let text = text.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(text) {
+        return Some(Parsed {
+            date: (Some(datetime.year()), datetime.month(), datetime.day()),
+            time: (datetime.hour(), datetime.minute(), datetime.second()),
+            timezone: Some(*datetime.offset()),
+            alternative: None,
+        });
+    }
+
+    let text = text.replace(',', " ");
+    let mut tokens = text.split_whitespace().peekable();
+    let (date, alternative) = match tokens.peek() {
+        // NOTE: Days can be written with a trailing period, such as `14. März`, which doesn't make them numeric dates.
+        Some(token)
+            if token.trim_end_matches('.').contains(['-', '/', '.'])
+                && token.starts_with(|char: char| char.is_ascii_digit()) =>
+        {
+            let parsed = numeric_date(token, order)?;
+            tokens.next();
+            parsed
+        },
+        _ => (written_date(&mut tokens, language)?, None),
+    };
+
+    let mut parsed = Parsed {
+        date,
+        time: (0, 0, 0),
+        timezone: None,
+        alternative,
+    };
+    let (mut has_time, mut has_meridiem) = (false, false);
+    for token in tokens {
+        let lower = token.to_lowercase();
+        if has_time
+            && !has_meridiem
+            && parsed.timezone.is_none()
+            && matches!(lower.as_str(), "am" | "a.m." | "pm" | "p.m.")
+        {
+            parsed.time.0 = meridiem(parsed.time.0, lower.starts_with('p'))?;
+            has_meridiem = true;
+        } else if !has_time
+            && parsed.timezone.is_none()
+            && token.starts_with(|char: char| char.is_ascii_digit())
+        {
+            let (hour, minute, second, pm) = clock_time(&lower)?;
+            parsed.time = (hour, minute, second);
+            if let Some(pm) = pm {
+                parsed.time.0 = meridiem(hour, pm)?;
+                has_meridiem = true;
+            }
+            has_time = true;
+        } else if parsed.timezone.is_none() {
+            parsed.timezone = Some(offset(token)?);
+        } else {
+            return None;
+        }
+    }
+
+    Some(parsed)
+}
+
+type Date = (Option<i32>, u32, u32);
+
+// NOTE: Dates such as `2000-01-02` start with the year, and are always read as year-month-day. Otherwise the year
+//       comes last (if at all), and only the order of the day and month can differ.
+fn numeric_date(token: &str, order: Order) -> Option<(Date, Option<Date>)> {
+    let separator = token.chars().find(|char| matches!(char, '-' | '/' | '.'))?;
+    let parts = token.split(separator).collect::<Vec<_>>();
+    if parts
+        .iter()
+        .any(|part| part.is_empty() || !part.chars().all(|char| char.is_ascii_digit()))
+    {
+        return None;
+    }
+
+    match parts[..] {
+        [year, month, day] if is_year(year) => Some((
+            (
+                Some(year.parse().ok()?),
+                month.parse().ok()?,
+                day.parse().ok()?,
+            ),
+            None,
+        )),
+        [first, second, year] if is_year(year) => disambiguate(
+            first.parse().ok()?,
+            second.parse().ok()?,
+            Some(year.parse().ok()?),
+            order,
+        ),
+        [first, second] if first.len() <= 2 && second.len() <= 2 => {
+            disambiguate(first.parse().ok()?, second.parse().ok()?, None, order)
+        },
+        _ => None,
+    }
+}
+
+fn disambiguate(
+    first: u32,
+    second: u32,
+    year: Option<i32>,
+    order: Order,
+) -> Option<(Date, Option<Date>)> {
+    let day_first = (year, second, first);
+    let month_first = (year, first, second);
+    match (first > 12, second > 12) {
+        (true, true) => None,
+        (true, false) => Some((day_first, None)),
+        (false, true) => Some((month_first, None)),
+        // NOTE: A date like `05/05` reads the same either way, so it isn't ambiguous.
+        (false, false) if first == second => Some((day_first, None)),
+        (false, false) => match order {
+            Order::DayFirst => Some((day_first, Some(month_first))),
+            Order::MonthFirst => Some((month_first, Some(day_first))),
+        },
+    }
+}
+
+fn written_date(tokens: &mut Peekable<SplitWhitespace<'_>>, language: Language) -> Option<Date> {
+    let (mut year, mut month, mut day) = (None, None, None);
+    while let Some(token) = tokens.peek() {
+        match (month_number(token, language), ordinal(token)) {
+            (Some(parsed), _) if month.is_none() => month = Some(parsed),
+            // NOTE: Years always come after the month, so that `14 1998` isn't read as a date.
+            _ if month.is_some() && year.is_none() && is_year(token) => year = token.parse().ok(),
+            (_, Some(parsed)) if day.is_none() => day = Some(parsed),
+            _ => break,
+        }
+        tokens.next();
+    }
+    Some((year, month?, day?))
+}
+
+fn is_year(token: &str) -> bool {
+    token.len() == 4 && token.chars().all(|char| char.is_ascii_digit())
+}
+
+// NOTE: Month names can be abbreviated to any prefix of at least three letters, such as `Mar` or `Sept`.
+fn month_number(token: &str, language: Language) -> Option<u32> {
+    let token = token.trim_end_matches('.').to_lowercase();
+    if token.chars().count() < 3 {
+        return None;
+    }
+    (1..=12).find(|&month| {
+        let english = chrono::Month::try_from(month as u8).map(|month| month.name().to_lowercase());
+        let localized = birthday::month_name(language, month).to_lowercase();
+        english.is_ok_and(|name| name.starts_with(&token)) || localized.starts_with(&token)
+    })
+}
+
+fn ordinal(token: &str) -> Option<u32> {
+    let digits = token
+        .trim_end_matches('.')
+        .trim_end_matches(|char: char| char.is_ascii_alphabetic());
+    if digits.is_empty() || digits.len() > 2 || !digits.chars().all(|char| char.is_ascii_digit()) {
+        return None;
+    }
+    let suffix = token[digits.len()..].trim_end_matches('.').to_lowercase();
+    if !matches!(suffix.as_str(), "" | "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+// NOTE: A trailing `am` or `pm` may be written without a space, such as `10:30pm`.
+fn clock_time(token: &str) -> Option<(u32, u32, u32, Option<bool>)> {
+    let (clock, suffix) = match token
+        .strip_suffix("am")
+        .or_else(|| token.strip_suffix("a.m."))
+    {
+        Some(clock) => (clock, Some(false)),
+        None => match token
+            .strip_suffix("pm")
+            .or_else(|| token.strip_suffix("p.m."))
+        {
+            Some(clock) => (clock, Some(true)),
+            None => (token, None),
+        },
+    };
+
+    let parts = clock
+        .split(':')
+        .map(|part| part.parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    match parts[..] {
+        [hour, minute] => Some((hour, minute, 0, suffix)),
+        [hour, minute, second] => Some((hour, minute, second, suffix)),
+        _ => None,
+    }
+}
+
+// NOTE: Invalid hours are left for `Birthday::from_parts` to reject, except for those that can't be on a 12-hour
+//       clock at all.
+fn meridiem(hour: u32, pm: bool) -> Option<u32> {
+    match (hour, pm) {
+        (0 | 13.., _) => None,
+        (12, false) => Some(0),
+        (12, true) => Some(12),
+        (hour, false) => Some(hour),
+        (hour, true) => Some(hour + 12),
+    }
+}
+
+fn offset(token: &str) -> Option<FixedOffset> {
+    let token = token.to_uppercase();
+    let offset = token
+        .strip_prefix("UTC")
+        .or_else(|| token.strip_prefix("GMT"))
+        .unwrap_or(&token);
+    match offset {
+        "" | "Z" => Some(Utc.fix()),
+        offset => offset.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offset(hours: i32, minutes: i32) -> FixedOffset {
+        FixedOffset::east_opt(hours * 60 * 60 + minutes * 60).unwrap()
+    }
+
+    #[test]
+    fn order_falls_back_to_locale() {
+        assert_eq!(
+            Order::of(Some(DateStyle::Mdy), Some("de")),
+            Order::MonthFirst
+        );
+        assert_eq!(
+            Order::of(Some(DateStyle::Dmy), Some("en-US")),
+            Order::DayFirst
+        );
+        assert_eq!(
+            Order::of(Some(DateStyle::Iso), Some("en-US")),
+            Order::MonthFirst
+        );
+        assert_eq!(Order::of(None, Some("en-US")), Order::MonthFirst);
+        assert_eq!(Order::of(None, Some("en-GB")), Order::DayFirst);
+        assert_eq!(Order::of(None, Some("de")), Order::DayFirst);
+        assert_eq!(Order::of(None, None), Order::DayFirst);
+    }
+
+    #[test]
+    fn common_formats_are_parsed() {
+        let cases = [
+            ("1998-03-14", (Some(1998), 3, 14), (0, 0, 0), None),
+            ("14/03/1998", (Some(1998), 3, 14), (0, 0, 0), None),
+            ("14.03.1998", (Some(1998), 3, 14), (0, 0, 0), None),
+            ("March 14 1998", (Some(1998), 3, 14), (0, 0, 0), None),
+            ("March 14th, 1998", (Some(1998), 3, 14), (0, 0, 0), None),
+            ("14 Mar", (None, 3, 14), (0, 0, 0), None),
+            ("14 mar. 1998", (Some(1998), 3, 14), (0, 0, 0), None),
+            (
+                "14 Mar 1998 10:30 +05:30",
+                (Some(1998), 3, 14),
+                (10, 30, 0),
+                Some(offset(5, 30)),
+            ),
+            (
+                "1998-03-14 10:30:15 pm UTC-08:00",
+                (Some(1998), 3, 14),
+                (22, 30, 15),
+                Some(offset(-8, 0)),
+            ),
+            (
+                "14 Mar 1998 12:05am Z",
+                (Some(1998), 3, 14),
+                (0, 5, 0),
+                Some(offset(0, 0)),
+            ),
+            (
+                "1998-03-14T10:30:00+05:30",
+                (Some(1998), 3, 14),
+                (10, 30, 0),
+                Some(offset(5, 30)),
+            ),
+        ];
+        for (text, date, time, timezone) in cases {
+            let parsed = parse(text, Language::English, Order::DayFirst);
+            assert_eq!(
+                parsed,
+                Some(Parsed {
+                    date,
+                    time,
+                    timezone,
+                    alternative: None,
+                }),
+                "{text}",
+            );
+        }
+    }
+
+    #[test]
+    fn ambiguous_dates_follow_the_order() {
+        let day_first = parse("01/02/2000", Language::English, Order::DayFirst).unwrap();
+        assert_eq!(day_first.date, (Some(2000), 2, 1));
+        assert_eq!(day_first.alternative, Some((Some(2000), 1, 2)));
+
+        let month_first = parse("01/02/2000", Language::English, Order::MonthFirst).unwrap();
+        assert_eq!(month_first.date, (Some(2000), 1, 2));
+        assert_eq!(month_first.alternative, Some((Some(2000), 2, 1)));
+    }
+
+    #[test]
+    fn unambiguous_dates_ignore_the_order() {
+        for order in [Order::DayFirst, Order::MonthFirst] {
+            let parsed = parse("14/03/1998", Language::English, order).unwrap();
+            assert_eq!(
+                (parsed.date, parsed.alternative),
+                ((Some(1998), 3, 14), None)
+            );
+            let parsed = parse("03/14/1998", Language::English, order).unwrap();
+            assert_eq!(
+                (parsed.date, parsed.alternative),
+                ((Some(1998), 3, 14), None)
+            );
+            let parsed = parse("05/05/1998", Language::English, order).unwrap();
+            assert_eq!(
+                (parsed.date, parsed.alternative),
+                ((Some(1998), 5, 5), None)
+            );
+        }
+    }
+
+    #[test]
+    fn localized_month_names_are_parsed() {
+        let parsed = parse("14. März 1998", Language::German, Order::DayFirst).unwrap();
+        assert_eq!(parsed.date, (Some(1998), 3, 14));
+        // NOTE: English names are always accepted.
+        let parsed = parse("14 March 1998", Language::German, Order::DayFirst).unwrap();
+        assert_eq!(parsed.date, (Some(1998), 3, 14));
+    }
+
+    #[test]
+    fn nonsense_is_rejected() {
+        let cases = [
+            "",
+            "tomorrow",
+            "14",
+            "March",
+            "31/31/1998",
+            "1998-03",
+            "14 Mar 1998 lunchtime",
+            "14 Mar 1998 13:00 pm",
+            "14 Mar 1998 10:30 +05:30 +01:00",
+        ];
+        for text in cases {
+            assert_eq!(
+                parse(text, Language::English, Order::DayFirst),
+                None,
+                "{text}"
+            );
+        }
+    }
+}
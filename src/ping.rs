@@ -0,0 +1,165 @@
+use poise::serenity_prelude as serenity;
+
+use rusqlite::Connection;
+
+use serenity::{CreateAllowedMentions, GuildId, RoleId, UserId};
+
+/// The template used for announcement messages when a guild hasn't provided its own.
+pub const DEFAULT_TEMPLATE: &str = "{users} {role} {here}";
+
+/// A guild's settings for who gets pinged when birthdays are announced.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Pings {
+    pub celebrants: bool,
+    pub role_id: Option<RoleId>,
+    pub here: bool,
+    pub everyone: bool,
+    pub template: Option<String>,
+}
+
+impl Default for Pings {
+    fn default() -> Self {
+        Self {
+            celebrants: true,
+            role_id: None,
+            here: false,
+            everyone: false,
+            template: None,
+        }
+    }
+}
+
+impl Pings {
+    /// Retrieves a guild's ping settings, falling back to the defaults if it hasn't set any.
+    pub fn load(conn: &Connection, guild_id: GuildId) -> rusqlite::Result<Self> {
+        let query =
+            "select celebrants, role_id, here, everyone, template from pings where guild_id = ?1";
+        let pings = conn
+            .prepare(query)?
+            // NOTE: See the note in `birthday::get`.
+            .query((guild_id.get() as i64,))?
+            .next()?
+            .map(|row| {
+                Ok::<_, rusqlite::Error>(Self {
+                    celebrants: row.get(0)?,
+                    // NOTE: See the note in `birthday::get`.
+                    role_id: row.get::<_, Option<i64>>(1)?.map(|id| RoleId::new(id as u64)),
+                    here: row.get(2)?,
+                    everyone: row.get(3)?,
+                    template: row.get(4)?,
+                })
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(pings)
+    }
+
+    /// Renders the message content for announcing the given users' birthdays, along with the mentions that
+    /// are allowed to notify anyone.
+    ///
+    /// The template can contain the `{users}`, `{role}`, and `{here}` placeholders, which are replaced with the
+    /// corresponding mentions if they are enabled (or with nothing otherwise). Any extra roles (such as those for
+    /// milestones) are always mentioned at the end.
+    pub fn render(
+        &self,
+        user_ids: &[UserId],
+        extra_role_ids: &[RoleId],
+    ) -> (String, CreateAllowedMentions) {
+        let template = self.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+
+        let users = if self.celebrants {
+            user_ids
+                .iter()
+                .map(|user_id| format!("<@{}>", user_id))
+                .collect::<Vec<_>>()
+                .join(" ")
+        } else {
+            String::new()
+        };
+        let role = self
+            .role_id
+            .map(|role_id| format!("<@&{}>", role_id))
+            .unwrap_or_default();
+        let here = if self.here { "@here" } else { "" };
+
+        let mut content = template
+            .replace("{users}", &users)
+            .replace("{role}", &role)
+            .replace("{here}", here);
+        for role_id in extra_role_ids {
+            content.push_str(&format!(" <@&{}>", role_id));
+        }
+        let content = content.trim();
+
+        // NOTE: Discord parses `@everyone` and `@here` under the same allowed mention type, so allowing `@here` would
+        //       also allow a template to ping `@everyone`. We place a zero-width space inside any literal `@everyone`
+        //       to prevent that unless an admin has explicitly allowed it. This has to happen after the placeholders
+        //       are filled in, since one that renders as nothing (such as in `@{role}everyone`) could otherwise join
+        //       the text around it into `@everyone`.
+        let content = if self.everyone {
+            content.to_owned()
+        } else {
+            content.replace("@everyone", "@\u{200B}everyone")
+        };
+
+        // NOTE: We always explicitly list the users and roles that can be pinged so that a template can never ping
+        //       anyone else.
+        let user_ids = if self.celebrants { user_ids } else { &[] };
+        let mentions = CreateAllowedMentions::new()
+            .users(user_ids.iter().copied())
+            .roles(
+                self.role_id
+                    .into_iter()
+                    .chain(extra_role_ids.iter().copied()),
+            )
+            .everyone(self.here || self.everyone);
+
+        (content, mentions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pings(template: &str, here: bool) -> Pings {
+        Pings {
+            celebrants: false,
+            role_id: None,
+            here,
+            everyone: false,
+            template: Some(template.to_owned()),
+        }
+    }
+
+    #[test]
+    fn templates_cant_ping_everyone() {
+        // NOTE: Celebrants and roles are disabled, so their placeholders render as nothing.
+        let templates = [
+            "@everyone",
+            "@{role}everyone",
+            "@{users}everyone",
+            "@{here}everyone",
+        ];
+        for template in templates {
+            // NOTE: Allowing `@here` also allows `@everyone` to ping, so that's the case that matters most.
+            for here in [true, false] {
+                let (content, _) = pings(template, here).render(&[UserId::new(1)], &[]);
+                assert!(
+                    !content.contains("@everyone"),
+                    "{template:?} rendered {content:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn everyone_can_be_allowed() {
+        let pings = Pings {
+            everyone: true,
+            ..pings("@{role}everyone", false)
+        };
+        let (content, _) = pings.render(&[], &[]);
+        assert_eq!(content, "@everyone");
+    }
+}
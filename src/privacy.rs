@@ -0,0 +1,85 @@
+use poise::serenity_prelude as serenity;
+
+use rusqlite::Connection;
+
+use serenity::{CacheHttp, GuildId, HttpError, UserId};
+
+use tracing::error;
+
+use crate::birthday::Birthday;
+
+// NOTE: This is the JSON error code that Discord responds with for users who aren't members of a guild.
+const UNKNOWN_MEMBER: isize = 10007;
+
+/// Retrieves whether a user allows their birthday to be exported outside of Discord, such as to calendars.
+///
+/// Birthdays are exported unless their users have opted out.
+pub fn is_exported(
+    conn: &Connection,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> rusqlite::Result<bool> {
+    let query = "select exported from privacy where guild_id = ?1 and user_id = ?2";
+    let exported = conn
+        .prepare(query)?
+        // NOTE: See the note in `birthday::get`.
+        .query((guild_id.get() as i64, user_id.get() as i64))?
+        .next()?
+        .map(|row| row.get(0))
+        .transpose()?
+        .unwrap_or(true);
+    Ok(exported)
+}
+
+/// Retrieves every birthday in a guild whose user allows it to be exported.
+pub fn exported_birthdays(
+    conn: &Connection,
+    guild_id: GuildId,
+) -> rusqlite::Result<Vec<(UserId, Birthday)>> {
+    let query = "select birthdays.user_id, birthday, coalesce(calendar, 'gregorian') from \
+                 birthdays left join privacy on birthdays.guild_id = privacy.guild_id and \
+                 birthdays.user_id = privacy.user_id left join calendars on birthdays.guild_id = \
+                 calendars.guild_id and birthdays.user_id = calendars.user_id where \
+                 birthdays.guild_id = ?1 and coalesce(exported, true) order by birthdays.user_id";
+    let mut stmt = conn.prepare(query)?;
+    let mut rows = stmt.query((guild_id.get() as i64,))?; // NOTE: See the note in `birthday::get`.
+
+    let mut birthdays = Vec::new();
+    while let Some(row) = rows.next()? {
+        // NOTE: See the note in `birthday::get`.
+        let user_id = row.get(0).map(|id: i64| UserId::new(id as u64))?;
+
+        // NOTE: See the note in `queue_birthday_announcements`.
+        let birthday = match row.get(1).and_then(|date| Ok(Birthday(date, row.get(2)?))) {
+            Ok(birthday) => birthday,
+            Err(err) => {
+                error!(?err, ?guild_id, "skipping invalid birthday of {}", user_id);
+                continue;
+            },
+        };
+        birthdays.push((user_id, birthday));
+    }
+
+    Ok(birthdays)
+}
+
+/// Retrieves the display names of the users whose birthdays are being exported.
+///
+/// Mentions aren't rendered outside of Discord, so exports use display names instead. Users who aren't members of
+/// the guild anymore are left out, while any other error (such as a rate limit) fails the whole export.
+pub async fn with_names(
+    cache_http: impl CacheHttp,
+    guild_id: GuildId,
+    birthdays: Vec<(UserId, Birthday)>,
+) -> serenity::Result<Vec<(UserId, String, Birthday)>> {
+    let mut named = Vec::new();
+    for (user_id, birthday) in birthdays {
+        match guild_id.member(&cache_http, user_id).await {
+            Ok(member) => named.push((user_id, member.display_name().to_owned(), birthday)),
+            Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(response)))
+                if response.error.code == UNKNOWN_MEMBER => {},
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(named)
+}
@@ -0,0 +1,461 @@
+use chrono::{DateTime, Datelike, FixedOffset, Months, NaiveDate};
+
+use icu_calendar::{
+    AnyCalendar,
+    AnyCalendarKind,
+    Date,
+    Iso,
+    options::{DateFromFieldsOptions, Overflow},
+    types::DateFields,
+};
+
+use poise::ChoiceParameter;
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use serde::{Deserialize, Serialize};
+
+/// A calendar that a date can recur in every year.
+///
+/// Dates are always stored in the Gregorian calendar, and converted to other calendars to find their
+/// anniversaries. Lunar and lunisolar years have a different length, so their anniversaries fall on a different
+/// Gregorian date every year.
+#[derive(
+    Debug,
+    ChoiceParameter,
+    Copy,
+    Clone,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Calendar {
+    #[default]
+    #[name = "Gregorian"]
+    Gregorian,
+    #[name = "Chinese"]
+    Chinese,
+    #[name = "Hebrew"]
+    Hebrew,
+    #[name = "Hijri"]
+    Hijri,
+}
+
+impl Calendar {
+    /// Returns the most recent anniversary of `date` in the calendar that is not after `now`, along with how many
+    /// of the calendar's years have passed since `date`, or `None` if `date` is after `now`.
+    pub fn last(
+        self,
+        date: DateTime<FixedOffset>,
+        now: DateTime<FixedOffset>,
+    ) -> Option<(u32, DateTime<FixedOffset>)> {
+        let Some(kind) = self.kind() else {
+            return last_anniversary(date, now);
+        };
+        if date > now {
+            return None;
+        }
+
+        // NOTE: The number of years is only estimated from the calendar years of both dates, since the anniversary
+        //       in the current calendar year may not have been reached yet.
+        let years = estimate_years(kind, date, now)?;
+        (years.saturating_sub(1)..=years + 1)
+            .rev()
+            .filter_map(|years| Some((years, converted_anniversary(kind, date, years)?)))
+            .find(|&(_, anniversary)| anniversary <= now)
+    }
+
+    /// Returns the earliest anniversary of `date` in the calendar that is not before `now`, or `None` if it can't
+    /// be represented.
+    pub fn next(
+        self,
+        date: DateTime<FixedOffset>,
+        now: DateTime<FixedOffset>,
+    ) -> Option<DateTime<FixedOffset>> {
+        let Some(kind) = self.kind() else {
+            return next(date, now);
+        };
+        if date >= now {
+            return Some(date);
+        }
+
+        let years = estimate_years(kind, date, now)?;
+        (years.saturating_sub(1)..=years + 2)
+            .filter_map(|years| converted_anniversary(kind, date, years))
+            .find(|&anniversary| anniversary >= now)
+    }
+
+    fn kind(self) -> Option<AnyCalendarKind> {
+        match self {
+            Self::Gregorian => None,
+            Self::Chinese => Some(AnyCalendarKind::Chinese),
+            Self::Hebrew => Some(AnyCalendarKind::Hebrew),
+            // NOTE: The Umm al-Qura calendar is the official Hijri calendar of Saudi Arabia, and follows the
+            //       observed calendar much more closely than the tabular ones.
+            Self::Hijri => Some(AnyCalendarKind::HijriUmmAlQura),
+        }
+    }
+}
+
+// NOTE: We store calendars as text for the same reasons as `digest::Frequency`.
+impl ToSql for Calendar {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let calendar = match self {
+            Self::Gregorian => "gregorian",
+            Self::Chinese => "chinese",
+            Self::Hebrew => "hebrew",
+            Self::Hijri => "hijri",
+        };
+        Ok(calendar.into())
+    }
+}
+
+impl FromSql for Calendar {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "gregorian" => Ok(Self::Gregorian),
+            "chinese" => Ok(Self::Chinese),
+            "hebrew" => Ok(Self::Hebrew),
+            "hijri" => Ok(Self::Hijri),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// Returns the most recent anniversary of `date` that is not after `now`, or `None` if `date` is after `now`.
+pub fn last(
+    date: DateTime<FixedOffset>,
+    now: DateTime<FixedOffset>,
+) -> Option<DateTime<FixedOffset>> {
+    last_anniversary(date, now).map(|(_, last)| last)
+}
+
+/// Returns the earliest anniversary of `date` that is not before `now`, or `None` if it is too far in the future to
+/// be represented.
+pub fn next(
+    date: DateTime<FixedOffset>,
+    now: DateTime<FixedOffset>,
+) -> Option<DateTime<FixedOffset>> {
+    // NOTE: A date that hasn't happened yet is its own next anniversary.
+    if date >= now {
+        return Some(date);
+    }
+    match last_anniversary(date, now)? {
+        (_, last) if last == now => Some(last),
+        (years, _) => anniversary(date, years + 1),
+    }
+}
+
+fn last_anniversary(
+    date: DateTime<FixedOffset>,
+    now: DateTime<FixedOffset>,
+) -> Option<(u32, DateTime<FixedOffset>)> {
+    // NOTE: We can't just use `date.with_year(now.year())` due to edge cases that might create
+    //       invalid dates, such as Feb 29 in a non-leap year. Instead, we figure out how many years
+    //       have passed since the date, then add that many years to it. This produces a date
+    //       that is either in the year before the current date, or in the same year as the current date.
+    //       In the former case, the anniversary has not happened yet this year, while in the latter case, it
+    //       has already passed this year.
+    let years = now.years_since(date)?;
+
+    // NOTE: `years_since` only counts a year once the same month and day have been reached, but adding months
+    //       to Feb 29 clamps to Feb 28 in non-leap years. Without this check, such dates would be one year
+    //       behind on Feb 28 itself, and their anniversary would never fall within an announcement interval.
+    let next = anniversary(date, years + 1)?;
+    if next <= now {
+        Some((years + 1, next))
+    } else {
+        Some((years, anniversary(date, years)?))
+    }
+}
+
+fn to_calendar(kind: AnyCalendarKind, date: NaiveDate) -> Option<Date<AnyCalendar>> {
+    let iso = Date::try_new_iso(date.year(), date.month() as u8, date.day() as u8).ok()?;
+    Some(iso.to_calendar(AnyCalendar::new(kind)))
+}
+
+fn estimate_years(
+    kind: AnyCalendarKind,
+    date: DateTime<FixedOffset>,
+    now: DateTime<FixedOffset>,
+) -> Option<u32> {
+    let born = to_calendar(kind, date.date_naive())?;
+    let today = to_calendar(kind, now.with_timezone(date.offset()).date_naive())?;
+    u32::try_from(today.year().extended_year() - born.year().extended_year()).ok()
+}
+
+// NOTE: Months and days that don't exist in a year are constrained to the nearest one that does. Leap months fall
+//       back to the regular month they repeat (such as Adar I to Adar in Hebrew common years), and the 30th day of a
+//       short month falls on its 29th day, much like Feb 29 falls on Feb 28 in the Gregorian calendar.
+fn converted_anniversary(
+    kind: AnyCalendarKind,
+    date: DateTime<FixedOffset>,
+    years: u32,
+) -> Option<DateTime<FixedOffset>> {
+    let born = to_calendar(kind, date.date_naive())?;
+
+    let mut fields = DateFields::default();
+    fields.extended_year = Some(
+        born.year()
+            .extended_year()
+            .checked_add(i32::try_from(years).ok()?)?,
+    );
+    fields.month = Some(born.month().to_input());
+    fields.day = Some(born.day_of_month().0);
+    let mut options = DateFromFieldsOptions::default();
+    options.overflow = Some(Overflow::Constrain);
+
+    let anniversary = Date::try_from_fields(fields, options, AnyCalendar::new(kind))
+        .ok()?
+        .to_calendar(Iso);
+    let anniversary = NaiveDate::from_ymd_opt(
+        anniversary.year().extended_year(),
+        anniversary.month().ordinal.into(),
+        anniversary.day_of_month().0.into(),
+    )?;
+
+    // NOTE: Fixed offsets never skip or repeat times, so this only fails at the edges of what can be represented.
+    anniversary
+        .and_time(date.time())
+        .and_local_timezone(*date.offset())
+        .single()
+}
+
+// NOTE: This only overflows for dates hundreds of thousands of years in the future.
+fn anniversary(date: DateTime<FixedOffset>, years: u32) -> Option<DateTime<FixedOffset>> {
+    date.checked_add_months(Months::new(years.checked_mul(12)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Datelike, FixedOffset, TimeDelta};
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap()
+    }
+
+    #[test]
+    fn last_is_this_year_once_passed() {
+        let date = at("2000-03-15T00:00:00Z");
+        assert_eq!(
+            last(date, at("2024-06-01T00:00:00Z")),
+            Some(at("2024-03-15T00:00:00Z")),
+        );
+        assert_eq!(
+            next(date, at("2024-06-01T00:00:00Z")),
+            Some(at("2025-03-15T00:00:00Z")),
+        );
+    }
+
+    #[test]
+    fn last_is_previous_year_before_passed() {
+        let date = at("2000-03-15T00:00:00Z");
+        assert_eq!(
+            last(date, at("2024-01-01T00:00:00Z")),
+            Some(at("2023-03-15T00:00:00Z")),
+        );
+        assert_eq!(
+            next(date, at("2024-01-01T00:00:00Z")),
+            Some(at("2024-03-15T00:00:00Z")),
+        );
+    }
+
+    #[test]
+    fn anniversary_at_now_is_both_last_and_next() {
+        let date = at("2000-03-15T12:30:00+05:30");
+        let now = at("2024-03-15T12:30:00+05:30");
+        assert_eq!(last(date, now), Some(now));
+        assert_eq!(next(date, now), Some(now));
+    }
+
+    #[test]
+    fn leap_day_falls_on_feb_28_in_common_years() {
+        let date = at("2000-02-29T00:00:00Z");
+        assert_eq!(
+            last(date, at("2023-03-01T00:00:00Z")),
+            Some(at("2023-02-28T00:00:00Z")),
+        );
+        assert_eq!(
+            next(date, at("2023-01-01T00:00:00Z")),
+            Some(at("2023-02-28T00:00:00Z")),
+        );
+    }
+
+    #[test]
+    fn leap_day_falls_on_feb_29_in_leap_years() {
+        let date = at("2000-02-29T00:00:00Z");
+        assert_eq!(
+            next(date, at("2024-01-01T00:00:00Z")),
+            Some(at("2024-02-29T00:00:00Z")),
+        );
+        assert_eq!(
+            last(date, at("2024-03-01T00:00:00Z")),
+            Some(at("2024-02-29T00:00:00Z")),
+        );
+    }
+
+    // NOTE: See the note in `last_anniversary`.
+    #[test]
+    fn leap_day_is_reached_on_feb_28() {
+        let date = at("2000-02-29T00:00:00Z");
+        let now = at("2023-02-28T00:00:00Z");
+        assert_eq!(last(date, now), Some(now));
+    }
+
+    #[test]
+    fn future_dates_have_no_last_anniversary() {
+        let date = at("2030-01-01T00:00:00Z");
+        let now = at("2024-01-01T00:00:00Z");
+        assert_eq!(last(date, now), None);
+        assert_eq!(next(date, now), Some(date));
+    }
+
+    #[test]
+    fn calendars_convert_every_year() {
+        // NOTE: Each birthday falls on a holiday, so its anniversaries can be checked against the holiday's
+        //       published Gregorian dates.
+        let cases = [
+            // Chinese New Year (month 1, day 1)
+            (Calendar::Chinese, "2000-02-05", "2023-01-01", "2023-01-22"),
+            (Calendar::Chinese, "2000-02-05", "2024-01-01", "2024-02-10"),
+            (Calendar::Chinese, "2000-02-05", "2025-01-01", "2025-01-29"),
+            // Mid-Autumn Festival (month 8, day 15)
+            (Calendar::Chinese, "2000-09-12", "2023-01-01", "2023-09-29"),
+            (Calendar::Chinese, "2000-09-12", "2024-01-01", "2024-09-17"),
+            (Calendar::Chinese, "2000-09-12", "2025-01-01", "2025-10-06"),
+            // Rosh Hashanah (1 Tishrei)
+            (Calendar::Hebrew, "2000-09-30", "2023-01-01", "2023-09-16"),
+            (Calendar::Hebrew, "2000-09-30", "2024-01-01", "2024-10-03"),
+            (Calendar::Hebrew, "2000-09-30", "2025-01-01", "2025-09-23"),
+            // Passover (15 Nisan)
+            (Calendar::Hebrew, "2000-04-20", "2024-01-01", "2024-04-23"),
+            (Calendar::Hebrew, "2000-04-20", "2025-01-01", "2025-04-13"),
+            // Eid al-Fitr (1 Shawwal)
+            (Calendar::Hijri, "2023-04-21", "2024-01-01", "2024-04-10"),
+            (Calendar::Hijri, "2023-04-21", "2025-01-01", "2025-03-30"),
+            (Calendar::Hijri, "2023-04-21", "2025-04-01", "2026-03-20"),
+            // The Gregorian calendar is unchanged
+            (
+                Calendar::Gregorian,
+                "2000-02-05",
+                "2024-01-01",
+                "2024-02-05",
+            ),
+        ];
+        for (calendar, date, now, expected) in cases {
+            let date = at(&format!("{date}T00:00:00+08:00"));
+            let now = at(&format!("{now}T00:00:00+08:00"));
+            let expected = at(&format!("{expected}T00:00:00+08:00"));
+            assert_eq!(
+                calendar.next(date, now),
+                Some(expected),
+                "{calendar:?} {date} {now}"
+            );
+        }
+    }
+
+    #[test]
+    fn calendars_count_their_own_years() {
+        let date = at("1990-01-01T00:00:00Z");
+        let now = at("2024-01-01T00:00:00Z");
+        assert_eq!(Calendar::Gregorian.last(date, now).unwrap().0, 34);
+        // NOTE: Lunar years are about 11 days shorter, so more of them have passed.
+        assert_eq!(Calendar::Hijri.last(date, now).unwrap().0, 35);
+        // NOTE: The date is in the last month of the Chinese year 1989, which hasn't come round again in 2023 yet.
+        assert_eq!(Calendar::Chinese.last(date, now).unwrap().0, 33);
+    }
+
+    #[test]
+    fn leap_months_fall_back_to_the_regular_month() {
+        // NOTE: 20 Adar I 5784 only exists in leap years, so it falls on 20 Adar in 5785.
+        let date = at("2024-02-29T00:00:00Z");
+        let now = at("2024-06-01T00:00:00Z");
+        assert_eq!(
+            Calendar::Hebrew.next(date, now),
+            Some(at("2025-03-20T00:00:00Z")),
+        );
+    }
+
+    #[test]
+    fn calendars_keep_the_time_and_offset() {
+        let date = at("2000-02-05T10:30:00-05:00");
+        let now = at("2024-01-01T00:00:00Z");
+        assert_eq!(
+            Calendar::Chinese.next(date, now),
+            Some(at("2024-02-10T10:30:00-05:00")),
+        );
+    }
+
+    fn datetime() -> impl Strategy<Value = DateTime<FixedOffset>> {
+        // NOTE: Roughly 1900 to 2100, in any offset from UTC-12:00 to UTC+14:00.
+        (-2_208_988_800i64..4_102_444_800, -12 * 60..=14 * 60).prop_map(|(secs, minutes)| {
+            let offset = FixedOffset::east_opt(minutes * 60).unwrap();
+            DateTime::from_timestamp(secs, 0)
+                .unwrap()
+                .with_timezone(&offset)
+        })
+    }
+
+    fn is_anniversary(date: DateTime<FixedOffset>, anniversary: DateTime<FixedOffset>) -> bool {
+        let same_day = (anniversary.month(), anniversary.day()) == (date.month(), date.day());
+        let leap_day = (date.month(), date.day()) == (2, 29)
+            && (anniversary.month(), anniversary.day()) == (2, 28);
+        (same_day || leap_day) && anniversary.time() == date.time()
+    }
+
+    proptest! {
+        #[test]
+        fn last_and_next_surround_now(date in datetime(), secs in 0i64..200 * 366 * 24 * 60 * 60) {
+            let now = date + TimeDelta::seconds(secs);
+            let last = last(date, now).unwrap();
+            let next = next(date, now).unwrap();
+
+            prop_assert!(last <= now);
+            prop_assert!(now <= next);
+            prop_assert!(next - last <= TimeDelta::days(366));
+            prop_assert!(is_anniversary(date, last));
+            prop_assert!(is_anniversary(date, next));
+        }
+
+        #[test]
+        fn last_is_the_most_recent(date in datetime(), secs in 0i64..200 * 366 * 24 * 60 * 60) {
+            let now = date + TimeDelta::seconds(secs);
+            let last = last(date, now).unwrap();
+
+            // NOTE: The anniversary after the last one must be after now, or it would be the last one instead.
+            let after = next(date, last + TimeDelta::seconds(1)).unwrap();
+            prop_assert!(after > now);
+        }
+
+        #[test]
+        fn calendars_surround_now(
+            date in datetime(),
+            secs in 0i64..100 * 366 * 24 * 60 * 60,
+            calendar in prop_oneof![
+                Just(Calendar::Chinese),
+                Just(Calendar::Hebrew),
+                Just(Calendar::Hijri),
+            ],
+        ) {
+            let now = date + TimeDelta::seconds(secs);
+            let (_, last) = calendar.last(date, now).unwrap();
+            let next = calendar.next(date, now).unwrap();
+
+            prop_assert!(last <= now);
+            prop_assert!(now <= next);
+            // NOTE: Lunisolar years with a leap month are up to 385 days long.
+            prop_assert!(next - last <= TimeDelta::days(386));
+            prop_assert_eq!(last.time(), date.time());
+        }
+    }
+}
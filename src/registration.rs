@@ -0,0 +1,70 @@
+use poise::serenity_prelude as serenity;
+
+use serenity::{GuildId, GuildPagination, Http};
+
+use tracing::info;
+
+use crate::{
+    error::{Error, Result},
+    state::State,
+};
+
+// NOTE: This is the maximum number of guilds that Discord returns at once.
+const GUILDS_PER_PAGE: u64 = 200;
+
+/// Registers the bot's commands with Discord.
+///
+/// Global commands can take a while to be updated, so during development they can be registered in dev guilds
+/// instead, where they're updated instantly. Clearing stale commands removes the commands that the other kind of
+/// registration would have left behind, which is every global command when using dev guilds, and every guild's
+/// commands otherwise.
+pub async fn register(
+    http: &Http,
+    commands: &[poise::Command<State, Error>],
+    dev_guilds: &[GuildId],
+    clear_stale: bool,
+) -> Result<()> {
+    if dev_guilds.is_empty() {
+        poise::builtins::register_globally(http, commands).await?;
+        info!("registered {} command(s) globally", commands.len());
+
+        if clear_stale {
+            let guild_ids = guilds(http).await?;
+            for &guild_id in &guild_ids {
+                guild_id.set_commands(http, Vec::new()).await?;
+            }
+            info!("cleared commands in {} guild(s)", guild_ids.len());
+        }
+    } else {
+        for &guild_id in dev_guilds {
+            poise::builtins::register_in_guild(http, commands, guild_id).await?;
+            info!("registered {} command(s) in {}", commands.len(), guild_id);
+        }
+
+        if clear_stale {
+            serenity::Command::set_global_commands(http, Vec::new()).await?;
+            info!("cleared global commands");
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns every guild that the bot is in.
+async fn guilds(http: &Http) -> Result<Vec<GuildId>> {
+    let mut guild_ids = Vec::new();
+    let mut after = None;
+    loop {
+        let page = http
+            .get_guilds(after.map(GuildPagination::After), Some(GUILDS_PER_PAGE))
+            .await?;
+        guild_ids.extend(page.iter().map(|guild| guild.id));
+
+        if page.len() < GUILDS_PER_PAGE as usize {
+            break;
+        }
+        after = page.last().map(|guild| guild.id);
+    }
+
+    Ok(guild_ids)
+}
@@ -0,0 +1,133 @@
+use std::{
+    mem,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{signal, sync::watch, task::JoinHandle, time};
+
+use tracing::{error, info, warn};
+
+/// How long to wait for queued announcements to be sent before persisting them instead.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How far along the bot is in shutting down.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Phase {
+    Running,
+    /// New commands are rejected, background tasks stop queueing new work, and queues are emptied.
+    Draining,
+    /// Anything still queued is persisted for the next start instead of being sent.
+    Stopped,
+}
+
+/// Coordinates shutting down the bot's background tasks.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    phase: Arc<watch::Sender<Phase>>,
+    tasks: Arc<Mutex<Vec<JoinHandle<()>>>>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self {
+            phase: Arc::new(watch::Sender::new(Phase::Running)),
+            tasks: Arc::default(),
+        }
+    }
+}
+
+impl Shutdown {
+    pub fn phase(&self) -> Phase {
+        *self.phase.borrow()
+    }
+
+    /// Moves on to a later phase. Phases are never moved back to.
+    pub fn advance(&self, phase: Phase) {
+        self.phase.send_if_modified(|current| {
+            let advanced = *current < phase;
+            if advanced {
+                *current = phase;
+            }
+            advanced
+        });
+    }
+
+    /// Waits until the given phase (or a later one) is reached.
+    pub async fn reached(&self, phase: Phase) {
+        let mut rx = self.phase.subscribe();
+        // NOTE: This only fails if the sender is dropped, which can't happen while `self` is alive.
+        let _ = rx.wait_for(|current| *current >= phase).await;
+    }
+
+    /// Spawns a task that is waited on when shutting down.
+    pub fn spawn(&self, task: impl Future<Output = ()> + Send + 'static) {
+        let handle = tokio::spawn(task);
+        // PANICS: Pushing and taking handles never panics, so the lock can't be poisoned.
+        self.tasks.lock().unwrap().push(handle);
+    }
+
+    /// Waits for every spawned task to finish, including tasks spawned while waiting.
+    async fn join(&self) {
+        loop {
+            // PANICS: See the note in `spawn`.
+            let tasks = mem::take(&mut *self.tasks.lock().unwrap());
+            if tasks.is_empty() {
+                break;
+            }
+            for task in tasks {
+                if let Err(err) = task.await {
+                    error!(?err, "background task failed while shutting down");
+                }
+            }
+        }
+    }
+}
+
+/// Shuts the bot's background tasks down once it receives SIGINT or SIGTERM.
+///
+/// Queues are given some time to drain, after which (or after a second signal) anything left in them is persisted.
+#[tracing::instrument(skip_all)]
+pub async fn on_signal(shutdown: Shutdown) {
+    wait_for_signal().await;
+    info!("shutting down, press Ctrl+C again to skip sending queued announcements");
+    shutdown.advance(Phase::Draining);
+
+    tokio::select! {
+        _ = shutdown.join() => {},
+        _ = time::sleep(DRAIN_TIMEOUT) => warn!("timed out sending queued announcements"),
+        _ = wait_for_signal() => warn!("skipped sending queued announcements"),
+    }
+
+    // NOTE: The tasks left running persist whatever is still queued once they see this, and then finish.
+    shutdown.advance(Phase::Stopped);
+    shutdown.join().await;
+}
+
+async fn wait_for_signal() {
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            },
+            Err(err) => {
+                error!(?err, "failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            },
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        result = signal::ctrl_c() => {
+            if let Err(err) = result {
+                error!(?err, "failed to listen for SIGINT");
+                std::future::pending::<()>().await;
+            }
+        },
+        _ = terminate => {},
+    }
+}
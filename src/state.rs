@@ -1,12 +1,109 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{Arc, LockResult, Mutex, MutexGuard, PoisonError, RwLock},
+    time::Instant,
+};
 
 use rusqlite::Connection;
 
+use tokio::sync::{Notify, mpsc::Sender};
+
+use crate::{
+    background::changelog::Post,
+    clock::Clock,
+    logging,
+    metrics::Metrics,
+    shutdown::Shutdown,
+};
+
 #[derive(Debug, Clone)]
 pub struct State {
     // NOTE: SQLite connections cannot be shared across threads. However, the bot maintains a single connection
     //       that may be accessed from multiple threads, so we need `Arc` + `Mutex` here. This unfortunately means
     //       that only a single command can be executed at any given time, since each query would lock the database
     //       connection. Fortunately, SQLite is fast and the bot is intended for mostly personal use, so this is OK.
-    pub conn: Arc<Mutex<Connection>>,
+    pub conn: Arc<SharedConnection>,
+    // NOTE: Whether the privileged `GUILD_MEMBERS` intent is requested, and thus whether members are cached.
+    pub members_intent: bool,
+    pub metrics: Arc<Metrics>,
+    pub shutdown: Shutdown,
+    pub clock: Arc<dyn Clock>,
+    pub started: Instant,
+    // NOTE: Notified to scan for birthdays right away instead of waiting for the next scheduled scan.
+    pub scan: Arc<Notify>,
+    // NOTE: The changelog is kept around so that it can be posted again, and replaced when reloading the config.
+    pub changelog: Arc<RwLock<Option<String>>>,
+    pub updates: Sender<Post>,
+    pub log_filter: logging::Filter,
+}
+
+/// A database connection shared between threads, which keeps track of how long it is waited on and held for.
+#[derive(Debug)]
+pub struct SharedConnection {
+    conn: Mutex<Connection>,
+    metrics: Arc<Metrics>,
+}
+
+impl SharedConnection {
+    pub fn new(conn: Connection, metrics: Arc<Metrics>) -> Self {
+        Self {
+            conn: Mutex::new(conn),
+            metrics,
+        }
+    }
+
+    /// Acquires the connection, blocking until it is available.
+    pub fn lock(&self) -> LockResult<ConnectionGuard<'_>> {
+        let started = Instant::now();
+        let result = self.conn.lock();
+        self.metrics.record_lock_wait(started.elapsed());
+
+        let guard = |guard| ConnectionGuard {
+            guard,
+            metrics: &self.metrics,
+            acquired: Instant::now(),
+        };
+        result
+            .map(guard)
+            .map_err(|err| PoisonError::new(guard(err.into_inner())))
+    }
+
+    /// Closes the connection, flushing anything that hasn't been written to the database yet.
+    pub fn close(self) -> rusqlite::Result<()> {
+        // NOTE: A poisoned connection is still worth closing properly, since SQLite rolls back unfinished
+        //       transactions itself.
+        let conn = self
+            .conn
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        conn.close().map_err(|(_, err)| err)
+    }
+}
+
+// NOTE: Queries are timed by how long the connection is held for, since that's also how long everything else has to
+//       wait to use it.
+pub struct ConnectionGuard<'a> {
+    guard: MutexGuard<'a, Connection>,
+    metrics: &'a Metrics,
+    acquired: Instant,
+}
+
+impl Deref for ConnectionGuard<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl DerefMut for ConnectionGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl Drop for ConnectionGuard<'_> {
+    fn drop(&mut self) {
+        self.metrics.record_query(self.acquired.elapsed());
+    }
 }
@@ -0,0 +1,46 @@
+use poise::serenity_prelude as serenity;
+
+use rusqlite::Connection;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use serenity::GuildId;
+
+use tracing::error;
+
+use crate::error::Result;
+
+/// Stores something that couldn't be delivered before shutting down, so that it can be delivered on the next start.
+///
+/// The `kind` distinguishes between different things that can be undelivered, such as announcements and changelogs.
+pub fn store(
+    conn: &Connection,
+    guild_id: GuildId,
+    kind: &str,
+    item: &impl Serialize,
+) -> Result<()> {
+    let payload = serde_json::to_string(item)?;
+    let query = "insert into undelivered (guild_id, kind, payload) values (?1, ?2, ?3)";
+    // NOTE: See the note in `birthday::get`.
+    conn.execute(query, (guild_id.get() as i64, kind, payload))?;
+    Ok(())
+}
+
+/// Removes and returns everything of a kind that was stored as undelivered.
+///
+/// Anything that can no longer be read (such as after its format changed in an update) is discarded.
+pub fn take<T: DeserializeOwned>(conn: &Connection, kind: &str) -> Result<Vec<T>> {
+    let query = "delete from undelivered where kind = ?1 returning payload";
+    let mut stmt = conn.prepare(query)?;
+    let mut rows = stmt.query((kind,))?;
+
+    let mut items = Vec::new();
+    while let Some(row) = rows.next()? {
+        let payload = row.get::<_, String>(0)?;
+        match serde_json::from_str(&payload) {
+            Ok(item) => items.push(item),
+            Err(err) => error!(?err, "discarding unreadable undelivered {}", kind),
+        }
+    }
+    Ok(items)
+}
@@ -0,0 +1,97 @@
+use std::fmt::Write;
+
+use chrono::TimeDelta;
+
+use poise::serenity_prelude as serenity;
+
+use rusqlite::Connection;
+
+use serenity::{CreateEmbed, CreateEmbedFooter, GuildId, UserId};
+
+use crate::{
+    announcement,
+    locale::{Language, tr},
+};
+
+/// How long before someone's birthday members can start leaving wishes for them.
+pub const WINDOW: TimeDelta = TimeDelta::days(7);
+
+// NOTE: Discord limits embeds to 25 fields and 6000 characters in total, so we split birthday cards into pages
+//       well below those limits.
+const WISHES_PER_PAGE: usize = 10;
+const CHARS_PER_PAGE: usize = 4000;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Wish {
+    pub author_id: UserId,
+    pub message: String,
+}
+
+/// Retrieves all the wishes left for a member, in the order they were left.
+pub fn load(conn: &Connection, guild_id: GuildId, user_id: UserId) -> rusqlite::Result<Vec<Wish>> {
+    let query =
+        "select author_id, message from wishes where guild_id = ?1 and user_id = ?2 order by rowid";
+    let mut stmt = conn.prepare(query)?;
+    // NOTE: See the note in `birthday::get`.
+    let mut rows = stmt.query((guild_id.get() as i64, user_id.get() as i64))?;
+
+    let mut wishes = Vec::new();
+    while let Some(row) = rows.next()? {
+        wishes.push(Wish {
+            // NOTE: See the note in `birthday::get`.
+            author_id: row.get(0).map(|id: i64| UserId::new(id as u64))?,
+            message: row.get(1)?,
+        });
+    }
+
+    Ok(wishes)
+}
+
+/// Deletes all the wishes left for a member, usually after they have been delivered.
+pub fn clear(conn: &Connection, guild_id: GuildId, user_id: UserId) -> rusqlite::Result<usize> {
+    let query = "delete from wishes where guild_id = ?1 and user_id = ?2";
+    // NOTE: See the note in `birthday::get`.
+    conn.execute(query, (guild_id.get() as i64, user_id.get() as i64))
+}
+
+/// Compiles wishes into the pages of a birthday card.
+pub fn card(language: Language, guild_name: &str, wishes: &[Wish]) -> Vec<CreateEmbed> {
+    let mut pages = vec![Vec::new()];
+    let mut chars = 0;
+    for wish in wishes {
+        let mut field = String::new();
+        writeln!(&mut field, "{}", wish.message).unwrap();
+        write!(&mut field, "\u{2014} <@{}>", wish.author_id).unwrap();
+
+        // PANICS: There is always at least one page.
+        let page = pages.last_mut().unwrap();
+        if page.len() >= WISHES_PER_PAGE || chars + field.len() > CHARS_PER_PAGE {
+            chars = field.len();
+            pages.push(vec![field]);
+        } else {
+            chars += field.len();
+            page.push(field);
+        }
+    }
+
+    let len = pages.len();
+    pages
+        .into_iter()
+        .enumerate()
+        .map(|(idx, fields)| {
+            let embed = announcement(&tr!(language, "card"))
+                .description(tr!(language, "card-description", guild = guild_name))
+                .fields(fields.into_iter().map(|field| ("\u{200B}", field, false)));
+            if len > 1 {
+                embed.footer(CreateEmbedFooter::new(tr!(
+                    language,
+                    "card-page",
+                    page = idx + 1,
+                    pages = len,
+                )))
+            } else {
+                embed
+            }
+        })
+        .collect()
+}